@@ -7,14 +7,36 @@ pub use world::World;
 
 // also export world components
 pub use world::bestiary::Bestiary;
+pub use world::biome::{Biome, BiomeMap};
 pub use world::coordinates::Coordinates;
-pub use world::enums::{LiquidType, BLOCK_TYPE_NAMES, WALL_TYPE_NAMES};
+pub use world::diff::{
+    ChestChange, HeaderChange, ItemChange, NpcChange, SignChange, TileEntityChange, WorldDiff,
+};
+pub use world::dissect::{FieldAnnotation, FileLayout, SectionLayout};
+pub use world::edit_session::{EditOperation, EditSession, Transaction};
+pub use world::entity_index::Entity;
+pub use world::enums::{
+    BlockData, LiquidType, TileFrame, BLOCK_DATA, BLOCK_TYPE_NAMES, TILE_FRAMES, WALL_TYPE_NAMES,
+};
 pub use world::environment::WorldEnvironment;
+pub use world::error::{PatchError, PlacementError, SignTextError};
 pub use world::invasions::InvasionData;
 pub use world::journey_powers::JourneyPowers;
+pub use world::layer::{pixels_to_tile, tile_to_pixels, Layer};
 pub use world::npc::NPC;
+pub use world::objects::object_styles;
+pub use world::patch::{PatchConflict, TilePatch, WorldPatch, PATCH_FORMAT_VERSION};
+pub use world::query::TileQuery;
+pub use world::recovery::{LostSection, RecoveryReport};
+pub use world::region::Rect;
+pub use world::repair::{RepairAction, RepairOptions};
+pub use world::resize::Anchor;
+pub use world::resources::{Deposit, OreTier, ResourceCount, ResourceReport};
 pub use world::saved_npcs::SavedNPCs;
-pub use world::sign::Sign;
+pub use world::schematic::{PasteOptions, Schematic};
+pub use world::sign::{Sign, SignKind};
 pub use world::tile::FrameImportantData;
-pub use world::tile_entity::TileEntity;
+pub use world::tile_entity::{LogicSensorKind, PylonBiome, TileEntity};
+pub use world::validation::ValidationIssue;
 pub use world::weather_events::WeatherAndEvents;
+pub use world::world_status::{DryadDescription, WorldStatus};
@@ -1,3 +1,4 @@
+use terraria_world::world::region::Rect;
 use terraria_world::world::World;
 use rand::seq::SliceRandom;
 
@@ -7,6 +8,7 @@ struct MazeCell {
     connections: [bool; 4], // [right, left, down, up]
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for MazeCell {
     fn default() -> Self {
         Self {
@@ -23,6 +25,7 @@ struct MazeData {
     cells: Vec<Vec<MazeCell>>,
     cell_size: usize,
     hall_size: usize,
+    #[allow(dead_code)]
     wall_size: usize,
     margin: usize,
 }
@@ -142,56 +145,33 @@ impl MazeData {
                     let base_y = self.margin + gy * self.cell_size;
 
                     // Carve hall
-                    for dx in 0..self.hall_size {
-                        for dy in 0..self.hall_size {
-                            let wx = base_x + dx;
-                            let wy = base_y + dy;
-
-                            if wx < world.world_width as usize && wy < world.world_height as usize {
-                                if world.tiles.tiles[wx][wy].block_id != u16::MAX {
-                                    blocks_carved += 1;
-                                }
-                                world.tiles.tiles[wx][wy].block_id = u16::MAX;
-                                world.tiles.tiles[wx][wy].block_active = false;
-                            }
-                        }
-                    }
+                    blocks_carved += carve(world, Rect::new(
+                        base_x as i32,
+                        base_y as i32,
+                        self.hall_size as i32,
+                        self.hall_size as i32,
+                    ));
 
                     // Carve connections to neighboring cells
+                    let passage_length = (self.cell_size - self.hall_size) as i32;
                     // Right connection
                     if cell.connections[0] {
-                        for dy in 0..self.hall_size {
-                            for dx in self.hall_size..self.cell_size {
-                                let wx = base_x + dx;
-                                let wy = base_y + dy;
-
-                                if wx < world.world_width as usize && wy < world.world_height as usize {
-                                    if world.tiles.tiles[wx][wy].block_id != u16::MAX {
-                                        blocks_carved += 1;
-                                    }
-                                    world.tiles.tiles[wx][wy].block_id = u16::MAX;
-                                    world.tiles.tiles[wx][wy].block_active = false;
-                                }
-                            }
-                        }
+                        blocks_carved += carve(world, Rect::new(
+                            (base_x + self.hall_size) as i32,
+                            base_y as i32,
+                            passage_length,
+                            self.hall_size as i32,
+                        ));
                     }
 
                     // Down connection
                     if cell.connections[2] {
-                        for dx in 0..self.hall_size {
-                            for dy in self.hall_size..self.cell_size {
-                                let wx = base_x + dx;
-                                let wy = base_y + dy;
-
-                                if wx < world.world_width as usize && wy < world.world_height as usize {
-                                    if world.tiles.tiles[wx][wy].block_id != u16::MAX {
-                                        blocks_carved += 1;
-                                    }
-                                    world.tiles.tiles[wx][wy].block_id = u16::MAX;
-                                    world.tiles.tiles[wx][wy].block_active = false;
-                                }
-                            }
-                        }
+                        blocks_carved += carve(world, Rect::new(
+                            base_x as i32,
+                            (base_y + self.hall_size) as i32,
+                            self.hall_size as i32,
+                            passage_length,
+                        ));
                     }
                 }
             }
@@ -201,6 +181,19 @@ impl MazeData {
     }
 }
 
+// Removes the blocks in `rect`, keeping walls and liquids, and counts the ones removed
+fn carve(world: &mut World, rect: Rect) -> usize {
+    let mut carved = 0;
+    for (_, _, tile) in world.tiles_in_mut(rect) {
+        if tile.block_id != u16::MAX {
+            carved += 1;
+        }
+        tile.block_id = u16::MAX;
+        tile.block_active = false;
+    }
+    carved
+}
+
 fn main() {
     let mut world = World::new("maze_world", "small", "classic", "corruption");
 
@@ -231,14 +224,10 @@ fn main() {
 
     // Fill the world with solid blocks (stone)
     println!("Filling world with stone blocks...");
-    let mut stone_blocks_placed = 0;
-    for x in 0..world.world_width as usize {
-        for y in 0..world.world_height as usize {
-            world.tiles.tiles[x][y].block_id = 1; // 1 = STONE
-            world.tiles.tiles[x][y].block_active = true;
-            stone_blocks_placed += 1;
-        }
-    }
+    world
+        .fill(world.bounds(), "STONE")
+        .expect("Failed to fill the world with stone");
+    let stone_blocks_placed = world.bounds().area();
 
     // Apply the maze to the world
     println!("Applying maze to world...");
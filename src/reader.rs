@@ -10,138 +10,125 @@ impl<'a> ByteReader<'a> {
         ByteReader { data, offset: 0 }
     }
 
-    pub fn u8(&mut self) -> u8 {
-        if self.offset >= self.data.len() {
-            panic!(
-                "Attempted to read u8 at offset {} but data length is {}",
-                self.offset,
-                self.data.len()
-            );
+    // The next `count` bytes, or an `UnexpectedEof` error naming what was read
+    fn take(&mut self, count: usize, what: &str) -> std::io::Result<&'a [u8]> {
+        if count > self.remaining() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                format!(
+                    "Attempted to read {} at offset {} but data length is {}",
+                    what,
+                    self.offset,
+                    self.data.len()
+                ),
+            ));
         }
-        let val = self.data[self.offset];
-        self.offset += 1;
-        val
+        let slice = &self.data[self.offset..self.offset + count];
+        self.offset += count;
+        Ok(slice)
+    }
+
+    pub fn try_u8(&mut self) -> std::io::Result<u8> {
+        let bytes = self.take(1, "u8")?;
+        Ok(u8::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    pub fn try_u16(&mut self) -> std::io::Result<u16> {
+        let bytes = self.take(2, "u16")?;
+        Ok(u16::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    pub fn try_u32(&mut self) -> std::io::Result<u32> {
+        let bytes = self.take(4, "u32")?;
+        Ok(u32::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    pub fn try_u64(&mut self) -> std::io::Result<u64> {
+        let bytes = self.take(8, "u64")?;
+        Ok(u64::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    pub fn try_i8(&mut self) -> std::io::Result<i8> {
+        let bytes = self.take(1, "i8")?;
+        Ok(i8::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    pub fn try_i16(&mut self) -> std::io::Result<i16> {
+        let bytes = self.take(2, "i16")?;
+        Ok(i16::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    pub fn try_i32(&mut self) -> std::io::Result<i32> {
+        let bytes = self.take(4, "i32")?;
+        Ok(i32::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    pub fn try_i64(&mut self) -> std::io::Result<i64> {
+        let bytes = self.take(8, "i64")?;
+        Ok(i64::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    pub fn try_bool(&mut self) -> std::io::Result<bool> {
+        // In Terraria world files, any non-zero value is considered true
+        Ok(self.try_u8()? != 0)
+    }
+
+    pub fn try_bits(&mut self) -> std::io::Result<Vec<bool>> {
+        let byte = self.try_u8()?;
+        Ok((0..8).map(|i| (byte & (1 << i)) != 0).collect())
+    }
+
+    pub fn try_bytes(&mut self, count: usize) -> std::io::Result<&'a [u8]> {
+        self.take(count, &format!("{count} bytes"))
+    }
+
+    pub fn u8(&mut self) -> u8 {
+        or_panic(self.try_u8())
     }
 
     pub fn u16(&mut self) -> u16 {
-        if self.offset + 2 > self.data.len() {
-            panic!(
-                "Attempted to read u16 at offset {} but data length is {}",
-                self.offset,
-                self.data.len()
-            );
-        }
-        let val = u16::from_le_bytes(self.data[self.offset..self.offset + 2].try_into().unwrap());
-        self.offset += 2;
-        val
+        or_panic(self.try_u16())
     }
 
     pub fn u32(&mut self) -> u32 {
-        if self.offset + 4 > self.data.len() {
-            panic!(
-                "Attempted to read u32 at offset {} but data length is {}",
-                self.offset,
-                self.data.len()
-            );
-        }
-        let val = u32::from_le_bytes(self.data[self.offset..self.offset + 4].try_into().unwrap());
-        self.offset += 4;
-        val
+        or_panic(self.try_u32())
     }
 
     pub fn u64(&mut self) -> u64 {
-        if self.offset + 8 > self.data.len() {
-            panic!(
-                "Attempted to read u64 at offset {} but data length is {}",
-                self.offset,
-                self.data.len()
-            );
-        }
-        let val = u64::from_le_bytes(self.data[self.offset..self.offset + 8].try_into().unwrap());
-        self.offset += 8;
-        val
+        or_panic(self.try_u64())
     }
 
     pub fn i8(&mut self) -> i8 {
-        if self.offset >= self.data.len() {
-            panic!(
-                "Attempted to read i8 at offset {} but data length is {}",
-                self.offset,
-                self.data.len()
-            );
-        }
-        let val = self.data[self.offset] as i8;
-        self.offset += 1;
-        val
+        or_panic(self.try_i8())
     }
 
     pub fn i16(&mut self) -> i16 {
-        if self.offset + 2 > self.data.len() {
-            panic!(
-                "Attempted to read i16 at offset {} but data length is {}",
-                self.offset,
-                self.data.len()
-            );
-        }
-        let val = i16::from_le_bytes(self.data[self.offset..self.offset + 2].try_into().unwrap());
-        self.offset += 2;
-        val
+        or_panic(self.try_i16())
     }
 
     pub fn i32(&mut self) -> i32 {
-        if self.offset + 4 > self.data.len() {
-            panic!(
-                "Attempted to read i32 at offset {} but data length is {}",
-                self.offset,
-                self.data.len()
-            );
-        }
-        let val = i32::from_le_bytes(self.data[self.offset..self.offset + 4].try_into().unwrap());
-        self.offset += 4;
-        val
+        or_panic(self.try_i32())
     }
 
     pub fn i64(&mut self) -> i64 {
-        if self.offset + 8 > self.data.len() {
-            panic!(
-                "Attempted to read i64 at offset {} but data length is {}",
-                self.offset,
-                self.data.len()
-            );
-        }
-        let val = i64::from_le_bytes(self.data[self.offset..self.offset + 8].try_into().unwrap());
-        self.offset += 8;
-        val
+        or_panic(self.try_i64())
     }
 
     pub fn bool(&mut self) -> bool {
-        let byte = self.u8();
-        // In Terraria world files, any non-zero value is considered true
-        byte != 0
+        or_panic(self.try_bool())
     }
 
     pub fn bits(&mut self) -> Vec<bool> {
-        let byte = self.u8(); // read one byte
-        (0..8).map(|i| (byte & (1 << i)) != 0).collect()
+        or_panic(self.try_bits())
     }
 
     pub fn bytes(&mut self, count: usize) -> &'a [u8] {
-        if self.offset + count > self.data.len() {
-            panic!(
-                "Attempted to read {} bytes at offset {} but data length is {}",
-                count,
-                self.offset,
-                self.data.len()
-            );
-        }
-        let slice = &self.data[self.offset..self.offset + count];
-        self.offset += count;
-        slice
+        or_panic(self.try_bytes(count))
     }
 
     /// Returns a slice of bytes from the current offset without advancing the offset.
     pub fn peek_bytes(&self, count: usize) -> &'a [u8] {
-        if self.offset + count > self.data.len() {
+        if count > self.remaining() {
             panic!(
                 "Attempted to peek {} bytes at offset {} but data length is {}",
                 count,
@@ -166,33 +153,52 @@ impl<'a> ByteReader<'a> {
         self.offset
     }
 
+    pub fn remaining(&self) -> usize {
+        self.data.len().saturating_sub(self.offset)
+    }
+
+    /// Capacity to reserve for `count` items that are still to be read, so a
+    /// corrupted count cannot cause a huge allocation.
+    pub fn capacity_hint(&self, count: i64) -> usize {
+        (count.max(0) as usize).min(self.remaining())
+    }
+
     pub fn seek(&mut self, offset: usize) {
         self.offset = offset;
     }
 
-    pub fn uleb128(&mut self) -> u64 {
+    pub fn try_uleb128(&mut self) -> std::io::Result<u64> {
         let mut value = 0u64;
         let mut shift = 0;
         loop {
-            let byte = self.u8();
+            let byte = self.try_u8()?;
+            if shift >= u64::BITS {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("uleb128 longer than 64 bits at offset {}", self.offset),
+                ));
+            }
             value |= ((byte & 0x7F) as u64) << shift;
             if (byte & 0x80) == 0 {
                 break;
             }
             shift += 7;
         }
-        value
+        Ok(value)
     }
 
-    pub fn string(&mut self, size: Option<usize>) -> String {
-        let size = size.unwrap_or_else(|| self.uleb128() as usize);
-        let bytes = self.bytes(size);
-        bytes.iter().map(|&b| b as char).collect() // assuming latin1
+    pub fn try_string(&mut self, size: Option<usize>) -> std::io::Result<String> {
+        let size = match size {
+            Some(size) => size,
+            None => self.try_uleb128()? as usize,
+        };
+        let bytes = self.try_bytes(size)?;
+        Ok(bytes.iter().map(|&b| b as char).collect()) // assuming latin1
     }
 
-    pub fn uuid(&mut self) -> String {
-        let bytes = self.bytes(16);
-        format!(
+    pub fn try_uuid(&mut self) -> std::io::Result<String> {
+        let bytes = self.try_bytes(16)?;
+        Ok(format!(
             "{:02x}{:02x}{:02x}{:02x}-\
              {:02x}{:02x}-\
              {:02x}{:02x}-\
@@ -214,64 +220,46 @@ impl<'a> ByteReader<'a> {
             bytes[13],
             bytes[14],
             bytes[15],
-        )
+        ))
     }
 
-    pub fn datetime(&mut self) -> String {
-        let raw = self.u64(); // already reads 8 bytes little-endian
-
-        let _kind: u64 = (raw >> 62) & 0b11;
-        let ticks: u64 = raw & 0x3FFF_FFFF_FFFF_FFFF; // mask top 2 bits
-
-        // println!("Kind: {}", match kind {
-        //     0 => "Unspecified",
-        //     1 => "Utc",
-        //     2 => "Local",
-        //     _ => "⚠️ Reserved/Invalid",
-        // });
-
-        // .NET ticks start at 0001-01-01
-        let unix_offset: u64 = 621355968000000000;
-        if ticks < unix_offset {
-            return "⚠️ Before UNIX epoch".to_string();
-        }
+    pub fn try_datetime(&mut self) -> std::io::Result<String> {
+        let raw = self.try_u64()?; // already reads 8 bytes little-endian
+        Ok(datetime_from_ticks(raw))
+    }
 
-        let unix_ticks: u64 = ticks - unix_offset;
-        let secs: u64 = unix_ticks / 10_000_000;
-        let nsecs: u64 = (unix_ticks % 10_000_000) * 100;
+    pub fn try_f32(&mut self) -> std::io::Result<f32> {
+        let bytes = self.take(4, "f32")?;
+        Ok(f32::from_le_bytes(bytes.try_into().unwrap()))
+    }
 
-        match DateTime::from_timestamp(secs as i64, nsecs as u32) {
-            Some(dt) => {
-                // Use format with 7 decimal places to preserve .NET tick precision
-                // .NET ticks are 100ns intervals, so 7 decimal places gives us the full precision
-                dt.format("%Y-%m-%d %H:%M:%S%.f").to_string()
-            }
-            _ => "⚠️ Invalid datetime".to_string(),
-        }
+    pub fn try_f64(&mut self) -> std::io::Result<f64> {
+        let bytes = self.take(8, "f64")?;
+        Ok(f64::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    pub fn uleb128(&mut self) -> u64 {
+        or_panic(self.try_uleb128())
+    }
+
+    pub fn string(&mut self, size: Option<usize>) -> String {
+        or_panic(self.try_string(size))
+    }
+
+    pub fn uuid(&mut self) -> String {
+        or_panic(self.try_uuid())
+    }
+
+    pub fn datetime(&mut self) -> String {
+        or_panic(self.try_datetime())
     }
 
     pub fn f32(&mut self) -> f32 {
-        if self.offset + 4 > self.data.len() {
-            panic!(
-                "Attempted to read f32 at offset {} but data length is {}",
-                self.offset,
-                self.data.len()
-            );
-        }
-        let bytes = self.bytes(4);
-        f32::from_le_bytes(bytes.try_into().unwrap())
+        or_panic(self.try_f32())
     }
 
     pub fn f64(&mut self) -> f64 {
-        if self.offset + 8 > self.data.len() {
-            panic!(
-                "Attempted to read f64 at offset {} but data length is {}",
-                self.offset,
-                self.data.len()
-            );
-        }
-        let bytes = self.bytes(8);
-        f64::from_le_bytes(bytes.try_into().unwrap())
+        or_panic(self.try_f64())
     }
 
     pub fn slice_bytes(&self, start: usize, end: usize) -> Vec<u8> {
@@ -286,3 +274,40 @@ impl<'a> ByteReader<'a> {
         self.data[start..end].to_vec()
     }
 }
+
+// Text of a .NET `DateTime` binary value, with the kind bits masked off
+fn datetime_from_ticks(raw: u64) -> String {
+    let _kind: u64 = (raw >> 62) & 0b11;
+    let ticks: u64 = raw & 0x3FFF_FFFF_FFFF_FFFF; // mask top 2 bits
+
+    // println!("Kind: {}", match kind {
+    //     0 => "Unspecified",
+    //     1 => "Utc",
+    //     2 => "Local",
+    //     _ => "⚠️ Reserved/Invalid",
+    // });
+
+    // .NET ticks start at 0001-01-01
+    let unix_offset: u64 = 621355968000000000;
+    if ticks < unix_offset {
+        return "⚠️ Before UNIX epoch".to_string();
+    }
+
+    let unix_ticks: u64 = ticks - unix_offset;
+    let secs: u64 = unix_ticks / 10_000_000;
+    let nsecs: u64 = (unix_ticks % 10_000_000) * 100;
+
+    match DateTime::from_timestamp(secs as i64, nsecs as u32) {
+        Some(dt) => {
+            // Use format with 7 decimal places to preserve .NET tick precision
+            // .NET ticks are 100ns intervals, so 7 decimal places gives us the full precision
+            dt.format("%Y-%m-%d %H:%M:%S%.f").to_string()
+        }
+        _ => "⚠️ Invalid datetime".to_string(),
+    }
+}
+
+// The panicking readers predate the checked ones and keep their messages
+fn or_panic<T>(result: std::io::Result<T>) -> T {
+    result.unwrap_or_else(|e| panic!("{e}"))
+}
@@ -1,4 +1,5 @@
 use rand::Rng;
+use std::ops::Range;
 use uuid::Uuid;
 
 use crate::reader::ByteReader;
@@ -6,8 +7,14 @@ use crate::writer::ByteWriter;
 
 // Module declarations
 pub mod bestiary;
+pub mod biome;
+pub mod block_ids;
 pub mod chest;
 pub mod coordinates;
+pub mod diff;
+pub mod dissect;
+pub mod edit_session;
+pub mod entity_index;
 pub mod enums;
 pub mod environment;
 pub mod error;
@@ -15,15 +22,32 @@ pub mod game_progression;
 pub mod invasions;
 pub mod item;
 pub mod journey_powers;
+pub mod layer;
 pub mod mob;
 pub mod npc;
+pub mod objects;
+pub mod patch;
 pub mod pressure_plate;
+pub mod query;
+pub mod recovery;
+pub mod region;
+pub mod repair;
+pub mod resize;
+pub mod resources;
 pub mod room;
+pub mod save;
 pub mod saved_npcs;
+pub mod schematic;
+pub mod sections;
 pub mod sign;
+pub mod tedit;
 pub mod tile;
 pub mod tile_entity;
+pub mod transform;
+pub mod transplant;
+pub mod validation;
 pub mod weather_events;
+pub mod world_status;
 
 use self::enums::LiquidType;
 use self::tile::{FrameImportantData, Tile, TileMatrix};
@@ -33,6 +57,8 @@ use serde::{Deserialize, Serialize};
 use crate::world::bestiary::Bestiary;
 use crate::world::chest::Chest;
 use crate::world::coordinates::Coordinates;
+use crate::world::edit_session::ChangeLog;
+use crate::world::entity_index::EntityIndex;
 use crate::world::environment::WorldEnvironment;
 use crate::world::error::InvalidFooterError;
 use crate::world::game_progression::GameProgression;
@@ -42,12 +68,21 @@ use crate::world::journey_powers::JourneyPowers;
 use crate::world::mob::Mob;
 use crate::world::npc::NPC;
 use crate::world::pressure_plate::WeighedPressurePlate;
+use crate::world::region::Rect;
 use crate::world::room::Room;
+use crate::world::save::SaveOptions;
 use crate::world::saved_npcs::SavedNPCs;
+use crate::world::sections::{Section, SectionCache};
 use crate::world::sign::Sign;
 use crate::world::tile_entity::{TileEntity, TileEntityExtra};
 use crate::world::weather_events::WeatherAndEvents;
 
+// The largest size accepted, twice the game's large world (8400x2400) to
+// leave room for worlds made with editors. A corrupt header could otherwise
+// ask for billions of tiles before any tile data is read.
+pub(crate) const MAX_WORLD_WIDTH: i32 = 16_800;
+pub(crate) const MAX_WORLD_HEIGHT: i32 = 4_800;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct World {
     // Core world info
@@ -136,6 +171,18 @@ pub struct World {
     pub rooms: Vec<Room>,
     pub bestiary: Bestiary,
     pub journey_powers: JourneyPowers,
+
+    // Raw bytes from the file this world was loaded from, reused when saving
+    #[serde(skip)]
+    pub(crate) section_cache: Option<SectionCache>,
+
+    // Where chests, signs and tile entities are
+    #[serde(skip)]
+    pub(crate) entity_index: EntityIndex,
+
+    // What the editing methods changed while an edit session records
+    #[serde(skip)]
+    pub(crate) change_log: Option<ChangeLog>,
 }
 
 impl World {
@@ -186,35 +233,7 @@ impl World {
             Coordinates { x: 4196, y: 572 },
             0,
         );
-        let tile_frame_important = vec![
-            0, 0, 0, 1, 1, 1, 0, 0, 0, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 0, 1, 0, 1, 1, 1,
-            1, 0, 1, 0, 1, 1, 1, 1, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 1, 1, 0, 0, 0, 0, 1, 0, 0,
-            0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 0, 0, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1,
-            1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 0, 0, 1, 0, 0, 1, 1, 0,
-            0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 0, 1, 1, 0, 0, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1,
-            0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 1, 1, 1,
-            1, 0, 0, 0, 1, 0, 0, 0, 0, 0, 1, 1, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0,
-            0, 0, 0, 0, 1, 0, 1, 1, 0, 1, 0, 0, 1, 1, 1, 1, 1, 1, 0, 0, 0, 0, 0, 0, 1, 1, 0, 0, 1,
-            0, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0,
-            0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 0, 0, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1,
-            1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 0, 0, 1, 0, 1, 1, 1,
-            1, 1, 0, 0, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 0, 1, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0,
-            0, 1, 0, 0, 0, 0, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 1,
-            1, 1, 0, 1, 0, 0, 0, 0, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1,
-            1, 0, 0, 0, 1, 1, 1, 1, 1, 0, 0, 0, 0, 1, 1, 0, 0, 1, 1, 1, 0, 1, 1, 1, 0, 0, 0, 0, 0,
-            1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 1, 1, 0, 0, 0, 1, 1, 1,
-            1, 1, 1, 1, 1, 1, 1, 1, 0, 0, 0, 1, 1, 0, 0, 0, 1, 0, 0, 0, 1, 1, 1, 1, 1, 1, 1, 1, 0,
-            1, 1, 0, 0, 1, 0, 1, 0, 0, 0, 0, 0, 1, 1, 0, 0, 1, 1, 1, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1,
-            1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 0, 0, 0, 0, 1, 0, 0, 0, 1, 1, 1, 1, 0, 1, 1, 1, 1,
-            1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 0, 0, 0, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 0, 0, 0, 0, 0, 1,
-            1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
-            1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 0, 0, 0, 0, 1, 1, 1, 1, 0, 1, 0, 0, 1,
-            0, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 0, 1, 0, 0, 1, 1, 1, 0,
-            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-        ];
-        // turn this vector to Vec<bool>
-        let tile_frame_important: Vec<bool> =
-            tile_frame_important.into_iter().map(|x| x != 0).collect();
+        let tile_frame_important = Self::default_tile_frame_important();
 
         Self {
             version_integer: 279,
@@ -276,7 +295,8 @@ impl World {
             moondial_is_running: false,
             moondial_cooldown: 0,
             tiles: TileMatrix::new((world_width as usize, world_height as usize)),
-            chests_max_items: 0,
+            // the game always saves 40 slots per chest
+            chests_max_items: 40,
             chests: Vec::new(),
             signs: Vec::new(),
             npcs: vec![guide_npc],
@@ -287,434 +307,800 @@ impl World {
             rooms: Vec::new(),
             bestiary: Bestiary::new(Vec::new(), Vec::new(), Vec::new()),
             journey_powers: JourneyPowers::new(),
+            section_cache: None,
+            entity_index: EntityIndex::default(),
+            change_log: None,
         }
     }
 
+    /// Which block types store frame data, as written by 1.4.4.9.
+    pub fn default_tile_frame_important() -> Vec<bool> {
+        let tile_frame_important = vec![
+            0, 0, 0, 1, 1, 1, 0, 0, 0, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 0, 1, 0, 1, 1, 1,
+            1, 0, 1, 0, 1, 1, 1, 1, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 1, 1, 0, 0, 0, 0, 1, 0, 0,
+            0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 0, 0, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1,
+            1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 0, 0, 1, 0, 0, 1, 1, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 0, 1, 1, 0, 0, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1,
+            0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 1, 1, 1,
+            1, 0, 0, 0, 1, 0, 0, 0, 0, 0, 1, 1, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0,
+            0, 0, 0, 0, 1, 0, 1, 1, 0, 1, 0, 0, 1, 1, 1, 1, 1, 1, 0, 0, 0, 0, 0, 0, 1, 1, 0, 0, 1,
+            0, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 0, 0, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1,
+            1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 0, 0, 1, 0, 1, 1, 1,
+            1, 1, 0, 0, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 0, 1, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 1, 0, 0, 0, 0, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 1,
+            1, 1, 0, 1, 0, 0, 0, 0, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1,
+            1, 0, 0, 0, 1, 1, 1, 1, 1, 0, 0, 0, 0, 1, 1, 0, 0, 1, 1, 1, 0, 1, 1, 1, 0, 0, 0, 0, 0,
+            1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 1, 1, 0, 0, 0, 1, 1, 1,
+            1, 1, 1, 1, 1, 1, 1, 1, 0, 0, 0, 1, 1, 0, 0, 0, 1, 0, 0, 0, 1, 1, 1, 1, 1, 1, 1, 1, 0,
+            1, 1, 0, 0, 1, 0, 1, 0, 0, 0, 0, 0, 1, 1, 0, 0, 1, 1, 1, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1,
+            1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 0, 0, 0, 0, 1, 0, 0, 0, 1, 1, 1, 1, 0, 1, 1, 1, 1,
+            1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 0, 0, 0, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 0, 0, 0, 0, 0, 1,
+            1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+            1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 0, 0, 0, 0, 1, 1, 1, 1, 0, 1, 0, 0, 1,
+            0, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 0, 1, 0, 0, 1, 1, 1, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        // turn this vector to Vec<bool>
+        tile_frame_important.into_iter().map(|x| x != 0).collect()
+    }
+
     pub fn from_file(path: &str) -> std::io::Result<Self> {
         let bytes = std::fs::read(path)?;
         let mut r = ByteReader::new(&bytes);
 
-        let version_integer = r.i32();
+        let (mut world, pointer_vector) = Self::read_header(&mut r)?;
 
-        let magic = String::from_utf8_lossy(r.bytes(7)).to_string();
-        if magic != "relogic" {
+        if r.offset() as u32 != pointer_vector[1] {
             return Err(std::io::Error::new(
                 std::io::ErrorKind::InvalidData,
-                "Invalid magic string in the file header. Please open an issue at https://github.com/osbm/terraria-world-rs/issues"));
+                "Pointer mismatch after world header section. Please open an issue at https://github.com/osbm/terraria-world-rs/issues"));
         }
 
-        let savefile_type = r.u8();
-        let revision = r.u32();
-        let is_favorite = r.u64();
+        // tiles
+        let (width, height) = (world.world_width as usize, world.world_height as usize);
+        let (tiles, tile_columns) =
+            Self::create_tile_matrix(&mut r, (width, height), &world.tile_frame_important)?;
+        world.tiles = tiles;
 
-        let pointer_count = r.u16();
-        let mut pointer_vector = vec![];
-        for _ in 0..pointer_count {
-            pointer_vector.push(r.u32());
+        if r.offset() as u32 != pointer_vector[2] {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Pointer mismatch after tiles section. Please open an issue at https://github.com/osbm/terraria-world-rs/issues"));
         }
 
-        let tile_frame_important_count = r.i16();
-        let tile_frame_important_size = (tile_frame_important_count + 7) / 8;
-        let mut tile_frame_important = vec![];
-        for _ in 0..tile_frame_important_size {
-            let current_bits = r.bits();
-            tile_frame_important.extend(current_bits);
-        }
-        tile_frame_important.truncate(tile_frame_important_count as usize);
+        world.read_chests_section(&mut r)?;
 
-        if r.offset() as u32 != pointer_vector[0] {
+        if r.offset() as u32 != pointer_vector[3] {
             return Err(std::io::Error::new(
                 std::io::ErrorKind::InvalidData,
-                "Pointer mismatch after file header. Please open an issue at https://github.com/osbm/terraria-world-rs/issues"));
+                "Pointer mismatch after chests section. Please open an issue at https://github.com/osbm/terraria-world-rs/issues"));
         }
 
-        let world_name = r.string(None);
-        let generator_seed = r.string(None);
-        let generator_version = r.u64();
-        let uuid = r.uuid();
-        let id = r.i32();
-        let bounds_vec = vec![
-            r.i32(), // left
-            r.i32(), // right
-            r.i32(), // top
-            r.i32(), // bottom
-        ];
+        world.read_signs_section(&mut r)?;
 
-        let world_height = r.i32();
-        let world_width = r.i32();
-        let difficulty_value = r.i32();
-        let is_drunk_world = r.bool();
-        let is_for_the_worthy = r.bool();
-        let is_tenth_anniversary = r.bool();
-        let is_the_constant = r.bool();
-        let is_bee_world = r.bool();
-        let is_upside_down = r.bool();
-        let is_trap_world = r.bool();
-        let is_zenith_world = r.bool();
-        let created_on = r.datetime();
-        let moon_style = r.u8();
-        let tree_style_separators = vec![r.i32(), r.i32(), r.i32()];
-        let tree_style_properties = vec![r.i32(), r.i32(), r.i32(), r.i32()];
-        let moss_style_separators = vec![r.i32(), r.i32(), r.i32()];
-        let moss_style_properties = vec![r.i32(), r.i32(), r.i32(), r.i32()];
-        let snow_background_style = r.i32();
-        let jungle_background_style = r.i32();
-        let hell_background_style = r.i32();
-        let spawn_point_x = r.i32();
-        let spawn_point_y = r.i32();
-        let underground_level = r.f64();
-        let cavern_level = r.f64();
-        let current_time = r.f64();
-        let is_daytime = r.bool();
-        let moon_phase = r.u32();
-        let blood_moon = r.bool();
-        let eclipse = r.bool();
-        let dungeon_point_x = r.i32();
-        let dungeon_point_y = r.i32();
-        let world_evil_type = r.bool();
-        let defeated_eye_of_cthulhu = r.bool();
-        let defeated_eater_of_worlds = r.bool();
-        let defeated_skeletron = r.bool();
-        let defeated_queen_bee = r.bool();
-        let defeated_the_twins = r.bool();
-        let defeated_the_destroyer = r.bool();
-        let defeated_skeletron_prime = r.bool();
-        let defeated_any_mechanical_boss = r.bool();
-        let defeated_plantera = r.bool();
-        let defeated_golem = r.bool();
-        let defeated_king_slime = r.bool();
-        let saved_goblin_tinkerer = r.bool();
-        let saved_wizard = r.bool();
-        let saved_mechanic = r.bool();
-        let defeated_goblin_army = r.bool();
-        let defeated_clown = r.bool();
-        let defeated_frost_moon = r.bool();
-        let defeated_pirate_invasion = r.bool();
-        let shadow_orbs_smashed_at_least_once = r.bool();
-        let shadow_orbs_spawn_meteorite = r.bool();
-        let shadow_orbs_evil_boss_counter = r.u8();
-        let altars_smashed = r.i32();
-        let is_hardmode = r.bool();
-        let party_is_doomed = !r.bool(); // ???
-        let invasion_delay = r.i32();
-        let invasion_size = r.i32();
-        let invasion_type = r.i32();
-        let invasion_position = r.f64();
-        let time_left_slime_rain = r.f64();
-        let sundial_cooldown = r.u8();
-        let is_rain_active = r.bool();
-        let rain_time_left = r.i32();
-        let max_rain = r.f32();
-        let hardmode_ore_1 = r.i32();
-        let hardmode_ore_2 = r.i32();
-        let hardmode_ore_3 = r.i32();
-        let forest_background = r.i8();
-        let corruption_background = r.i8();
-        let jungle_background = r.i8();
-        let snow_background = r.i8();
-        let hallow_background = r.i8();
-        let crimson_background = r.i8();
-        let desert_background = r.i8();
-        let ocean_background = r.i8();
-        let cloud_background = r.i32();
-        let cloud_number = r.i16();
-        let wind_speed = r.f32();
-
-        let angler_today_quest_completed_by_count = r.i32();
-        let mut angler_today_quest_completed_by = vec![];
-        for _ in 0..angler_today_quest_completed_by_count {
-            let name = r.string(None);
-            if !name.is_empty() {
-                angler_today_quest_completed_by.push(name);
-            }
+        if r.offset() as u32 != pointer_vector[4] {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Pointer mismatch after signs section. Please open an issue at https://github.com/osbm/terraria-world-rs/issues"));
         }
 
-        let saved_angler = r.bool();
-        let angler_daily_quest_target = r.i32();
-        let saved_stylist = r.bool();
-        let saved_tax_collector = r.bool();
-        let saved_golfer = r.bool();
-        let invasion_size_start = r.i32();
-        let cultist_delay = r.i32();
+        world.read_npcs_section(&mut r)?;
 
-        let mob_kills_count = r.i16();
-        let mut mob_kills = vec![];
-        for _ in 0..mob_kills_count {
-            mob_kills.push(r.i32());
-        }
-        let sundial_is_running = r.bool();
-        let defeated_duke_fishron = r.bool();
-        let defeated_martian_madness = r.bool();
-        let defeated_lunatic_cultist = r.bool();
-        let defeated_moon_lord = r.bool();
-        let defeated_pumpking = r.bool();
-        let defeated_mourning_wood = r.bool();
-        let defeated_ice_queen = r.bool();
-        let defeated_santa_nk1 = r.bool();
-        let defeated_everscream = r.bool();
-        let defeated_solar_pillar = r.bool();
-        let defeated_vortex_pillar = r.bool();
-        let defeated_nebula_pillar = r.bool();
-        let defeated_stardust_pillar = r.bool();
-        let lunar_events_pillars_present_solar = r.bool();
-        let lunar_events_pillars_present_vortex = r.bool();
-        let lunar_events_pillars_present_nebula = r.bool();
-        let lunar_events_pillars_present_stardust = r.bool();
-        let lunar_events_are_active = r.bool();
-        let party_center_active = r.bool();
-        let party_natural_active = r.bool();
-        let party_cooldown = r.i32();
-
-        let partying_npcs_count = r.i32();
-        let mut partying_npcs = vec![];
-        for _ in 0..partying_npcs_count {
-            partying_npcs.push(r.i32());
-        }
-
-        let is_sandstorm_active = r.bool();
-        let sandstorm_time_left = r.i32();
-        let sandstorm_severity = r.f32();
-        let sandstorm_intended_severity = r.f32();
-        let saved_bartender = r.bool();
-        let old_ones_army_tier_1 = r.bool();
-        let old_ones_army_tier_2 = r.bool();
-        let old_ones_army_tier_3 = r.bool();
-        let mushroom_background = r.i8();
-        let underworld_background = r.i8();
-        let forest_background_2 = r.i8();
-        let forest_background_3 = r.i8();
-        let forest_background_4 = r.i8();
-        let combat_book_used = r.bool();
-        let lantern_nights_on_cooldown = r.i32();
-        let lantern_night_genuine = r.bool();
-        let lantern_night_manual = r.bool();
-        let next_night_is_lantern_night = r.bool();
-
-        let treetop_variants_count = r.i32();
-        let mut treetop_variants = vec![];
-        for _ in 0..treetop_variants_count {
-            treetop_variants.push(r.i32());
-        }
-
-        let halloween_today = r.bool();
-        let christmas_today = r.bool();
-        let ore_1 = r.i32();
-        let ore_2 = r.i32();
-        let ore_3 = r.i32();
-        let ore_4 = r.i32();
-        let has_cat = r.bool();
-        let has_dog = r.bool();
-        let has_bunny = r.bool();
-        let defeated_empress_of_light = r.bool();
-        let defeated_queen_slime = r.bool();
-        let defeated_deerclops = r.bool();
-        let saved_slime_nerdy = r.bool();
-        let saved_merchant = r.bool();
-        let saved_demolitionist = r.bool();
-        let saved_party_girl = r.bool();
-        let saved_dye_trader = r.bool();
-        let saved_truffle = r.bool();
-        let saved_arms_dealer = r.bool();
-        let saved_nurse = r.bool();
-        let saved_princess = r.bool();
-        let combat_book_2_used = r.bool();
-        let peddler_satchel_used = r.bool();
-        let saved_slime_cool = r.bool();
-        let saved_slime_elder = r.bool();
-        let saved_slime_clumsy = r.bool();
-        let saved_slime_diva = r.bool();
-        let saved_slime_surly = r.bool();
-        let saved_slime_mystic = r.bool();
-        let saved_slime_squire = r.bool();
-        let moondial_is_running = r.bool();
-        let moondial_cooldown = r.u8();
+        if r.offset() as u32 != pointer_vector[5] {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Pointer mismatch after NPCs and mobs section. Please open an issue at https://github.com/osbm/terraria-world-rs/issues"));
+        }
 
-        if r.offset() as u32 != pointer_vector[1] {
+        world.read_tile_entities_section(&mut r)?;
+
+        if r.offset() as u32 != pointer_vector[6] {
             return Err(std::io::Error::new(
                 std::io::ErrorKind::InvalidData,
-                "Pointer mismatch after world header section. Please open an issue at https://github.com/osbm/terraria-world-rs/issues"));
+                "Pointer mismatch after tile entities section. Please open an issue at https://github.com/osbm/terraria-world-rs/issues"));
         }
 
-        // tiles
-        let (width, height) = (world_width as usize, world_height as usize);
-        let tiles = Self::create_tile_matrix(&mut r, (width, height), &tile_frame_important);
+        world.read_pressure_plates_section(&mut r)?;
 
-        if r.offset() as u32 != pointer_vector[2] {
+        if r.offset() as u32 != pointer_vector[7] {
             return Err(std::io::Error::new(
                 std::io::ErrorKind::InvalidData,
-                "Pointer mismatch after tiles section. Please open an issue at https://github.com/osbm/terraria-world-rs/issues"));
+                "Pointer mismatch after weighed pressure plates section. Please open an issue at https://github.com/osbm/terraria-world-rs/issues"));
         }
 
-        // --- CHEST PARSING ---
-        let chests_count = r.i16();
-        let chests_max_items = r.i16();
-        let mut chests = Vec::with_capacity(chests_count as usize);
-        for _ in 0..chests_count {
-            let chest_x = r.i32();
-            let chest_y = r.i32();
-            let chest_name = r.string(None);
-            let mut chest_contents = Vec::with_capacity(chests_max_items as usize);
-            for _ in 0..chests_max_items {
-                let item_quantity = r.i16();
-                if item_quantity > 0 {
-                    let item_type = r.i32();
-                    let item_prefix = r.u8();
-                    chest_contents.push(Some(ItemStack {
-                        quantity: item_quantity,
-                        type_id: item_type,
-                        prefix: item_prefix,
-                    }));
-                } else {
-                    chest_contents.push(None);
-                }
-            }
-            chests.push(Chest {
-                position: Coordinates {
-                    x: chest_x,
-                    y: chest_y,
-                },
-                name: chest_name,
-                contents: chest_contents,
-            });
+        world.read_town_manager_section(&mut r)?;
+
+        if r.offset() as u32 != pointer_vector[8] {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Pointer mismatch after rooms section. Please open an issue at https://github.com/osbm/terraria-world-rs/issues"));
         }
 
-        if r.offset() as u32 != pointer_vector[3] {
+        world.read_bestiary_section(&mut r)?;
+
+        if r.offset() as u32 != pointer_vector[9] {
             return Err(std::io::Error::new(
                 std::io::ErrorKind::InvalidData,
-                "Pointer mismatch after chests section. Please open an issue at https://github.com/osbm/terraria-world-rs/issues"));
+                "Pointer mismatch after bestiary section. Please open an issue at https://github.com/osbm/terraria-world-rs/issues"));
         }
 
-        // --- SIGN PARSING ---
-        let signs_count = r.i16();
-        let mut signs = Vec::with_capacity(signs_count as usize);
-        for _ in 0..signs_count {
-            let sign_text = r.string(None);
-            let sign_x = r.i32();
-            let sign_y = r.i32();
-            signs.push(Sign {
-                text: sign_text,
-                position: Coordinates {
-                    x: sign_x,
-                    y: sign_y,
-                },
-            });
+        world.read_journey_powers_section(&mut r)?;
+
+        if r.offset() as u32 != pointer_vector[10] {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Pointer mismatch after journey powers section. Please open an issue at https://github.com/osbm/terraria-world-rs/issues"));
         }
 
-        if r.offset() as u32 != pointer_vector[4] {
+        world.read_footer_section(&mut r)?;
+        let footer_end = r.offset();
+
+        // Keep the loaded bytes around so unchanged sections can be saved verbatim
+        world.section_cache = Some(SectionCache::capture(
+            bytes,
+            &pointer_vector,
+            footer_end,
+            tile_columns,
+        ));
+        world.reindex_entities();
+
+        Ok(world)
+    }
+
+    // Reads the file header and the world header section. Everything stored
+    // in later sections is left empty.
+    fn read_header(r: &mut ByteReader) -> std::io::Result<(Self, Vec<u32>)> {
+        let version_integer = r.try_i32()?;
+
+        let magic = String::from_utf8_lossy(r.try_bytes(7)?).to_string();
+        if magic != "relogic" {
             return Err(std::io::Error::new(
                 std::io::ErrorKind::InvalidData,
-                "Pointer mismatch after signs section. Please open an issue at https://github.com/osbm/terraria-world-rs/issues"));
+                "Invalid magic string in the file header. Please open an issue at https://github.com/osbm/terraria-world-rs/issues"));
         }
 
-        // Parse entities
-        let mut npcs = Vec::new();
-        let mut mobs = Vec::new();
+        let savefile_type = r.try_u8()?;
+        let revision = r.try_u32()?;
+        let is_favorite = r.try_u64()?;
 
-        // Parse shimmered NPCs
-        let shimmered_npcs_count = r.i32();
-        let mut shimmered_npcs = Vec::with_capacity(shimmered_npcs_count as usize);
-        for _i in 0..shimmered_npcs_count {
-            let npc_id = r.i32();
-            shimmered_npcs.push(npc_id);
+        let pointer_count = r.try_u16()?;
+        let mut pointer_vector = vec![];
+        for _ in 0..pointer_count {
+            pointer_vector.push(r.try_u32()?);
+        }
+        if pointer_vector.len() < Section::ALL.len() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!(
+                    "Pointer table has {} entries, expected {}",
+                    pointer_vector.len(),
+                    Section::ALL.len()
+                ),
+            ));
         }
 
-        // Parse NPCs
-        let mut _npc_index = 0;
-        while r.bool() {
-            let npc_type = r.i32();
-            let npc_name = r.string(None);
-            let npc_position_x = r.f32();
-            let npc_position_y = r.f32();
-            let is_homeless = r.bool();
-            let npc_home = Coordinates {
-                x: r.i32(),
-                y: r.i32(),
-            };
-            let npc_flags = r.bits();
-            let npc_variation_index = r.i32();
-            if !npc_flags[0] {
-                let _npc_variation_index = 0i32;
-            }
-            let npc = NPC::new(
-                npc_type,
-                npc_name,
-                npc_position_x,
-                npc_position_y,
-                is_homeless,
-                npc_home,
-                npc_variation_index,
-            );
-            npcs.push(npc);
-            _npc_index += 1;
+        let tile_frame_important_count = r.try_i16()?.max(0);
+        let tile_frame_important_size = (tile_frame_important_count as usize).div_ceil(8);
+        let mut tile_frame_important = vec![];
+        for _ in 0..tile_frame_important_size {
+            let current_bits = r.try_bits()?;
+            tile_frame_important.extend(current_bits);
         }
+        tile_frame_important.truncate(tile_frame_important_count as usize);
 
-        // Parse mobs
-        while r.bool() {
-            let mob_type = r.i32();
-            let mob_position_x = r.f32();
-            let mob_position_y = r.f32();
-            let mob = Mob::new(mob_type, mob_position_x, mob_position_y);
-            mobs.push(mob);
+        if pointer_vector.first() != Some(&(r.offset() as u32)) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Pointer mismatch after file header. Please open an issue at https://github.com/osbm/terraria-world-rs/issues"));
         }
 
-        if r.offset() as u32 != pointer_vector[5] {
+        let world_name = r.try_string(None)?;
+        let generator_seed = r.try_string(None)?;
+        let generator_version = r.try_u64()?;
+        let uuid = r.try_uuid()?;
+        let id = r.try_i32()?;
+        let bounds_vec = vec![
+            r.try_i32()?, // left
+            r.try_i32()?, // right
+            r.try_i32()?, // top
+            r.try_i32()?, // bottom
+        ];
+
+        let world_height = r.try_i32()?;
+        let world_width = r.try_i32()?;
+        if !(1..=MAX_WORLD_WIDTH).contains(&world_width)
+            || !(1..=MAX_WORLD_HEIGHT).contains(&world_height)
+        {
             return Err(std::io::Error::new(
                 std::io::ErrorKind::InvalidData,
-                "Pointer mismatch after NPCs and mobs section. Please open an issue at https://github.com/osbm/terraria-world-rs/issues"));
+                format!("World header has an impossible size of {world_width}x{world_height}"),
+            ));
+        }
+        let difficulty_value = r.try_i32()?;
+        let is_drunk_world = r.try_bool()?;
+        let is_for_the_worthy = r.try_bool()?;
+        let is_tenth_anniversary = r.try_bool()?;
+        let is_the_constant = r.try_bool()?;
+        let is_bee_world = r.try_bool()?;
+        let is_upside_down = r.try_bool()?;
+        let is_trap_world = r.try_bool()?;
+        let is_zenith_world = r.try_bool()?;
+        let created_on = r.try_datetime()?;
+        let moon_style = r.try_u8()?;
+        let tree_style_separators = vec![r.try_i32()?, r.try_i32()?, r.try_i32()?];
+        let tree_style_properties = vec![r.try_i32()?, r.try_i32()?, r.try_i32()?, r.try_i32()?];
+        let moss_style_separators = vec![r.try_i32()?, r.try_i32()?, r.try_i32()?];
+        let moss_style_properties = vec![r.try_i32()?, r.try_i32()?, r.try_i32()?, r.try_i32()?];
+        let snow_background_style = r.try_i32()?;
+        let jungle_background_style = r.try_i32()?;
+        let hell_background_style = r.try_i32()?;
+        let spawn_point_x = r.try_i32()?;
+        let spawn_point_y = r.try_i32()?;
+        let underground_level = r.try_f64()?;
+        let cavern_level = r.try_f64()?;
+        let current_time = r.try_f64()?;
+        let is_daytime = r.try_bool()?;
+        let moon_phase = r.try_u32()?;
+        let blood_moon = r.try_bool()?;
+        let eclipse = r.try_bool()?;
+        let dungeon_point_x = r.try_i32()?;
+        let dungeon_point_y = r.try_i32()?;
+        let world_evil_type = r.try_bool()?;
+        let defeated_eye_of_cthulhu = r.try_bool()?;
+        let defeated_eater_of_worlds = r.try_bool()?;
+        let defeated_skeletron = r.try_bool()?;
+        let defeated_queen_bee = r.try_bool()?;
+        let defeated_the_twins = r.try_bool()?;
+        let defeated_the_destroyer = r.try_bool()?;
+        let defeated_skeletron_prime = r.try_bool()?;
+        let defeated_any_mechanical_boss = r.try_bool()?;
+        let defeated_plantera = r.try_bool()?;
+        let defeated_golem = r.try_bool()?;
+        let defeated_king_slime = r.try_bool()?;
+        let saved_goblin_tinkerer = r.try_bool()?;
+        let saved_wizard = r.try_bool()?;
+        let saved_mechanic = r.try_bool()?;
+        let defeated_goblin_army = r.try_bool()?;
+        let defeated_clown = r.try_bool()?;
+        let defeated_frost_moon = r.try_bool()?;
+        let defeated_pirate_invasion = r.try_bool()?;
+        let shadow_orbs_smashed_at_least_once = r.try_bool()?;
+        let shadow_orbs_spawn_meteorite = r.try_bool()?;
+        let shadow_orbs_evil_boss_counter = r.try_u8()?;
+        let altars_smashed = r.try_i32()?;
+        let is_hardmode = r.try_bool()?;
+        let party_is_doomed = !r.try_bool()?; // ???
+        let invasion_delay = r.try_i32()?;
+        let invasion_size = r.try_i32()?;
+        let invasion_type = r.try_i32()?;
+        let invasion_position = r.try_f64()?;
+        let time_left_slime_rain = r.try_f64()?;
+        let sundial_cooldown = r.try_u8()?;
+        let is_rain_active = r.try_bool()?;
+        let rain_time_left = r.try_i32()?;
+        let max_rain = r.try_f32()?;
+        let hardmode_ore_1 = r.try_i32()?;
+        let hardmode_ore_2 = r.try_i32()?;
+        let hardmode_ore_3 = r.try_i32()?;
+        let forest_background = r.try_i8()?;
+        let corruption_background = r.try_i8()?;
+        let jungle_background = r.try_i8()?;
+        let snow_background = r.try_i8()?;
+        let hallow_background = r.try_i8()?;
+        let crimson_background = r.try_i8()?;
+        let desert_background = r.try_i8()?;
+        let ocean_background = r.try_i8()?;
+        let cloud_background = r.try_i32()?;
+        let cloud_number = r.try_i16()?;
+        let wind_speed = r.try_f32()?;
+
+        let angler_today_quest_completed_by_count = r.try_i32()?;
+        let mut angler_today_quest_completed_by = vec![];
+        for _ in 0..angler_today_quest_completed_by_count {
+            let name = r.try_string(None)?;
+            if !name.is_empty() {
+                angler_today_quest_completed_by.push(name);
+            }
         }
 
-        // Parse tile entities
-        let tile_entities_count = r.i32();
-        let mut tile_entities = Vec::with_capacity(tile_entities_count as usize);
-        for _ in 0..tile_entities_count {
-            let te_type = r.u8();
-            let te_id = r.i32();
-            let te_position = Coordinates {
-                x: r.i16() as i32,
-                y: r.i16() as i32,
-            };
+        let saved_angler = r.try_bool()?;
+        let angler_daily_quest_target = r.try_i32()?;
+        let saved_stylist = r.try_bool()?;
+        let saved_tax_collector = r.try_bool()?;
+        let saved_golfer = r.try_bool()?;
+        let invasion_size_start = r.try_i32()?;
+        let cultist_delay = r.try_i32()?;
 
-            let te_extra = match te_type {
-                0 => {
-                    // Target Dummy
-                    let npc = r.i16();
-                    Some(TileEntityExtra::TargetDummy { npc })
-                }
-                1 => {
-                    // Item Frame
-                    let item_type = r.i16();
-                    let item_prefix = r.u8();
-                    let item_quantity = r.i16();
-                    let item = ItemStack {
-                        quantity: item_quantity,
-                        type_id: item_type as i32,
-                        prefix: item_prefix,
-                    };
-                    Some(TileEntityExtra::ItemFrame { item })
-                }
-                2 => {
-                    // Logic Sensor
-                    let logic_check = r.u8();
-                    let enabled = r.bool();
-                    Some(TileEntityExtra::LogicSensor {
-                        logic_check,
-                        enabled,
-                    })
-                }
-                3 => {
-                    // Mannequin
-                    let item_flags = r.bits();
-                    let dye_flags = r.bits();
-                    let mut mannequin_items = vec![None; item_flags.len()];
-                    let mut mannequin_dyes = vec![None; dye_flags.len()];
+        let mob_kills_count = r.try_i16()?;
+        let mut mob_kills = vec![];
+        for _ in 0..mob_kills_count {
+            mob_kills.push(r.try_i32()?);
+        }
+        let sundial_is_running = r.try_bool()?;
+        let defeated_duke_fishron = r.try_bool()?;
+        let defeated_martian_madness = r.try_bool()?;
+        let defeated_lunatic_cultist = r.try_bool()?;
+        let defeated_moon_lord = r.try_bool()?;
+        let defeated_pumpking = r.try_bool()?;
+        let defeated_mourning_wood = r.try_bool()?;
+        let defeated_ice_queen = r.try_bool()?;
+        let defeated_santa_nk1 = r.try_bool()?;
+        let defeated_everscream = r.try_bool()?;
+        let defeated_solar_pillar = r.try_bool()?;
+        let defeated_vortex_pillar = r.try_bool()?;
+        let defeated_nebula_pillar = r.try_bool()?;
+        let defeated_stardust_pillar = r.try_bool()?;
+        let lunar_events_pillars_present_solar = r.try_bool()?;
+        let lunar_events_pillars_present_vortex = r.try_bool()?;
+        let lunar_events_pillars_present_nebula = r.try_bool()?;
+        let lunar_events_pillars_present_stardust = r.try_bool()?;
+        let lunar_events_are_active = r.try_bool()?;
+        let party_center_active = r.try_bool()?;
+        let party_natural_active = r.try_bool()?;
+        let party_cooldown = r.try_i32()?;
+
+        let partying_npcs_count = r.try_i32()?;
+        let mut partying_npcs = vec![];
+        for _ in 0..partying_npcs_count {
+            partying_npcs.push(r.try_i32()?);
+        }
+
+        let is_sandstorm_active = r.try_bool()?;
+        let sandstorm_time_left = r.try_i32()?;
+        let sandstorm_severity = r.try_f32()?;
+        let sandstorm_intended_severity = r.try_f32()?;
+        let saved_bartender = r.try_bool()?;
+        let old_ones_army_tier_1 = r.try_bool()?;
+        let old_ones_army_tier_2 = r.try_bool()?;
+        let old_ones_army_tier_3 = r.try_bool()?;
+        let mushroom_background = r.try_i8()?;
+        let underworld_background = r.try_i8()?;
+        let forest_background_2 = r.try_i8()?;
+        let forest_background_3 = r.try_i8()?;
+        let forest_background_4 = r.try_i8()?;
+        let combat_book_used = r.try_bool()?;
+        let lantern_nights_on_cooldown = r.try_i32()?;
+        let lantern_night_genuine = r.try_bool()?;
+        let lantern_night_manual = r.try_bool()?;
+        let next_night_is_lantern_night = r.try_bool()?;
+
+        let treetop_variants_count = r.try_i32()?;
+        let mut treetop_variants = vec![];
+        for _ in 0..treetop_variants_count {
+            treetop_variants.push(r.try_i32()?);
+        }
+
+        let halloween_today = r.try_bool()?;
+        let christmas_today = r.try_bool()?;
+        let ore_1 = r.try_i32()?;
+        let ore_2 = r.try_i32()?;
+        let ore_3 = r.try_i32()?;
+        let ore_4 = r.try_i32()?;
+        let has_cat = r.try_bool()?;
+        let has_dog = r.try_bool()?;
+        let has_bunny = r.try_bool()?;
+        let defeated_empress_of_light = r.try_bool()?;
+        let defeated_queen_slime = r.try_bool()?;
+        let defeated_deerclops = r.try_bool()?;
+        let saved_slime_nerdy = r.try_bool()?;
+        let saved_merchant = r.try_bool()?;
+        let saved_demolitionist = r.try_bool()?;
+        let saved_party_girl = r.try_bool()?;
+        let saved_dye_trader = r.try_bool()?;
+        let saved_truffle = r.try_bool()?;
+        let saved_arms_dealer = r.try_bool()?;
+        let saved_nurse = r.try_bool()?;
+        let saved_princess = r.try_bool()?;
+        let combat_book_2_used = r.try_bool()?;
+        let peddler_satchel_used = r.try_bool()?;
+        let saved_slime_cool = r.try_bool()?;
+        let saved_slime_elder = r.try_bool()?;
+        let saved_slime_clumsy = r.try_bool()?;
+        let saved_slime_diva = r.try_bool()?;
+        let saved_slime_surly = r.try_bool()?;
+        let saved_slime_mystic = r.try_bool()?;
+        let saved_slime_squire = r.try_bool()?;
+        let moondial_is_running = r.try_bool()?;
+        let moondial_cooldown = r.try_u8()?;
 
-                    for (index, &flag) in item_flags.iter().enumerate() {
-                        if !flag {
-                            continue;
+        let game_progression = GameProgression {
+            defeated_eye_of_cthulhu,
+            defeated_eater_of_worlds,
+            defeated_skeletron,
+            defeated_queen_bee,
+            defeated_the_twins,
+            defeated_the_destroyer,
+            defeated_skeletron_prime,
+            defeated_any_mechanical_boss,
+            defeated_plantera,
+            defeated_golem,
+            defeated_king_slime,
+            defeated_duke_fishron,
+            defeated_martian_madness,
+            defeated_lunatic_cultist,
+            defeated_moon_lord,
+            defeated_pumpking,
+            defeated_mourning_wood,
+            defeated_ice_queen,
+            defeated_santa_nk1,
+            defeated_everscream,
+            defeated_empress_of_light,
+            defeated_queen_slime,
+            defeated_deerclops,
+            defeated_solar_pillar,
+            defeated_vortex_pillar,
+            defeated_nebula_pillar,
+            defeated_stardust_pillar,
+            lunar_events_pillars_present_solar,
+            lunar_events_pillars_present_vortex,
+            lunar_events_pillars_present_nebula,
+            lunar_events_pillars_present_stardust,
+            lunar_events_are_active,
+            defeated_goblin_army,
+            defeated_clown,
+            defeated_frost_moon,
+            defeated_pirate_invasion,
+            is_hardmode,
+            shadow_orbs_smashed_at_least_once,
+            shadow_orbs_spawn_meteorite,
+            shadow_orbs_evil_boss_counter,
+            altars_smashed,
+        };
+
+        let saved_npcs = SavedNPCs {
+            saved_goblin_tinkerer,
+            saved_wizard,
+            saved_mechanic,
+            saved_angler,
+            saved_stylist,
+            saved_tax_collector,
+            saved_golfer,
+            saved_bartender,
+            saved_slime_nerdy,
+            saved_merchant,
+            saved_demolitionist,
+            saved_party_girl,
+            saved_dye_trader,
+            saved_truffle,
+            saved_arms_dealer,
+            saved_nurse,
+            saved_princess,
+            saved_slime_cool,
+            saved_slime_elder,
+            saved_slime_clumsy,
+            saved_slime_diva,
+            saved_slime_surly,
+            saved_slime_mystic,
+            saved_slime_squire,
+        };
+
+        let environment = WorldEnvironment {
+            moon_style,
+            tree_style_separators,
+            tree_style_properties,
+            moss_style_separators,
+            moss_style_properties,
+            snow_background_style,
+            jungle_background_style,
+            hell_background_style,
+            forest_background,
+            corruption_background,
+            jungle_background,
+            snow_background,
+            hallow_background,
+            crimson_background,
+            desert_background,
+            ocean_background,
+            mushroom_background,
+            underworld_background,
+            forest_background_2,
+            forest_background_3,
+            forest_background_4,
+            cloud_background,
+            cloud_number,
+            wind_speed,
+            treetop_variants,
+        };
+
+        let weather_events = WeatherAndEvents {
+            current_time,
+            is_daytime,
+            moon_phase,
+            blood_moon,
+            eclipse,
+            is_rain_active,
+            rain_time_left,
+            max_rain,
+            is_sandstorm_active,
+            sandstorm_time_left,
+            sandstorm_severity,
+            sandstorm_intended_severity,
+            halloween_today,
+            christmas_today,
+            party_center_active,
+            party_natural_active,
+            party_cooldown,
+            partying_npcs,
+            party_is_doomed,
+            lantern_nights_on_cooldown,
+            lantern_night_genuine,
+            lantern_night_manual,
+            next_night_is_lantern_night,
+        };
+
+        let invasions = InvasionData {
+            invasion_delay,
+            invasion_size,
+            invasion_type,
+            invasion_position,
+            invasion_size_start,
+            cultist_delay,
+            time_left_slime_rain,
+            old_ones_army_tier_1,
+            old_ones_army_tier_2,
+            old_ones_army_tier_3,
+        };
+
+        let world = World {
+            version_integer,
+            savefile_type,
+            revision,
+            is_favorite,
+            tile_frame_important,
+            world_name,
+            generator_seed,
+            generator_version,
+            uuid,
+            id,
+            bounds_vec,
+            world_height,
+            world_width,
+            difficulty_value,
+            is_drunk_world,
+            is_for_the_worthy,
+            is_tenth_anniversary,
+            is_the_constant,
+            is_bee_world,
+            is_upside_down,
+            is_trap_world,
+            is_zenith_world,
+            created_on,
+            game_progression,
+            saved_npcs,
+            environment,
+            weather_events,
+            invasions,
+            spawn_point_x,
+            spawn_point_y,
+            underground_level,
+            cavern_level,
+            dungeon_point_x,
+            dungeon_point_y,
+            world_evil_type,
+            hardmode_ore_1,
+            hardmode_ore_2,
+            hardmode_ore_3,
+            ore_1,
+            ore_2,
+            ore_3,
+            ore_4,
+            has_cat,
+            has_dog,
+            has_bunny,
+            combat_book_used,
+            combat_book_2_used,
+            peddler_satchel_used,
+            angler_today_quest_completed_by,
+            angler_daily_quest_target,
+            mob_kills,
+            sundial_cooldown,
+            sundial_is_running,
+            moondial_is_running,
+            moondial_cooldown,
+            tiles: TileMatrix::new((0, 0)),
+            chests_max_items: 40,
+            chests: Vec::new(),
+            signs: Vec::new(),
+            npcs: Vec::new(),
+            mobs: Vec::new(),
+            shimmered_npcs: Vec::new(),
+            tile_entities: Vec::new(),
+            weighed_pressure_plates: Vec::new(),
+            rooms: Vec::new(),
+            bestiary: Bestiary::new(Vec::new(), Vec::new(), Vec::new()),
+            journey_powers: JourneyPowers::new(),
+            section_cache: None,
+            entity_index: EntityIndex::default(),
+            change_log: None,
+        };
+
+        Ok((world, pointer_vector))
+    }
+
+    fn read_chests_section(&mut self, r: &mut ByteReader) -> std::io::Result<()> {
+        (self.chests_max_items, self.chests) = Self::read_chests(r)?;
+        Ok(())
+    }
+
+    fn read_chests(r: &mut ByteReader) -> std::io::Result<(i16, Vec<Chest>)> {
+        // --- CHEST PARSING ---
+        let chests_count = r.try_i16()?;
+        let chests_max_items = r.try_i16()?;
+        let mut chests = Vec::with_capacity(r.capacity_hint(chests_count as i64));
+        for _ in 0..chests_count {
+            let chest_x = r.try_i32()?;
+            let chest_y = r.try_i32()?;
+            let chest_name = r.try_string(None)?;
+            let mut chest_contents = Vec::with_capacity(r.capacity_hint(chests_max_items as i64));
+            for _ in 0..chests_max_items {
+                let item_quantity = r.try_i16()?;
+                if item_quantity > 0 {
+                    let item_type = r.try_i32()?;
+                    let item_prefix = r.try_u8()?;
+                    chest_contents.push(Some(ItemStack {
+                        quantity: item_quantity,
+                        type_id: item_type,
+                        prefix: item_prefix,
+                    }));
+                } else {
+                    chest_contents.push(None);
+                }
+            }
+            chests.push(Chest {
+                position: Coordinates {
+                    x: chest_x,
+                    y: chest_y,
+                },
+                name: chest_name,
+                contents: chest_contents,
+            });
+        }
+
+        Ok((chests_max_items, chests))
+    }
+
+    fn read_signs_section(&mut self, r: &mut ByteReader) -> std::io::Result<()> {
+        self.signs = Self::read_signs(r)?;
+        Ok(())
+    }
+
+    fn read_signs(r: &mut ByteReader) -> std::io::Result<Vec<Sign>> {
+        // --- SIGN PARSING ---
+        let signs_count = r.try_i16()?;
+        let mut signs = Vec::with_capacity(r.capacity_hint(signs_count as i64));
+        for _ in 0..signs_count {
+            let sign_text = r.try_string(None)?;
+            let sign_x = r.try_i32()?;
+            let sign_y = r.try_i32()?;
+            signs.push(Sign {
+                text: sign_text,
+                position: Coordinates {
+                    x: sign_x,
+                    y: sign_y,
+                },
+            });
+        }
+
+        Ok(signs)
+    }
+
+    fn read_npcs_section(&mut self, r: &mut ByteReader) -> std::io::Result<()> {
+        // Parse entities
+        let mut npcs = Vec::new();
+        let mut mobs = Vec::new();
+
+        // Parse shimmered NPCs
+        let shimmered_npcs_count = r.try_i32()?;
+        let mut shimmered_npcs = Vec::with_capacity(r.capacity_hint(shimmered_npcs_count as i64));
+        for _i in 0..shimmered_npcs_count {
+            let npc_id = r.try_i32()?;
+            shimmered_npcs.push(npc_id);
+        }
+
+        // Parse NPCs
+        let mut _npc_index = 0;
+        while r.try_bool()? {
+            let npc_type = r.try_i32()?;
+            let npc_name = r.try_string(None)?;
+            let npc_position_x = r.try_f32()?;
+            let npc_position_y = r.try_f32()?;
+            let is_homeless = r.try_bool()?;
+            let npc_home = Coordinates {
+                x: r.try_i32()?,
+                y: r.try_i32()?,
+            };
+            let npc_flags = r.try_bits()?;
+            let npc_variation_index = r.try_i32()?;
+            if !npc_flags[0] {
+                let _npc_variation_index = 0i32;
+            }
+            let npc = NPC::new(
+                npc_type,
+                npc_name,
+                npc_position_x,
+                npc_position_y,
+                is_homeless,
+                npc_home,
+                npc_variation_index,
+            );
+            npcs.push(npc);
+            _npc_index += 1;
+        }
+
+        // Parse mobs
+        while r.try_bool()? {
+            let mob_type = r.try_i32()?;
+            let mob_position_x = r.try_f32()?;
+            let mob_position_y = r.try_f32()?;
+            let mob = Mob::new(mob_type, mob_position_x, mob_position_y);
+            mobs.push(mob);
+        }
+
+        self.shimmered_npcs = shimmered_npcs;
+        self.npcs = npcs;
+        self.mobs = mobs;
+        Ok(())
+    }
+
+    fn read_tile_entities_section(&mut self, r: &mut ByteReader) -> std::io::Result<()> {
+        self.tile_entities = Self::read_tile_entities(r)?;
+        Ok(())
+    }
+
+    fn read_tile_entities(r: &mut ByteReader) -> std::io::Result<Vec<TileEntity>> {
+        // Parse tile entities
+        let tile_entities_count = r.try_i32()?;
+        let mut tile_entities = Vec::with_capacity(r.capacity_hint(tile_entities_count as i64));
+        for _ in 0..tile_entities_count {
+            let te_type = r.try_u8()?;
+            let te_id = r.try_i32()?;
+            let te_position = Coordinates {
+                x: r.try_i16()? as i32,
+                y: r.try_i16()? as i32,
+            };
+
+            let te_extra = match te_type {
+                0 => {
+                    // Target Dummy
+                    let npc = r.try_i16()?;
+                    Some(TileEntityExtra::TargetDummy { npc })
+                }
+                1 => {
+                    // Item Frame
+                    let item_type = r.try_i16()?;
+                    let item_prefix = r.try_u8()?;
+                    let item_quantity = r.try_i16()?;
+                    let item = ItemStack {
+                        quantity: item_quantity,
+                        type_id: item_type as i32,
+                        prefix: item_prefix,
+                    };
+                    Some(TileEntityExtra::ItemFrame { item })
+                }
+                2 => {
+                    // Logic Sensor
+                    let logic_check = r.try_u8()?;
+                    let enabled = r.try_bool()?;
+                    Some(TileEntityExtra::LogicSensor {
+                        logic_check,
+                        enabled,
+                    })
+                }
+                3 => {
+                    // Mannequin
+                    let item_flags = r.try_bits()?;
+                    let dye_flags = r.try_bits()?;
+                    let mut mannequin_items = vec![None; item_flags.len()];
+                    let mut mannequin_dyes = vec![None; dye_flags.len()];
+
+                    for (index, &flag) in item_flags.iter().enumerate() {
+                        if !flag {
+                            continue;
                         }
-                        let item_type = r.i16();
-                        let item_prefix = r.u8();
-                        let item_quantity = r.i16();
+                        let item_type = r.try_i16()?;
+                        let item_prefix = r.try_u8()?;
+                        let item_quantity = r.try_i16()?;
                         mannequin_items[index] = Some(ItemStack {
                             quantity: item_quantity,
                             type_id: item_type as i32,
@@ -726,9 +1112,9 @@ impl World {
                         if !flag {
                             continue;
                         }
-                        let item_type = r.i16();
-                        let item_prefix = r.u8();
-                        let item_quantity = r.i16();
+                        let item_type = r.try_i16()?;
+                        let item_prefix = r.try_u8()?;
+                        let item_quantity = r.try_i16()?;
                         mannequin_dyes[index] = Some(ItemStack {
                             quantity: item_quantity,
                             type_id: item_type as i32,
@@ -743,9 +1129,9 @@ impl World {
                 }
                 4 => {
                     // Weapon Rack
-                    let item_type = r.i16();
-                    let item_prefix = r.u8();
-                    let item_quantity = r.i16();
+                    let item_type = r.try_i16()?;
+                    let item_prefix = r.try_u8()?;
+                    let item_quantity = r.try_i16()?;
                     let item = ItemStack {
                         quantity: item_quantity,
                         type_id: item_type as i32,
@@ -755,7 +1141,7 @@ impl World {
                 }
                 5 => {
                     // Hat Rack
-                    let item_flags = r.bits();
+                    let item_flags = r.try_bits()?;
                     let mut rack_items = vec![None; 2];
                     let mut rack_dyes = vec![None; 2];
 
@@ -763,9 +1149,9 @@ impl World {
                         if !flag {
                             continue;
                         }
-                        let item_type = r.i16();
-                        let item_prefix = r.u8();
-                        let item_quantity = r.i16();
+                        let item_type = r.try_i16()?;
+                        let item_prefix = r.try_u8()?;
+                        let item_quantity = r.try_i16()?;
                         rack_items[index] = Some(ItemStack {
                             quantity: item_quantity,
                             type_id: item_type as i32,
@@ -777,9 +1163,9 @@ impl World {
                         if !flag {
                             continue;
                         }
-                        let item_type = r.i16();
-                        let item_prefix = r.u8();
-                        let item_quantity = r.i16();
+                        let item_type = r.try_i16()?;
+                        let item_prefix = r.try_u8()?;
+                        let item_quantity = r.try_i16()?;
                         rack_dyes[index] = Some(ItemStack {
                             quantity: item_quantity,
                             type_id: item_type as i32,
@@ -794,9 +1180,9 @@ impl World {
                 }
                 6 => {
                     // Food Plate
-                    let item_type = r.i16();
-                    let item_prefix = r.u8();
-                    let item_quantity = r.i16();
+                    let item_type = r.try_i16()?;
+                    let item_prefix = r.try_u8()?;
+                    let item_quantity = r.try_i16()?;
                     let item = ItemStack {
                         quantity: item_quantity,
                         type_id: item_type as i32,
@@ -818,331 +1204,117 @@ impl World {
             tile_entities.push(tile_entity);
         }
 
-        if r.offset() as u32 != pointer_vector[6] {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                "Pointer mismatch after tile entities section. Please open an issue at https://github.com/osbm/terraria-world-rs/issues"));
-        }
+        Ok(tile_entities)
+    }
 
+    fn read_pressure_plates_section(&mut self, r: &mut ByteReader) -> std::io::Result<()> {
         // Parse weighed pressure plates
-        let weighed_pressure_plates_count = r.i32();
+        let weighed_pressure_plates_count = r.try_i32()?;
         let mut weighed_pressure_plates =
-            Vec::with_capacity(weighed_pressure_plates_count as usize);
+            Vec::with_capacity(r.capacity_hint(weighed_pressure_plates_count as i64));
         for _ in 0..weighed_pressure_plates_count {
             let position = Coordinates {
-                x: r.i32(),
-                y: r.i32(),
+                x: r.try_i32()?,
+                y: r.try_i32()?,
             };
             weighed_pressure_plates.push(WeighedPressurePlate::new(position));
         }
 
-        if r.offset() as u32 != pointer_vector[7] {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                "Pointer mismatch after weighed pressure plates section. Please open an issue at https://github.com/osbm/terraria-world-rs/issues"));
-        }
+        self.weighed_pressure_plates = weighed_pressure_plates;
+        Ok(())
+    }
 
+    fn read_town_manager_section(&mut self, r: &mut ByteReader) -> std::io::Result<()> {
         // Parse town manager (rooms)
-        let rooms_count = r.i32();
-        let mut rooms = Vec::with_capacity(rooms_count as usize);
+        let rooms_count = r.try_i32()?;
+        let mut rooms = Vec::with_capacity(r.capacity_hint(rooms_count as i64));
         for _ in 0..rooms_count {
-            let npc = r.i32();
+            let npc = r.try_i32()?;
             let position = Coordinates {
-                x: r.i32(),
-                y: r.i32(),
+                x: r.try_i32()?,
+                y: r.try_i32()?,
             };
             rooms.push(Room::new(npc, position));
         }
 
-        if r.offset() as u32 != pointer_vector[8] {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                "Pointer mismatch after rooms section. Please open an issue at https://github.com/osbm/terraria-world-rs/issues"));
-        }
-
-        // Parse bestiary
-        let bestiary_kills_count = r.i32();
-        let mut bestiary_kills = Vec::with_capacity(bestiary_kills_count as usize);
-        for _ in 0..bestiary_kills_count {
-            let entity = r.string(None);
-            let kills = r.i32();
-            bestiary_kills.push((entity, kills));
-        }
-
-        let bestiary_sightings_count = r.i32();
-        let mut bestiary_sightings = Vec::with_capacity(bestiary_sightings_count as usize);
-        for _ in 0..bestiary_sightings_count {
-            bestiary_sightings.push(r.string(None));
-        }
-
-        let bestiary_chats_count = r.i32();
-        let mut bestiary_chats = Vec::with_capacity(bestiary_chats_count as usize);
-        for _ in 0..bestiary_chats_count {
-            bestiary_chats.push(r.string(None));
-        }
-
-        let bestiary = Bestiary::new(bestiary_kills, bestiary_sightings, bestiary_chats);
-
-        if r.offset() as u32 != pointer_vector[9] {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                "Pointer mismatch after bestiary section. Please open an issue at https://github.com/osbm/terraria-world-rs/issues"));
-        }
-
-        // Parse journey powers
-        let mut journey_powers = JourneyPowers::new();
-        while r.bool() {
-            let power_id = r.i16();
-            match power_id {
-                0 => journey_powers.freeze_time = r.bool(),
-                8 => journey_powers.time_rate = r.f32(),
-                9 => journey_powers.freeze_rain = r.bool(),
-                10 => journey_powers.freeze_wind = r.bool(),
-                12 => journey_powers.difficulty = r.f32(),
-                13 => journey_powers.freeze_biome_spread = r.bool(),
-                _ => {
-                    println!("Unknown journey power ID: {power_id} please open a issue at github.com/osbm/terraria-world-rs");
-                }
-            }
-        }
-
-        if r.offset() as u32 != pointer_vector[10] {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                "Pointer mismatch after journey powers section. Please open an issue at https://github.com/osbm/terraria-world-rs/issues"));
-        }
-
-        // Parse footer
-        if !r.bool() {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                InvalidFooterError("Invalid footer".to_string()),
-            ));
-        }
-        let footer_world_name = r.string(None);
-        if footer_world_name != world_name {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                InvalidFooterError("Invalid footer - world name mismatch".to_string()),
-            ));
-        }
-        let footer_world_id = r.i32();
-        if footer_world_id != id {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                InvalidFooterError("Invalid footer - world ID mismatch".to_string()),
-            ));
-        }
-
-        let game_progression = GameProgression {
-            defeated_eye_of_cthulhu,
-            defeated_eater_of_worlds,
-            defeated_skeletron,
-            defeated_queen_bee,
-            defeated_the_twins,
-            defeated_the_destroyer,
-            defeated_skeletron_prime,
-            defeated_any_mechanical_boss,
-            defeated_plantera,
-            defeated_golem,
-            defeated_king_slime,
-            defeated_duke_fishron,
-            defeated_martian_madness,
-            defeated_lunatic_cultist,
-            defeated_moon_lord,
-            defeated_pumpking,
-            defeated_mourning_wood,
-            defeated_ice_queen,
-            defeated_santa_nk1,
-            defeated_everscream,
-            defeated_empress_of_light,
-            defeated_queen_slime,
-            defeated_deerclops,
-            defeated_solar_pillar,
-            defeated_vortex_pillar,
-            defeated_nebula_pillar,
-            defeated_stardust_pillar,
-            lunar_events_pillars_present_solar,
-            lunar_events_pillars_present_vortex,
-            lunar_events_pillars_present_nebula,
-            lunar_events_pillars_present_stardust,
-            lunar_events_are_active,
-            defeated_goblin_army,
-            defeated_clown,
-            defeated_frost_moon,
-            defeated_pirate_invasion,
-            is_hardmode,
-            shadow_orbs_smashed_at_least_once,
-            shadow_orbs_spawn_meteorite,
-            shadow_orbs_evil_boss_counter,
-            altars_smashed,
-        };
-
-        let saved_npcs = SavedNPCs {
-            saved_goblin_tinkerer,
-            saved_wizard,
-            saved_mechanic,
-            saved_angler,
-            saved_stylist,
-            saved_tax_collector,
-            saved_golfer,
-            saved_bartender,
-            saved_slime_nerdy,
-            saved_merchant,
-            saved_demolitionist,
-            saved_party_girl,
-            saved_dye_trader,
-            saved_truffle,
-            saved_arms_dealer,
-            saved_nurse,
-            saved_princess,
-            saved_slime_cool,
-            saved_slime_elder,
-            saved_slime_clumsy,
-            saved_slime_diva,
-            saved_slime_surly,
-            saved_slime_mystic,
-            saved_slime_squire,
-        };
+        self.rooms = rooms;
+        Ok(())
+    }
 
-        let environment = WorldEnvironment {
-            moon_style,
-            tree_style_separators,
-            tree_style_properties,
-            moss_style_separators,
-            moss_style_properties,
-            snow_background_style,
-            jungle_background_style,
-            hell_background_style,
-            forest_background,
-            corruption_background,
-            jungle_background,
-            snow_background,
-            hallow_background,
-            crimson_background,
-            desert_background,
-            ocean_background,
-            mushroom_background,
-            underworld_background,
-            forest_background_2,
-            forest_background_3,
-            forest_background_4,
-            cloud_background,
-            cloud_number,
-            wind_speed,
-            treetop_variants,
-        };
+    fn read_bestiary_section(&mut self, r: &mut ByteReader) -> std::io::Result<()> {
+        // Parse bestiary
+        let bestiary_kills_count = r.try_i32()?;
+        let mut bestiary_kills = Vec::with_capacity(r.capacity_hint(bestiary_kills_count as i64));
+        for _ in 0..bestiary_kills_count {
+            let entity = r.try_string(None)?;
+            let kills = r.try_i32()?;
+            bestiary_kills.push((entity, kills));
+        }
 
-        let weather_events = WeatherAndEvents {
-            current_time,
-            is_daytime,
-            moon_phase,
-            blood_moon,
-            eclipse,
-            is_rain_active,
-            rain_time_left,
-            max_rain,
-            is_sandstorm_active,
-            sandstorm_time_left,
-            sandstorm_severity,
-            sandstorm_intended_severity,
-            halloween_today,
-            christmas_today,
-            party_center_active,
-            party_natural_active,
-            party_cooldown,
-            partying_npcs,
-            party_is_doomed,
-            lantern_nights_on_cooldown,
-            lantern_night_genuine,
-            lantern_night_manual,
-            next_night_is_lantern_night,
-        };
+        let bestiary_sightings_count = r.try_i32()?;
+        let mut bestiary_sightings =
+            Vec::with_capacity(r.capacity_hint(bestiary_sightings_count as i64));
+        for _ in 0..bestiary_sightings_count {
+            bestiary_sightings.push(r.try_string(None)?);
+        }
 
-        let invasions = InvasionData {
-            invasion_delay,
-            invasion_size,
-            invasion_type,
-            invasion_position,
-            invasion_size_start,
-            cultist_delay,
-            time_left_slime_rain,
-            old_ones_army_tier_1,
-            old_ones_army_tier_2,
-            old_ones_army_tier_3,
-        };
+        let bestiary_chats_count = r.try_i32()?;
+        let mut bestiary_chats = Vec::with_capacity(r.capacity_hint(bestiary_chats_count as i64));
+        for _ in 0..bestiary_chats_count {
+            bestiary_chats.push(r.try_string(None)?);
+        }
 
-        let world = World {
-            version_integer,
-            savefile_type,
-            revision,
-            is_favorite,
-            tile_frame_important,
-            world_name,
-            generator_seed,
-            generator_version,
-            uuid,
-            id,
-            bounds_vec,
-            world_height,
-            world_width,
-            difficulty_value,
-            is_drunk_world,
-            is_for_the_worthy,
-            is_tenth_anniversary,
-            is_the_constant,
-            is_bee_world,
-            is_upside_down,
-            is_trap_world,
-            is_zenith_world,
-            created_on,
-            game_progression,
-            saved_npcs,
-            environment,
-            weather_events,
-            invasions,
-            spawn_point_x,
-            spawn_point_y,
-            underground_level,
-            cavern_level,
-            dungeon_point_x,
-            dungeon_point_y,
-            world_evil_type,
-            hardmode_ore_1,
-            hardmode_ore_2,
-            hardmode_ore_3,
-            ore_1,
-            ore_2,
-            ore_3,
-            ore_4,
-            has_cat,
-            has_dog,
-            has_bunny,
-            combat_book_used,
-            combat_book_2_used,
-            peddler_satchel_used,
-            angler_today_quest_completed_by,
-            angler_daily_quest_target,
-            mob_kills,
-            sundial_cooldown,
-            sundial_is_running,
-            moondial_is_running,
-            moondial_cooldown,
-            tiles,
-            chests_max_items,
-            chests,
-            signs,
-            npcs,
-            mobs,
-            shimmered_npcs,
-            tile_entities,
-            weighed_pressure_plates,
-            rooms,
-            bestiary,
-            journey_powers,
-        };
+        let bestiary = Bestiary::new(bestiary_kills, bestiary_sightings, bestiary_chats);
 
-        Ok(world)
+        self.bestiary = bestiary;
+        Ok(())
+    }
+
+    fn read_journey_powers_section(&mut self, r: &mut ByteReader) -> std::io::Result<()> {
+        // Parse journey powers
+        let mut journey_powers = JourneyPowers::new();
+        while r.try_bool()? {
+            let power_id = r.try_i16()?;
+            match power_id {
+                0 => journey_powers.freeze_time = r.try_bool()?,
+                8 => journey_powers.time_rate = r.try_f32()?,
+                9 => journey_powers.freeze_rain = r.try_bool()?,
+                10 => journey_powers.freeze_wind = r.try_bool()?,
+                12 => journey_powers.difficulty = r.try_f32()?,
+                13 => journey_powers.freeze_biome_spread = r.try_bool()?,
+                _ => {
+                    println!("Unknown journey power ID: {power_id} please open a issue at github.com/osbm/terraria-world-rs");
+                }
+            }
+        }
+
+        self.journey_powers = journey_powers;
+        Ok(())
+    }
+
+    fn read_footer_section(&self, r: &mut ByteReader) -> std::io::Result<()> {
+        // Parse footer
+        if !r.try_bool()? {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                InvalidFooterError("Invalid footer".to_string()),
+            ));
+        }
+        let footer_world_name = r.try_string(None)?;
+        if footer_world_name != self.world_name {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                InvalidFooterError("Invalid footer - world name mismatch".to_string()),
+            ));
+        }
+        let footer_world_id = r.try_i32()?;
+        if footer_world_id != self.id {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                InvalidFooterError("Invalid footer - world ID mismatch".to_string()),
+            ));
+        }
+        Ok(())
     }
 
     pub fn version(&self) -> &str {
@@ -1215,6 +1387,7 @@ impl World {
     // set block by name
     pub fn set_block(&mut self, x: usize, y: usize, block_name: &str) {
         if x < self.world_width as usize && y < self.world_height as usize {
+            self.touch_tiles(Rect::new(x as i32, y as i32, 1, 1));
             let tile = &mut self.tiles.tiles[x][y];
             tile.set_block_name(block_name);
         }
@@ -1235,6 +1408,7 @@ impl World {
 
     pub fn set_wall(&mut self, x: usize, y: usize, wall_name: &str) {
         if x < self.world_width as usize && y < self.world_height as usize {
+            self.touch_tiles(Rect::new(x as i32, y as i32, 1, 1));
             let tile = &mut self.tiles.tiles[x][y];
             tile.set_wall_name(wall_name);
         } else {
@@ -1247,6 +1421,7 @@ impl World {
 
     pub fn place_tile(&mut self, x: usize, y: usize, tile: Tile) {
         if x < self.world_width as usize && y < self.world_height as usize {
+            self.touch_tiles(Rect::new(x as i32, y as i32, 1, 1));
             self.tiles.tiles[x][y] = tile;
         } else {
             eprintln!(
@@ -1256,227 +1431,184 @@ impl World {
         }
     }
 
-    // there is 3 types of corruption: TODO make it 100% match the game
-    // 0 - Corruption
-    // 1 - Crimson
-    // 2 - Hallow
-    // and game tells us how many of each block is the percentage of the world
-    // like your world is 4% corrupted, 2% crimson, and 1% hallowed
+    /// Percentages of corruption, crimson and hallow, unrounded.
+    /// See [`World::world_status`] for the numbers the Dryad shows.
     pub fn get_corruption_stats(&self) -> (f32, f32, f32) {
-        // https://terraria.wiki.gg/wiki/Dryad#World_status
-        let mut corruption_count = 0;
-        let mut crimson_count = 0;
-        let mut hallow_count = 0;
-        let mut total_nonempty_tiles = 0;
-
-        let corruption_blocks = [
-            "CORRUPT_ICE",
-            "CORRUPT_HARDENED_SAND",
-            "CORRUPT_SANDSTONE",
-            "CORRUPT_VINES",
-            "CRIMSON_JUNGLE_GRASS",
-            "CORRUPT_GRASS",
-            "EBONSTONE",
-            "EBONSAND",
-            "EBONWOOD",
-        ];
-
-        let crimson_blocks = [
-            "CRIMSON_HARDENED_SAND",
-            "CRIMSON_HARDENED_SANDSTONE",
-            "CRIMSON_VINES",
-            "CRIMSTONE",
-            "CRIMSAND",
-            "CRIMTANE_THORNS",
-            "CRIMSON_JUNGLE_GRASS",
-            "FLESH_ICE",
-            "FLESH_WEEDS",
-            "FLESH_GRASS",
-        ];
-
-        let hallow_blocks = [
-            "HALLOWED_ICE",
-            "HALLOW_HARDENED_SAND",
-            "HALLOW_SANDSTONE",
-            "PEARLSAND",
-            "PEARLSTONE",
-            "GOLF_GRASS_HALLOWED",
-            "HALLOWED_GRASS",
-            "HALLOWED_PLANTS",
-            "HALLOWED_PLANTS2",
-            "HALLOWED_VINES",
-        ];
-
-        let solid_blocks = [
-            "STONE",
-            "GRASS",
-            // "DIRT",
-            "ICE",
-            "JUNGLE_GRASS",
-            "MOWED_GOLF_GRASS",
-        ];
-
-        for x in 0..self.world_width as usize {
-            for y in 0..self.world_height as usize {
-                let tile = &self.tiles.tiles[x][y];
-                let block_name = tile.get_block_name();
-
-                if corruption_blocks.contains(&block_name) {
-                    corruption_count += 1;
-                } else if crimson_blocks.contains(&block_name) {
-                    crimson_count += 1;
-                } else if hallow_blocks.contains(&block_name) {
-                    hallow_count += 1;
-                }
-
-                if solid_blocks.contains(&block_name) {
-                    total_nonempty_tiles += 1;
-                }
-            }
-        }
-        total_nonempty_tiles += corruption_count + hallow_count + crimson_count;
-
+        let status = self.world_status();
         (
-            corruption_count as f32 * 100.0 / total_nonempty_tiles as f32,
-            crimson_count as f32 * 100.0 / total_nonempty_tiles as f32,
-            hallow_count as f32 * 100.0 / total_nonempty_tiles as f32,
+            status.evil_exact as f32,
+            status.blood_exact as f32,
+            status.good_exact as f32,
         )
     }
 
     pub fn read_from_json(path: &str) -> std::io::Result<Self> {
         let file = std::fs::File::open(path)?;
         let reader = std::io::BufReader::new(file);
-        let world = serde_json::from_reader(reader)
+        let mut world: Self = serde_json::from_reader(reader)
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        world.reindex_entities();
         Ok(world)
     }
 
     pub fn save_as_wld(&self, path: &str) -> std::io::Result<()> {
-        // Create all section writers
-        let world_header_writer = self.write_world_header_section();
-        let tiles_writer = self.write_tiles_section();
-        let chests_writer = self.write_chests_section();
-        let signs_writer = self.write_signs_section();
-        let npcs_writer = self.write_npcs_section();
-        let tile_entities_writer = self.write_tile_entities_section();
-        let pressure_plates_writer = self.write_pressure_plates_section();
-        let town_manager_writer = self.write_town_manager_section();
-        let bestiary_writer = self.write_bestiary_section();
-        let journey_powers_writer = self.write_journey_powers_section();
-        let footer_writer = self.write_footer_section();
-
-        // Create header writer with placeholders
-        let mut header_writer = ByteWriter::new();
-        header_writer.i32(self.version_integer);
-        header_writer.bytes("relogic".as_bytes());
-        header_writer.u8(self.savefile_type);
-        header_writer.u32(self.revision);
-        header_writer.u64(self.is_favorite);
-        header_writer.u16(11); // 11 sections
-
-        // Write placeholder pointers (will be updated later)
-        for _ in 0..11 {
-            header_writer.u32(0);
-        }
-
-        // Write tile_frame_important count and bits in the file header
-        let original_count = self.tile_frame_important.len() as i16;
-        header_writer.i16(original_count);
-        for chunk in self.tile_frame_important.chunks(8) {
-            header_writer.bits(chunk);
-        }
-
-        // Calculate section lengths and update pointers
-        let mut current_offset = header_writer.offset() as u32;
-
-        // Update pointer vector with actual offsets
-        let mut pointer_vector = Vec::new();
-
-        // Section 0: World Header (starts after the fixed header)
-        pointer_vector.push(current_offset);
-        current_offset += world_header_writer.offset() as u32;
-
-        // Section 1: Tiles
-        pointer_vector.push(current_offset);
-        current_offset += tiles_writer.offset() as u32;
-
-        // Section 2: Chests
-        pointer_vector.push(current_offset);
-        current_offset += chests_writer.offset() as u32;
-
-        // Section 3: Signs
-        pointer_vector.push(current_offset);
-        current_offset += signs_writer.offset() as u32;
-
-        // Section 4: NPCs
-        pointer_vector.push(current_offset);
-        current_offset += npcs_writer.offset() as u32;
-
-        // Section 5: Tile Entities
-        pointer_vector.push(current_offset);
-        current_offset += tile_entities_writer.offset() as u32;
-
-        // Section 6: Pressure Plates
-        pointer_vector.push(current_offset);
-        current_offset += pressure_plates_writer.offset() as u32;
-
-        // Section 7: Town Manager
-        pointer_vector.push(current_offset);
-        current_offset += town_manager_writer.offset() as u32;
-
-        // Section 8: Bestiary
-        pointer_vector.push(current_offset);
-        current_offset += bestiary_writer.offset() as u32;
+        self.save_as_wld_with_options(path, &SaveOptions::default())
+    }
 
-        // Section 9: Journey Powers
-        pointer_vector.push(current_offset);
-        current_offset += journey_powers_writer.offset() as u32;
+    /// Save the world through a temporary file that is atomically renamed into
+    /// place, optionally keeping backups of the previous file.
+    pub fn save_as_wld_with_options(
+        &self,
+        path: &str,
+        options: &SaveOptions,
+    ) -> std::io::Result<()> {
+        let buffer = self.to_wld_bytes();
+        save::write_atomically(std::path::Path::new(path), &buffer, options)
+    }
 
-        // Section 10: Footer
-        pointer_vector.push(current_offset);
-        let _unused_offset = current_offset + footer_writer.offset() as u32;
+    /// Serialize the world into the bytes of a `.wld` file.
+    ///
+    /// If the world was loaded from a file, sections (and tile columns) that
+    /// were not modified since are copied from the original file verbatim.
+    pub fn to_wld_bytes(&self) -> Vec<u8> {
+        // Create all section buffers
+        let section_buffers: Vec<Vec<u8>> = Section::ALL
+            .iter()
+            .map(|&section| self.section_bytes(section))
+            .collect();
+
+        // Calculate the size of the file header so we know where the first section starts
+        let tile_frame_important_bytes = self.tile_frame_important.len().div_ceil(8);
+        let header_size = 4 // version_integer
+            + 7 // magic
+            + 1 // savefile_type
+            + 4 // revision
+            + 8 // is_favorite
+            + 2 // pointer count
+            + 4 * section_buffers.len() // pointer vector
+            + 2 // tile_frame_important count
+            + tile_frame_important_bytes;
+
+        // Every section starts where the previous one ended
+        let mut pointer_vector = Vec::with_capacity(section_buffers.len());
+        let mut current_offset = header_size as u32;
+        for buffer in &section_buffers {
+            pointer_vector.push(current_offset);
+            current_offset += buffer.len() as u32;
+        }
 
         // Write the complete file
         let mut final_writer = ByteWriter::new();
 
-        // Write header with updated pointers
+        // Write header with the section pointers
         final_writer.i32(self.version_integer);
         final_writer.bytes("relogic".as_bytes());
-        // print the current offset for debugging
         final_writer.u8(self.savefile_type);
         final_writer.u32(self.revision);
         final_writer.u64(self.is_favorite);
         final_writer.u16(pointer_vector.len() as u16);
-
-        // Write actual pointer values from world object for debugging section sizes
         for pointer in pointer_vector {
             final_writer.u32(pointer);
         }
 
         // Write tile_frame_important count and bits in the file header
-        final_writer.i16(original_count);
+        final_writer.i16(self.tile_frame_important.len() as i16);
         for chunk in self.tile_frame_important.chunks(8) {
             final_writer.bits(chunk);
         }
 
         // Write all section buffers
-        final_writer.bytes(&world_header_writer.into_inner());
-        final_writer.bytes(&tiles_writer.into_inner());
-        final_writer.bytes(&chests_writer.into_inner());
-        final_writer.bytes(&signs_writer.into_inner());
-        final_writer.bytes(&npcs_writer.into_inner());
-        final_writer.bytes(&tile_entities_writer.into_inner());
-        final_writer.bytes(&pressure_plates_writer.into_inner());
-        final_writer.bytes(&town_manager_writer.into_inner());
-        final_writer.bytes(&bestiary_writer.into_inner());
-        final_writer.bytes(&journey_powers_writer.into_inner());
-        final_writer.bytes(&footer_writer.into_inner());
-
-        // Write buffer to file
-        let buffer = final_writer.into_inner();
-        std::fs::write(path, buffer)?;
+        for buffer in &section_buffers {
+            final_writer.bytes(buffer);
+        }
 
-        Ok(())
+        final_writer.into_inner()
+    }
+
+    /// Sections changed since the world was loaded, as marked by the editing
+    /// methods and [`World::mark_dirty`].
+    ///
+    /// Worlds that were not loaded from a file report every section as dirty.
+    pub fn dirty_sections(&self) -> Vec<Section> {
+        let Some(cache) = &self.section_cache else {
+            return Section::ALL.to_vec();
+        };
+        Section::ALL
+            .iter()
+            .copied()
+            .filter(|&section| match section {
+                Section::Tiles => !self.dirty_columns().is_empty(),
+                _ => cache.unchanged_section(section).is_none(),
+            })
+            .collect()
+    }
+
+    /// X coordinates of the tile columns changed since the world was loaded.
+    pub fn dirty_columns(&self) -> Vec<usize> {
+        let Some(cache) = &self.section_cache else {
+            return (0..self.tiles.tiles.len()).collect();
+        };
+        (0..self.tiles.tiles.len())
+            .filter(|&x| cache.unchanged_column(x).is_none())
+            .collect()
+    }
+
+    /// Mark `section` as changed, so the next save writes it from the fields
+    /// of the world instead of copying the loaded bytes. [`Section::Tiles`]
+    /// marks every tile column.
+    ///
+    /// The editing methods mark what they change themselves, this is needed
+    /// after editing fields or tiles of a loaded world by hand. Changes that
+    /// are not marked are lost on save.
+    pub fn mark_dirty(&mut self, section: Section) {
+        if let Some(cache) = &mut self.section_cache {
+            cache.mark_section(section);
+        }
+    }
+
+    // Mark the tile columns of `rect` as changed
+    pub(crate) fn mark_tiles_dirty(&mut self, rect: Rect) {
+        if let Some(cache) = &mut self.section_cache {
+            let start = rect.x.max(0) as usize;
+            cache.mark_columns(start..rect.right().max(0) as usize);
+        }
+    }
+
+    // Bytes of a section, reusing the loaded bytes when the section is unchanged
+    fn section_bytes(&self, section: Section) -> Vec<u8> {
+        let Some(cache) = &self.section_cache else {
+            return self.write_section(section).into_inner();
+        };
+        if section == Section::Tiles {
+            let mut writer = ByteWriter::new();
+            for (x, column) in self.tiles.tiles.iter().enumerate() {
+                match cache.unchanged_column(x) {
+                    Some(raw) => writer.bytes(raw),
+                    None => writer.bytes(Self::write_tile_column(column).as_slice()),
+                }
+            }
+            return writer.into_inner();
+        }
+        match cache.unchanged_section(section) {
+            Some(raw) => raw.to_vec(),
+            None => self.write_section(section).into_inner(),
+        }
+    }
+
+    fn write_section(&self, section: Section) -> ByteWriter {
+        match section {
+            Section::Header => self.write_world_header_section(),
+            Section::Tiles => self.write_tiles_section(),
+            Section::Chests => self.write_chests_section(),
+            Section::Signs => self.write_signs_section(),
+            Section::Npcs => self.write_npcs_section(),
+            Section::TileEntities => self.write_tile_entities_section(),
+            Section::PressurePlates => self.write_pressure_plates_section(),
+            Section::TownManager => self.write_town_manager_section(),
+            Section::Bestiary => self.write_bestiary_section(),
+            Section::JourneyPowers => self.write_journey_powers_section(),
+            Section::Footer => self.write_footer_section(),
+        }
     }
 
     fn write_world_header_section(&self) -> ByteWriter {
@@ -1694,23 +1826,28 @@ impl World {
 
     fn write_tiles_section(&self) -> ByteWriter {
         let mut writer = ByteWriter::new();
-        let tiles = &self.tiles.tiles;
-
-        for column in tiles {
-            let mut y: usize = 0;
-            while y < self.world_height as usize {
-                // Find run length for RLE
-                let mut run_length = 1;
-                while y + run_length < self.world_height as usize
-                    && column[y].tiles_equal(&column[y + run_length])
-                    && run_length < 0x10000
-                {
-                    run_length += 1;
-                }
-                let tile_bytes = Self::serialize_tile(&column[y], run_length);
-                writer.bytes(&tile_bytes.into_inner());
-                y += run_length;
+
+        for column in &self.tiles.tiles {
+            writer.bytes(&Self::write_tile_column(column).into_inner());
+        }
+        writer
+    }
+
+    fn write_tile_column(column: &[Tile]) -> ByteWriter {
+        let mut writer = ByteWriter::new();
+        let mut y: usize = 0;
+        while y < column.len() {
+            // Find run length for RLE
+            let mut run_length = 1;
+            while y + run_length < column.len()
+                && column[y].tiles_equal(&column[y + run_length])
+                && run_length < 0x10000
+            {
+                run_length += 1;
             }
+            let tile_bytes = Self::serialize_tile(&column[y], run_length);
+            writer.bytes(&tile_bytes.into_inner());
+            y += run_length;
         }
         writer
     }
@@ -1914,8 +2051,7 @@ impl World {
                 tile_bytes.u8(tile.block_id as u8);
             }
             // Frame important
-            if tile.block_frame.is_some() {
-                let frame = tile.block_frame.as_ref().unwrap();
+            if let Some(frame) = &tile.block_frame {
                 tile_bytes.u16(frame.x);
                 tile_bytes.u16(frame.y);
             }
@@ -1949,15 +2085,23 @@ impl World {
     }
 
     fn write_chests_section(&self) -> ByteWriter {
+        Self::write_chests(self.chests_max_items, &self.chests)
+    }
+
+    // Every chest gets exactly `chests_max_items` slots, the reader expects
+    // that many: shorter contents are padded with empty slots, longer ones cut
+    fn write_chests(chests_max_items: i16, chests: &[Chest]) -> ByteWriter {
         let mut writer = ByteWriter::new();
 
-        writer.i16(self.chests.len() as i16);
-        writer.i16(self.chests_max_items);
-        for chest in &self.chests {
+        writer.i16(chests.len() as i16);
+        writer.i16(chests_max_items);
+        let slots = chests_max_items.max(0) as usize;
+        for chest in chests {
             writer.i32(chest.position.x);
             writer.i32(chest.position.y);
             writer.string(&chest.name);
-            for item in &chest.contents {
+            let contents = chest.contents.iter().chain(std::iter::repeat(&None));
+            for item in contents.take(slots) {
                 if let Some(item) = item {
                     writer.i16(item.quantity);
                     writer.i32(item.type_id);
@@ -1972,10 +2116,14 @@ impl World {
     }
 
     fn write_signs_section(&self) -> ByteWriter {
+        Self::write_signs(&self.signs)
+    }
+
+    fn write_signs(signs: &[Sign]) -> ByteWriter {
         let mut writer = ByteWriter::new();
 
-        writer.i16(self.signs.len() as i16);
-        for sign in &self.signs {
+        writer.i16(signs.len() as i16);
+        for sign in signs {
             writer.string(&sign.text);
             writer.i32(sign.position.x);
             writer.i32(sign.position.y);
@@ -2017,10 +2165,14 @@ impl World {
     }
 
     fn write_tile_entities_section(&self) -> ByteWriter {
+        Self::write_tile_entities(&self.tile_entities)
+    }
+
+    fn write_tile_entities(tile_entities: &[TileEntity]) -> ByteWriter {
         let mut writer = ByteWriter::new();
 
-        writer.i32(self.tile_entities.len() as i32);
-        for te in &self.tile_entities {
+        writer.i32(tile_entities.len() as i32);
+        for te in tile_entities {
             let (te_type, extra) = match &te.extra {
                 Some(crate::world::TileEntityExtra::TargetDummy { .. }) => (0u8, &te.extra),
                 Some(crate::world::TileEntityExtra::ItemFrame { .. }) => (1u8, &te.extra),
@@ -2182,14 +2334,29 @@ impl World {
         writer
     }
 
-    fn read_tile_block(r: &mut ByteReader, tile_frame_important: &[bool]) -> (Tile, usize) {
-        let flags1 = r.bits();
+    fn read_tile_block(
+        r: &mut ByteReader,
+        tile_frame_important: &[bool],
+    ) -> std::io::Result<(Tile, usize)> {
+        let flags1 = r.try_bits()?;
         let has_flags2 = flags1[0];
-        let flags2 = if has_flags2 { r.bits() } else { vec![false; 8] };
+        let flags2 = if has_flags2 {
+            r.try_bits()?
+        } else {
+            vec![false; 8]
+        };
         let has_flags3 = flags2[0];
-        let flags3 = if has_flags3 { r.bits() } else { vec![false; 8] };
+        let flags3 = if has_flags3 {
+            r.try_bits()?
+        } else {
+            vec![false; 8]
+        };
         let has_flags4 = flags3[0];
-        let flags4 = if has_flags4 { r.bits() } else { vec![false; 8] };
+        let flags4 = if has_flags4 {
+            r.try_bits()?
+        } else {
+            vec![false; 8]
+        };
 
         let has_block = flags1[1];
         let has_extended_block_id = flags1[5];
@@ -2224,9 +2391,9 @@ impl World {
         // Parse block
         if has_block {
             let block_id = if has_extended_block_id {
-                r.u16()
+                r.try_u16()?
             } else {
-                r.u8() as u16
+                r.try_u8()? as u16
             };
 
             let frame = if tile_frame_important
@@ -2234,12 +2401,16 @@ impl World {
                 .copied()
                 .unwrap_or(false)
             {
-                Some(FrameImportantData::new(r.u16(), r.u16()))
+                Some(FrameImportantData::new(r.try_u16()?, r.try_u16()?))
             } else {
                 None
             };
 
-            let block_paint = if is_block_painted { Some(r.u8()) } else { None };
+            let block_paint = if is_block_painted {
+                Some(r.try_u8()?)
+            } else {
+                None
+            };
 
             tile.block_id = block_id;
             tile.block_frame = frame;
@@ -2247,9 +2418,9 @@ impl World {
         }
 
         // Parse wall
-        let wall_id_l = if has_wall { r.u8() } else { 0 };
+        let wall_id_l = if has_wall { r.try_u8()? } else { 0 };
         let wall_paint = if has_wall && is_wall_painted {
-            Some(r.u8())
+            Some(r.try_u8()?)
         } else {
             None
         };
@@ -2257,11 +2428,11 @@ impl World {
         // Parse liquid
         if liquid_type != LiquidType::NoLiquid {
             tile.liquid_type = liquid_type;
-            tile.liquid_amount = r.u8();
+            tile.liquid_amount = r.try_u8()?;
         }
 
         // Parse wall, again
-        let wall_id_g = if has_extended_wall_id { r.u8() } else { 0 };
+        let wall_id_g = if has_extended_wall_id { r.try_u8()? } else { 0 };
 
         if has_wall {
             let wall_id = (wall_id_g as u16) * 256 + (wall_id_l as u16);
@@ -2281,13 +2452,13 @@ impl World {
         // Find RLE Compression multiplier
         let rle_value = (flags1[7] as u8) * 2 + (flags1[6] as u8);
         let multiply_by = match rle_value {
-            2 => r.u16() as usize + 1,
-            1 => r.u8() as usize + 1,
+            2 => r.try_u16()? as usize + 1,
+            1 => r.try_u8()? as usize + 1,
             0 => 1,
             _ => 1, // i am not sure if it can be anything else
         };
 
-        (tile, multiply_by)
+        Ok((tile, multiply_by))
     }
 
     fn liquid_type_from_flags(flags1: &[bool], flags3: &[bool]) -> LiquidType {
@@ -2308,32 +2479,38 @@ impl World {
         }
     }
 
+    // Returns the tile matrix along with the raw bytes of every column
     fn create_tile_matrix(
         r: &mut ByteReader,
         world_size: (usize, usize),
         tile_frame_important: &[bool],
-    ) -> TileMatrix {
+    ) -> std::io::Result<(TileMatrix, Vec<Range<usize>>)> {
         let mut tm = TileMatrix::new((0, 0));
+        let mut column_ranges = Vec::with_capacity(world_size.0);
         let (width, height) = world_size;
 
         for _ in 0..width {
-            let mut column = Vec::new();
-            let mut column_bytes = Vec::new();
             let start_offset = r.offset();
-
-            while column.len() < height {
-                let (tile, multiply_by) = Self::read_tile_block(r, tile_frame_important);
-                for _ in 0..multiply_by {
-                    column.push(tile.clone());
-                }
-            }
-
-            let end_offset = r.offset();
-            let column_data = r.slice_bytes(start_offset, end_offset);
-            column_bytes.extend_from_slice(&column_data);
+            let column = Self::read_tile_column(r, height, tile_frame_important)?;
+            column_ranges.push(start_offset..r.offset());
 
             tm.add_column(column);
         }
-        tm
+        Ok((tm, column_ranges))
+    }
+
+    fn read_tile_column(
+        r: &mut ByteReader,
+        height: usize,
+        tile_frame_important: &[bool],
+    ) -> std::io::Result<Vec<Tile>> {
+        let mut column = Vec::with_capacity(height);
+        while column.len() < height {
+            let (tile, multiply_by) = Self::read_tile_block(r, tile_frame_important)?;
+            for _ in 0..multiply_by {
+                column.push(tile.clone());
+            }
+        }
+        Ok(column)
     }
 }
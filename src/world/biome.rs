@@ -0,0 +1,345 @@
+use serde::{Deserialize, Serialize};
+
+use super::block_ids::*;
+use super::layer::Layer;
+use super::World;
+
+// Size of the area the game counts blocks in around the player
+const SCAN_WIDTH: usize = 169;
+const SCAN_HEIGHT: usize = 124;
+
+// Columns from either edge of the world that count as ocean
+const OCEAN_WIDTH: usize = 380;
+
+/// What a part of the world is, see [`World::biome_map`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Biome {
+    Forest,
+    Snow,
+    Desert,
+    Jungle,
+    Ocean,
+    Corruption,
+    Crimson,
+    Hallow,
+    Mushroom,
+    Dungeon,
+    Underworld,
+    Space,
+    Cavern,
+    Underground,
+}
+
+impl std::fmt::Display for Biome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Biome::Forest => "Forest",
+            Biome::Snow => "Snow",
+            Biome::Desert => "Desert",
+            Biome::Jungle => "Jungle",
+            Biome::Ocean => "Ocean",
+            Biome::Corruption => "Corruption",
+            Biome::Crimson => "Crimson",
+            Biome::Hallow => "Hallow",
+            Biome::Mushroom => "Mushroom",
+            Biome::Dungeon => "Dungeon",
+            Biome::Underworld => "Underworld",
+            Biome::Space => "Space",
+            Biome::Cavern => "Cavern",
+            Biome::Underground => "Underground",
+        };
+        write!(f, "{name}")
+    }
+}
+
+// Blocks the game counts towards a biome (`SceneMetrics`) and how many it
+// takes around the player to be in it. A block can count towards several.
+// Listed in the order of `COUNTERS`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Counter {
+    Evil,
+    Blood,
+    Holy,
+    Jungle,
+    Sand,
+    Snow,
+    Mushroom,
+    Dungeon,
+    Sunflower,
+}
+
+const COUNTERS: [Counter; 9] = [
+    Counter::Evil,
+    Counter::Blood,
+    Counter::Holy,
+    Counter::Jungle,
+    Counter::Sand,
+    Counter::Snow,
+    Counter::Mushroom,
+    Counter::Dungeon,
+    Counter::Sunflower,
+];
+
+// How much every sunflower tile takes off the corruption and crimson counts
+const SUNFLOWER_WEIGHT: u32 = 5;
+
+impl Counter {
+    fn blocks(self) -> &'static [u16] {
+        match self {
+            Counter::Evil => &[
+                CORRUPT_GRASS,
+                CORRUPT_JUNGLE_GRASS,
+                CORRUPT_PLANTS,
+                CORRUPT_VINES,
+                EBONSTONE,
+                CORRUPT_THORNS,
+                EBONSAND,
+                CORRUPT_ICE,
+                CORRUPT_SANDSTONE,
+                CORRUPT_HARDENED_SAND,
+            ],
+            Counter::Blood => &[
+                FLESH_GRASS,
+                CRIMSON_JUNGLE_GRASS,
+                FLESH_WEEDS,
+                CRIMSON_VINES,
+                CRIMSTONE,
+                FLESH_ICE,
+                CRIMSON_SANDSTONE,
+                CRIMSON_HARDENED_SAND,
+                CRIMSAND,
+                CRIMTANE_THORNS,
+            ],
+            Counter::Holy => &[
+                HALLOWED_GRASS,
+                GOLF_GRASS_HALLOWED,
+                HALLOWED_PLANTS,
+                HALLOWED_PLANTS2,
+                PEARLSTONE,
+                PEARLSAND,
+                HALLOWED_ICE,
+                HALLOW_SANDSTONE,
+                HALLOW_HARDENED_SAND,
+            ],
+            Counter::Jungle => &[
+                JUNGLE_GRASS,
+                JUNGLE_PLANTS,
+                JUNGLE_VINES,
+                JUNGLE_PLANTS2,
+                HIVE,
+                LIHZAHRD_BRICK,
+            ],
+            Counter::Sand => &[
+                SAND,
+                EBONSAND,
+                PEARLSAND,
+                CRIMSAND,
+                SANDSTONE,
+                HARDENED_SAND,
+                CORRUPT_HARDENED_SAND,
+                CRIMSON_HARDENED_SAND,
+                HALLOW_HARDENED_SAND,
+                CORRUPT_SANDSTONE,
+                CRIMSON_SANDSTONE,
+                HALLOW_SANDSTONE,
+            ],
+            Counter::Snow => &[
+                SNOW_BLOCK,
+                SNOW_BRICK,
+                ICE_BLOCK,
+                BREAKABLE_ICE,
+                CORRUPT_ICE,
+                HALLOWED_ICE,
+                FLESH_ICE,
+            ],
+            Counter::Mushroom => &[
+                MUSHROOM_GRASS,
+                MUSHROOM_PLANTS,
+                MUSHROOM_TREES,
+                MUSHROOMVINES,
+            ],
+            Counter::Dungeon => &[
+                BLUE_DUNGEON_BRICK,
+                GREEN_DUNGEON_BRICK,
+                PINK_DUNGEON_BRICK,
+                CRACKED_BLUE_DUNGEON_BRICK,
+                CRACKED_GREEN_DUNGEON_BRICK,
+                CRACKED_PINK_DUNGEON_BRICK,
+            ],
+            Counter::Sunflower => &[SUNFLOWER],
+        }
+    }
+
+    fn threshold(self) -> u32 {
+        match self {
+            Counter::Evil | Counter::Blood => 300,
+            Counter::Holy => 125,
+            Counter::Jungle => 140,
+            Counter::Sand | Counter::Snow => 1500,
+            Counter::Mushroom => 100,
+            Counter::Dungeon => 250,
+            // only ever taken off other counts
+            Counter::Sunflower => u32::MAX,
+        }
+    }
+
+    fn bit(self) -> u16 {
+        1 << self as u8
+    }
+}
+
+/// The biome of every `cell_size` by `cell_size` square of a world, stored
+/// column by column like the tiles.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BiomeMap {
+    pub cell_size: usize,
+    pub cells: Vec<Vec<Biome>>,
+}
+
+impl BiomeMap {
+    /// Number of cells across and down.
+    pub fn size(&self) -> (usize, usize) {
+        (self.cells.len(), self.cells.first().map_or(0, Vec::len))
+    }
+
+    /// The biome of the cell holding tile (`x`, `y`).
+    pub fn biome_at(&self, x: usize, y: usize) -> Option<Biome> {
+        self.cells
+            .get(x / self.cell_size)?
+            .get(y / self.cell_size)
+            .copied()
+    }
+
+    /// How many cells have `biome`.
+    pub fn count(&self, biome: Biome) -> usize {
+        self.cells
+            .iter()
+            .flatten()
+            .filter(|&&cell| cell == biome)
+            .count()
+    }
+}
+
+impl World {
+    /// Label every `cell_size` square of the world with the biome a player
+    /// standing in its middle would be in.
+    ///
+    /// Blocks are counted in the same 169x124 area around that point the game
+    /// uses, against the game's thresholds: 300 corruption or crimson, 125
+    /// hallow, 140 jungle, 1500 sand or snow, 100 mushroom and 250 dungeon
+    /// blocks. Every sunflower tile takes 5 off the corruption and crimson
+    /// counts, and hallow cancels them out, like in the game.
+    ///
+    /// Space and the underworld come first. When several biomes apply the
+    /// first of dungeon, corruption, crimson, hallow, mushroom, jungle, snow,
+    /// desert and ocean wins, otherwise the layer decides between forest,
+    /// underground and cavern.
+    pub fn biome_map(&self, cell_size: usize) -> BiomeMap {
+        let cell_size = cell_size.max(1);
+        let (width, height) = self.tiles.size;
+        let (columns, rows) = (width.div_ceil(cell_size), height.div_ceil(cell_size));
+
+        // Which counters each block type counts towards
+        let mut masks = vec![0u16; u16::MAX as usize + 1];
+        for counter in COUNTERS {
+            for &block in counter.blocks() {
+                masks[block as usize] |= counter.bit();
+            }
+        }
+
+        // Scan area of every cell, clipped to the world
+        let center = |cell: usize, size: usize| (cell * cell_size + cell_size / 2).min(size - 1);
+        let span = |middle: usize, scan: usize, size: usize| {
+            let start = middle.saturating_sub(scan / 2);
+            (start, (start + scan).min(size))
+        };
+
+        let mut counts = vec![[0u32; COUNTERS.len()]; columns * rows];
+        // (width + 1) x (height + 1) running totals, one counter at a time
+        let mut sums = vec![0u32; (width + 1) * (height + 1)];
+        let at = |x: usize, y: usize| x * (height + 1) + y;
+        for (i, counter) in COUNTERS.iter().enumerate() {
+            let bit = counter.bit();
+            for x in 0..width {
+                let column = &self.tiles.tiles[x];
+                let mut running = 0;
+                for y in 0..height {
+                    let tile = &column[y];
+                    if tile.has_block() && masks[tile.block_id as usize] & bit != 0 {
+                        running += 1;
+                    }
+                    sums[at(x + 1, y + 1)] = sums[at(x, y + 1)] + running;
+                }
+            }
+            for cx in 0..columns {
+                let (x0, x1) = span(center(cx, width), SCAN_WIDTH, width);
+                for cy in 0..rows {
+                    let (y0, y1) = span(center(cy, height), SCAN_HEIGHT, height);
+                    counts[cx * rows + cy][i] =
+                        sums[at(x1, y1)] + sums[at(x0, y0)] - sums[at(x0, y1)] - sums[at(x1, y0)];
+                }
+            }
+        }
+
+        let cells = (0..columns)
+            .map(|cx| {
+                (0..rows)
+                    .map(|cy| {
+                        let (x, y) = (center(cx, width), center(cy, height));
+                        self.classify(x, y, counts[cx * rows + cy])
+                    })
+                    .collect()
+            })
+            .collect();
+        BiomeMap { cell_size, cells }
+    }
+
+    fn classify(&self, x: usize, y: usize, mut counts: [u32; COUNTERS.len()]) -> Biome {
+        let layer = self.layer_at(y as i32);
+        match layer {
+            Layer::Space => return Biome::Space,
+            Layer::Underworld => return Biome::Underworld,
+            _ => {}
+        }
+
+        let (evil, blood, holy) = (
+            Counter::Evil as usize,
+            Counter::Blood as usize,
+            Counter::Holy as usize,
+        );
+        let sunflowers = counts[Counter::Sunflower as usize] * SUNFLOWER_WEIGHT;
+        counts[evil] = counts[evil].saturating_sub(sunflowers);
+        counts[blood] = counts[blood].saturating_sub(sunflowers);
+        let holy_count = counts[holy];
+        counts[holy] = counts[holy].saturating_sub(counts[evil] + counts[blood]);
+        counts[evil] = counts[evil].saturating_sub(holy_count);
+        counts[blood] = counts[blood].saturating_sub(holy_count);
+
+        let enough = |counter: Counter| counts[counter as usize] >= counter.threshold();
+        let underground = layer > Layer::Surface;
+        if underground && enough(Counter::Dungeon) {
+            return Biome::Dungeon;
+        }
+        let biomes = [
+            (Counter::Evil, Biome::Corruption),
+            (Counter::Blood, Biome::Crimson),
+            (Counter::Holy, Biome::Hallow),
+            (Counter::Mushroom, Biome::Mushroom),
+            (Counter::Jungle, Biome::Jungle),
+            (Counter::Snow, Biome::Snow),
+            (Counter::Sand, Biome::Desert),
+        ];
+        if let Some(&(_, biome)) = biomes.iter().find(|(counter, _)| enough(*counter)) {
+            return biome;
+        }
+        let width = self.tiles.size.0;
+        if !underground && (x < OCEAN_WIDTH || x >= width.saturating_sub(OCEAN_WIDTH)) {
+            return Biome::Ocean;
+        }
+        match layer {
+            Layer::Cavern => Biome::Cavern,
+            Layer::Underground => Biome::Underground,
+            _ => Biome::Forest,
+        }
+    }
+}
@@ -0,0 +1,106 @@
+// Block ids the crate needs to reason about, named like in data/blocks.csv
+
+pub const STONE: u16 = 1;
+pub const GRASS: u16 = 2;
+pub const IRON: u16 = 6;
+pub const COPPER: u16 = 7;
+pub const GOLD: u16 = 8;
+pub const SILVER: u16 = 9;
+pub const CONTAINERS: u16 = 21;
+pub const DEMONITE: u16 = 22;
+pub const CORRUPT_GRASS: u16 = 23;
+pub const CORRUPT_PLANTS: u16 = 24;
+pub const EBONSTONE: u16 = 25;
+pub const SUNFLOWER: u16 = 27;
+pub const CORRUPT_THORNS: u16 = 32;
+pub const METEORITE: u16 = 37;
+pub const BLUE_DUNGEON_BRICK: u16 = 41;
+pub const GREEN_DUNGEON_BRICK: u16 = 43;
+pub const PINK_DUNGEON_BRICK: u16 = 44;
+pub const SAND: u16 = 53;
+pub const SIGNS: u16 = 55;
+pub const HELLSTONE: u16 = 58;
+pub const JUNGLE_GRASS: u16 = 60;
+pub const JUNGLE_PLANTS: u16 = 61;
+pub const JUNGLE_VINES: u16 = 62;
+pub const SAPPHIRE: u16 = 63;
+pub const RUBY: u16 = 64;
+pub const EMERALD: u16 = 65;
+pub const TOPAZ: u16 = 66;
+pub const AMETHYST: u16 = 67;
+pub const DIAMOND: u16 = 68;
+pub const MUSHROOM_GRASS: u16 = 70;
+pub const MUSHROOM_PLANTS: u16 = 71;
+pub const MUSHROOM_TREES: u16 = 72;
+pub const JUNGLE_PLANTS2: u16 = 74;
+pub const TOMBSTONES: u16 = 85;
+pub const DRESSERS: u16 = 88;
+pub const COBALT: u16 = 107;
+pub const MYTHRIL: u16 = 108;
+pub const HALLOWED_GRASS: u16 = 109;
+pub const HALLOWED_PLANTS: u16 = 110;
+pub const ADAMANTITE: u16 = 111;
+pub const EBONSAND: u16 = 112;
+pub const HALLOWED_PLANTS2: u16 = 113;
+pub const PEARLSAND: u16 = 116;
+pub const PEARLSTONE: u16 = 117;
+pub const SNOW_BLOCK: u16 = 147;
+pub const SNOW_BRICK: u16 = 148;
+pub const ICE_BLOCK: u16 = 161;
+pub const BREAKABLE_ICE: u16 = 162;
+pub const CORRUPT_ICE: u16 = 163;
+pub const HALLOWED_ICE: u16 = 164;
+pub const TIN: u16 = 166;
+pub const LEAD: u16 = 167;
+pub const TUNGSTEN: u16 = 168;
+pub const PLATINUM: u16 = 169;
+pub const EXPOSED_GEMS: u16 = 178;
+pub const FLESH_GRASS: u16 = 199;
+pub const FLESH_ICE: u16 = 200;
+pub const FLESH_WEEDS: u16 = 201;
+pub const CRIMSTONE: u16 = 203;
+pub const CRIMTANE: u16 = 204;
+pub const CRIMSON_VINES: u16 = 205;
+pub const CHLOROPHYTE: u16 = 211;
+pub const PALLADIUM: u16 = 221;
+pub const ORICHALCUM: u16 = 222;
+pub const TITANIUM: u16 = 223;
+pub const HIVE: u16 = 225;
+pub const LIHZAHRD_BRICK: u16 = 226;
+pub const CRIMSAND: u16 = 234;
+pub const CRIMTANE_THORNS: u16 = 352;
+pub const TARGET_DUMMY: u16 = 378;
+pub const ITEM_FRAME: u16 = 395;
+pub const SANDSTONE: u16 = 396;
+pub const HARDENED_SAND: u16 = 397;
+pub const CORRUPT_HARDENED_SAND: u16 = 398;
+pub const CRIMSON_HARDENED_SAND: u16 = 399;
+pub const CORRUPT_SANDSTONE: u16 = 400;
+pub const CRIMSON_SANDSTONE: u16 = 401;
+pub const HALLOW_HARDENED_SAND: u16 = 402;
+pub const HALLOW_SANDSTONE: u16 = 403;
+pub const LUNAR_ORE: u16 = 408;
+pub const LOGIC_SENSOR: u16 = 423;
+pub const ANNOUNCEMENT_BOX: u16 = 425;
+pub const CONTAINERS2: u16 = 467;
+pub const DISPLAY_DOLL: u16 = 470;
+pub const WEAPON_RACK_2: u16 = 471;
+pub const HAT_RACK: u16 = 475;
+pub const MOWED_GOLF_GRASS: u16 = 477;
+pub const CRACKED_BLUE_DUNGEON_BRICK: u16 = 481;
+pub const CRACKED_GREEN_DUNGEON_BRICK: u16 = 482;
+pub const CRACKED_PINK_DUNGEON_BRICK: u16 = 483;
+pub const GOLF_GRASS_HALLOWED: u16 = 492;
+pub const PLATE: u16 = 520;
+pub const MUSHROOMVINES: u16 = 528;
+pub const TATTERED_WOOD_SIGN: u16 = 573;
+pub const TELEPORTATION_PYLON: u16 = 597;
+pub const CORRUPT_VINES: u16 = 636;
+pub const CORRUPT_JUNGLE_GRASS: u16 = 661;
+pub const CRIMSON_JUNGLE_GRASS: u16 = 662;
+
+/// Blocks a chest can be stored in.
+pub const CHEST_BLOCKS: [u16; 3] = [CONTAINERS, CONTAINERS2, DRESSERS];
+
+/// Blocks that hold sign text (`Main.tileSign` in the game).
+pub const SIGN_BLOCKS: [u16; 4] = [SIGNS, TOMBSTONES, ANNOUNCEMENT_BOX, TATTERED_WOOD_SIGN];
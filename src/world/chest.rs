@@ -1,5 +1,9 @@
 use super::coordinates::Coordinates;
+use super::entity_index::{Entity, Slot};
+use super::error::PlacementError;
 use super::item::ItemStack;
+use super::validation::{is_chest_origin, MAX_CHESTS};
+use super::World;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -8,3 +12,104 @@ pub struct Chest {
     pub name: String,
     pub contents: Vec<Option<ItemStack>>,
 }
+
+impl Chest {
+    /// An empty chest with `slots` item slots.
+    pub fn new(position: Coordinates, name: &str, slots: usize) -> Self {
+        Self {
+            position,
+            name: name.to_string(),
+            contents: vec![None; slots],
+        }
+    }
+
+    /// Put `item` in the first empty slot and return the slot, `None` when the chest is full.
+    pub fn insert_item(&mut self, item: ItemStack) -> Option<usize> {
+        let slot = self.contents.iter().position(Option::is_none)?;
+        self.contents[slot] = Some(item);
+        Some(slot)
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.contents.iter().all(Option::is_some)
+    }
+}
+
+impl World {
+    /// Place a chest with its top-left tile at (`x`, `y`) and add an empty
+    /// chest of `chests_max_items` slots for it. `style` indexes the styles of
+    /// `"CONTAINERS"`, see [`World::place_object`] for the placement rules.
+    pub fn add_chest(
+        &mut self,
+        x: i32,
+        y: i32,
+        style: usize,
+        name: &str,
+    ) -> Result<&mut Chest, PlacementError> {
+        if self.chests.len() >= MAX_CHESTS {
+            return Err(PlacementError::LimitReached {
+                object: "chests".to_string(),
+                max: MAX_CHESTS,
+            });
+        }
+        self.place_object(x, y, "CONTAINERS", style)?;
+
+        let position = Coordinates { x, y };
+        // anything left at the spot belonged to an object that is gone
+        self.remove_entities(|other| *other == position);
+        let slots = self.chests_max_items.max(0) as usize;
+        Ok(self.push_chest(Chest::new(position, name, slots)))
+    }
+
+    /// The chest stored in the chest or dresser that (`x`, `y`) is part of.
+    pub fn chest_at(&self, x: i32, y: i32) -> Option<&Chest> {
+        self.chest_position(x, y)?;
+        match self.entity_at(x, y)? {
+            Entity::Chest(chest) => Some(chest),
+            _ => None,
+        }
+    }
+
+    pub fn chest_at_mut(&mut self, x: i32, y: i32) -> Option<&mut Chest> {
+        self.chest_position(x, y)?;
+        let Slot::Chest(i) = self.slot_at(x, y)? else {
+            return None;
+        };
+        self.touch_lists();
+        self.chests.get_mut(i)
+    }
+
+    /// Remove the chest that (`x`, `y`) is part of along with its tiles.
+    pub fn remove_chest(&mut self, x: i32, y: i32) -> Option<Chest> {
+        self.chest_at(x, y)?;
+        let (_, removed) = self.take_object(x, y)?;
+        removed.chests.into_iter().next()
+    }
+
+    /// Put `item` in the first empty slot of the chest at (`x`, `y`) and
+    /// return the slot. Only the first `chests_max_items` slots are used,
+    /// shorter chests are padded to that size first. `None` when there is no
+    /// chest there or it is full.
+    pub fn insert_chest_item(&mut self, x: i32, y: i32, item: ItemStack) -> Option<usize> {
+        let slots = self.chests_max_items.max(0) as usize;
+        let chest = self.chest_at_mut(x, y)?;
+        if chest.contents.len() < slots {
+            chest.contents.resize(slots, None);
+        }
+        let slot = chest.contents[..slots].iter().position(Option::is_none)?;
+        chest.contents[slot] = Some(item);
+        Some(slot)
+    }
+
+    // Top-left tile of the chest or dresser at (x, y)
+    fn chest_position(&self, x: i32, y: i32) -> Option<Coordinates> {
+        let rect = self.object_at(x, y)?;
+        let position = Coordinates {
+            x: rect.x,
+            y: rect.y,
+        };
+        self.tile_at(&position)
+            .is_some_and(is_chest_origin)
+            .then_some(position)
+    }
+}
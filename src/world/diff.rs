@@ -0,0 +1,605 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+use super::chest::Chest;
+use super::coordinates::Coordinates;
+use super::item::ItemStack;
+use super::npc::NPC;
+use super::region::Rect;
+use super::sign::Sign;
+use super::tile_entity::TileEntity;
+use super::World;
+
+/// Everything that differs between two worlds, see [`World::diff`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorldDiff {
+    /// Tile matrix sizes of both worlds when they differ. Only the tiles
+    /// both worlds have are compared then.
+    pub size: Option<((usize, usize), (usize, usize))>,
+    /// Number of tiles that differ.
+    pub changed_tiles: usize,
+    /// Rectangles covering exactly the tiles that differ.
+    pub tiles: Vec<Rect>,
+    pub chests: Vec<ChestChange>,
+    pub signs: Vec<SignChange>,
+    pub npcs: Vec<NpcChange>,
+    pub tile_entities: Vec<TileEntityChange>,
+    pub header: Vec<HeaderChange>,
+}
+
+/// A changed item slot of a chest.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ItemChange {
+    pub slot: usize,
+    pub before: Option<ItemStack>,
+    pub after: Option<ItemStack>,
+}
+
+/// Chests are matched by position.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ChestChange {
+    Added(Chest),
+    Removed(Chest),
+    Modified {
+        position: Coordinates,
+        /// Name before and after, when it changed.
+        name: Option<(String, String)>,
+        items: Vec<ItemChange>,
+    },
+}
+
+/// Signs are matched by position.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SignChange {
+    Added(Sign),
+    Removed(Sign),
+    TextChanged {
+        position: Coordinates,
+        before: String,
+        after: String,
+    },
+}
+
+/// NPCs are matched by type and name, in the order they are stored.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum NpcChange {
+    Added(NPC),
+    Removed(NPC),
+    Moved {
+        type_: i32,
+        name: String,
+        from: (f32, f32),
+        to: (f32, f32),
+    },
+    MovedHome {
+        type_: i32,
+        name: String,
+        from: (Coordinates, bool),
+        to: (Coordinates, bool),
+    },
+}
+
+/// Tile entities are matched by position.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TileEntityChange {
+    Added(TileEntity),
+    Removed(TileEntity),
+    Modified {
+        before: TileEntity,
+        after: TileEntity,
+    },
+}
+
+/// A header value that differs, `field` being its name as it appears in
+/// the JSON export (`"game_progression.defeated_plantera"`, `"mob_kills[4]"`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HeaderChange {
+    pub field: String,
+    pub before: Value,
+    pub after: Value,
+}
+
+// Header fields outside of the grouped structs that are worth reporting.
+// Layout data like sizes, bounds and frame flags is left out.
+macro_rules! header_fields {
+    ($world:expr, $($field:ident),* $(,)?) => {
+        vec![$((
+            stringify!($field).to_string(),
+            serde_json::to_value(&$world.$field).unwrap_or_default(),
+        )),*]
+    };
+}
+
+impl World {
+    /// Compare this world with `other`, reporting what changed from `self` to `other`.
+    ///
+    /// Changed tiles are merged into rectangles, column runs of the same rows
+    /// in neighbouring columns becoming one rectangle. Chests, signs and tile
+    /// entities are matched by position, NPCs by type and name.
+    pub fn diff(&self, other: &World) -> WorldDiff {
+        let (changed_tiles, tiles) = self.diff_tiles(other);
+        WorldDiff {
+            size: (self.tiles.size != other.tiles.size)
+                .then_some((self.tiles.size, other.tiles.size)),
+            changed_tiles,
+            tiles,
+            chests: diff_chests(&self.chests, &other.chests),
+            signs: diff_signs(&self.signs, &other.signs),
+            npcs: diff_npcs(&self.npcs, &other.npcs),
+            tile_entities: diff_tile_entities(&self.tile_entities, &other.tile_entities),
+            header: diff_header(&self.header_fields(), &other.header_fields()),
+        }
+    }
+
+    fn diff_tiles(&self, other: &World) -> (usize, Vec<Rect>) {
+        let width = self.tiles.size.0.min(other.tiles.size.0);
+        let height = self.tiles.size.1.min(other.tiles.size.1);
+        let mut changed = 0;
+        let mut rects: Vec<Rect> = Vec::new();
+        // rectangles reaching the previous column, by their first and last row
+        let mut open: HashMap<(usize, usize), usize> = HashMap::new();
+        for x in 0..width {
+            let (ours, theirs) = (&self.tiles.tiles[x], &other.tiles.tiles[x]);
+            let mut still_open = HashMap::new();
+            let mut y = 0;
+            while y < height {
+                if ours[y] == theirs[y] {
+                    y += 1;
+                    continue;
+                }
+                let start = y;
+                while y < height && ours[y] != theirs[y] {
+                    y += 1;
+                }
+                changed += y - start;
+                let run = (start, y);
+                match open.get(&run) {
+                    Some(&index) => {
+                        rects[index].width += 1;
+                        still_open.insert(run, index);
+                    }
+                    None => {
+                        still_open.insert(run, rects.len());
+                        rects.push(Rect::new(x as i32, start as i32, 1, (y - start) as i32));
+                    }
+                }
+            }
+            open = still_open;
+        }
+        (changed, rects)
+    }
+
+    // Header values by name, grouped structs flattened to "group.field"
+    pub(crate) fn header_fields(&self) -> Vec<(String, Value)> {
+        let mut fields = header_fields!(
+            self,
+            world_name,
+            difficulty_value,
+            is_drunk_world,
+            is_for_the_worthy,
+            is_tenth_anniversary,
+            is_the_constant,
+            is_bee_world,
+            is_upside_down,
+            is_trap_world,
+            is_zenith_world,
+            spawn_point_x,
+            spawn_point_y,
+            underground_level,
+            cavern_level,
+            dungeon_point_x,
+            dungeon_point_y,
+            world_evil_type,
+            hardmode_ore_1,
+            hardmode_ore_2,
+            hardmode_ore_3,
+            ore_1,
+            ore_2,
+            ore_3,
+            ore_4,
+            has_cat,
+            has_dog,
+            has_bunny,
+            combat_book_used,
+            combat_book_2_used,
+            peddler_satchel_used,
+            angler_today_quest_completed_by,
+            angler_daily_quest_target,
+            sundial_cooldown,
+            sundial_is_running,
+            moondial_is_running,
+            moondial_cooldown,
+        );
+        let groups = [
+            (
+                "game_progression",
+                serde_json::to_value(&self.game_progression),
+            ),
+            ("saved_npcs", serde_json::to_value(&self.saved_npcs)),
+            ("environment", serde_json::to_value(&self.environment)),
+            ("weather_events", serde_json::to_value(&self.weather_events)),
+            ("invasions", serde_json::to_value(&self.invasions)),
+        ];
+        for (group, value) in groups {
+            if let Ok(Value::Object(map)) = value {
+                fields.extend(
+                    map.into_iter()
+                        .map(|(key, value)| (format!("{group}.{key}"), value)),
+                );
+            }
+        }
+        fields.extend(
+            self.mob_kills
+                .iter()
+                .enumerate()
+                .map(|(i, kills)| (format!("mob_kills[{i}]"), Value::from(*kills))),
+        );
+        fields
+    }
+}
+
+impl WorldDiff {
+    pub fn is_empty(&self) -> bool {
+        self.size.is_none()
+            && self.tiles.is_empty()
+            && self.chests.is_empty()
+            && self.signs.is_empty()
+            && self.npcs.is_empty()
+            && self.tile_entities.is_empty()
+            && self.header.is_empty()
+    }
+
+    pub fn save_as_json(&self, path: &str) -> std::io::Result<()> {
+        let file = std::fs::File::create(path)?;
+        let writer = std::io::BufWriter::new(file);
+        serde_json::to_writer_pretty(writer, self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+}
+
+fn diff_chests(before: &[Chest], after: &[Chest]) -> Vec<ChestChange> {
+    let mut changes = Vec::new();
+    let (ours, theirs) = (
+        by_position(before, |c| &c.position),
+        by_position(after, |c| &c.position),
+    );
+    for chest in before {
+        match theirs.get(&chest.position) {
+            None => changes.push(ChestChange::Removed(chest.clone())),
+            Some(&new) if new != chest => {
+                let slots = chest.contents.len().max(new.contents.len());
+                let items = (0..slots)
+                    .filter_map(|slot| {
+                        let old_item = chest.contents.get(slot).cloned().flatten();
+                        let new_item = new.contents.get(slot).cloned().flatten();
+                        (old_item != new_item).then_some(ItemChange {
+                            slot,
+                            before: old_item,
+                            after: new_item,
+                        })
+                    })
+                    .collect();
+                changes.push(ChestChange::Modified {
+                    position: chest.position.clone(),
+                    name: (chest.name != new.name).then(|| (chest.name.clone(), new.name.clone())),
+                    items,
+                });
+            }
+            Some(_) => {}
+        }
+    }
+    for chest in after {
+        if !ours.contains_key(&chest.position) {
+            changes.push(ChestChange::Added(chest.clone()));
+        }
+    }
+    changes
+}
+
+fn diff_signs(before: &[Sign], after: &[Sign]) -> Vec<SignChange> {
+    let mut changes = Vec::new();
+    let (ours, theirs) = (
+        by_position(before, |s| &s.position),
+        by_position(after, |s| &s.position),
+    );
+    for sign in before {
+        match theirs.get(&sign.position) {
+            None => changes.push(SignChange::Removed(sign.clone())),
+            Some(new) if new.text != sign.text => changes.push(SignChange::TextChanged {
+                position: sign.position.clone(),
+                before: sign.text.clone(),
+                after: new.text.clone(),
+            }),
+            Some(_) => {}
+        }
+    }
+    for sign in after {
+        if !ours.contains_key(&sign.position) {
+            changes.push(SignChange::Added(sign.clone()));
+        }
+    }
+    changes
+}
+
+fn diff_npcs(before: &[NPC], after: &[NPC]) -> Vec<NpcChange> {
+    let mut changes = Vec::new();
+    let mut matched = vec![false; after.len()];
+    for npc in before {
+        let found = after
+            .iter()
+            .enumerate()
+            .position(|(i, new)| !matched[i] && new.type_ == npc.type_ && new.name == npc.name);
+        let Some(i) = found else {
+            changes.push(NpcChange::Removed(npc.clone()));
+            continue;
+        };
+        matched[i] = true;
+        let new = &after[i];
+        let (from, to) = (
+            (npc.position_x, npc.position_y),
+            (new.position_x, new.position_y),
+        );
+        if from != to {
+            changes.push(NpcChange::Moved {
+                type_: npc.type_,
+                name: npc.name.clone(),
+                from,
+                to,
+            });
+        }
+        if npc.home != new.home || npc.is_homeless != new.is_homeless {
+            changes.push(NpcChange::MovedHome {
+                type_: npc.type_,
+                name: npc.name.clone(),
+                from: (npc.home.clone(), npc.is_homeless),
+                to: (new.home.clone(), new.is_homeless),
+            });
+        }
+    }
+    for (npc, matched) in after.iter().zip(matched) {
+        if !matched {
+            changes.push(NpcChange::Added(npc.clone()));
+        }
+    }
+    changes
+}
+
+fn diff_tile_entities(before: &[TileEntity], after: &[TileEntity]) -> Vec<TileEntityChange> {
+    let mut changes = Vec::new();
+    let (ours, theirs) = (
+        by_position(before, |e| &e.position),
+        by_position(after, |e| &e.position),
+    );
+    for entity in before {
+        match theirs.get(&entity.position) {
+            None => changes.push(TileEntityChange::Removed(entity.clone())),
+            Some(&new) if new != entity => changes.push(TileEntityChange::Modified {
+                before: entity.clone(),
+                after: new.clone(),
+            }),
+            Some(_) => {}
+        }
+    }
+    for entity in after {
+        if !ours.contains_key(&entity.position) {
+            changes.push(TileEntityChange::Added(entity.clone()));
+        }
+    }
+    changes
+}
+
+fn diff_header(before: &[(String, Value)], after: &[(String, Value)]) -> Vec<HeaderChange> {
+    let theirs: HashMap<&str, &Value> = after.iter().map(|(k, v)| (k.as_str(), v)).collect();
+    let ours: HashMap<&str, &Value> = before.iter().map(|(k, v)| (k.as_str(), v)).collect();
+    let mut changes: Vec<HeaderChange> = before
+        .iter()
+        .filter_map(|(field, value)| {
+            let new = theirs.get(field.as_str()).copied().unwrap_or(&Value::Null);
+            (new != value).then(|| HeaderChange {
+                field: field.clone(),
+                before: value.clone(),
+                after: new.clone(),
+            })
+        })
+        .collect();
+    // counters the other world has more of
+    changes.extend(
+        after
+            .iter()
+            .filter(|(field, _)| !ours.contains_key(field.as_str()))
+            .map(|(field, value)| HeaderChange {
+                field: field.clone(),
+                before: Value::Null,
+                after: value.clone(),
+            }),
+    );
+    changes
+}
+
+// The first entry at each position wins, like in the game
+fn by_position<T>(list: &[T], position: fn(&T) -> &Coordinates) -> HashMap<&Coordinates, &T> {
+    let mut map = HashMap::new();
+    for entry in list {
+        map.entry(position(entry)).or_insert(entry);
+    }
+    map
+}
+
+impl std::fmt::Display for WorldDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "no differences");
+        }
+        if let Some(((w1, h1), (w2, h2))) = self.size {
+            writeln!(f, "size changed from {w1}x{h1} to {w2}x{h2}")?;
+        }
+        if !self.tiles.is_empty() {
+            writeln!(
+                f,
+                "{} tiles changed in {} areas",
+                self.changed_tiles,
+                self.tiles.len()
+            )?;
+            for rect in &self.tiles {
+                writeln!(f, "  {rect}")?;
+            }
+        }
+        for change in &self.chests {
+            writeln!(f, "{change}")?;
+        }
+        for change in &self.signs {
+            writeln!(f, "{change}")?;
+        }
+        for change in &self.npcs {
+            writeln!(f, "{change}")?;
+        }
+        for change in &self.tile_entities {
+            writeln!(f, "{change}")?;
+        }
+        for change in &self.header {
+            writeln!(f, "{change}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for ChestChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChestChange::Added(chest) => write!(
+                f,
+                "chest {:?} added at ({}, {})",
+                chest.name, chest.position.x, chest.position.y
+            ),
+            ChestChange::Removed(chest) => write!(
+                f,
+                "chest {:?} removed from ({}, {})",
+                chest.name, chest.position.x, chest.position.y
+            ),
+            ChestChange::Modified {
+                position,
+                name,
+                items,
+            } => {
+                write!(f, "chest at ({}, {}) changed", position.x, position.y)?;
+                if let Some((before, after)) = name {
+                    write!(f, "\n  name {before:?} -> {after:?}")?;
+                }
+                for item in items {
+                    write!(
+                        f,
+                        "\n  slot {}: {} -> {}",
+                        item.slot,
+                        describe_item(&item.before),
+                        describe_item(&item.after)
+                    )?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+fn describe_item(item: &Option<ItemStack>) -> String {
+    match item {
+        Some(item) if item.prefix != 0 => format!(
+            "{}x item {} (prefix {})",
+            item.quantity, item.type_id, item.prefix
+        ),
+        Some(item) => format!("{}x item {}", item.quantity, item.type_id),
+        None => "empty".to_string(),
+    }
+}
+
+impl std::fmt::Display for SignChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SignChange::Added(sign) => write!(
+                f,
+                "sign added at ({}, {}): {:?}",
+                sign.position.x, sign.position.y, sign.text
+            ),
+            SignChange::Removed(sign) => write!(
+                f,
+                "sign removed from ({}, {}): {:?}",
+                sign.position.x, sign.position.y, sign.text
+            ),
+            SignChange::TextChanged {
+                position,
+                before,
+                after,
+            } => write!(
+                f,
+                "sign at ({}, {}) text {before:?} -> {after:?}",
+                position.x, position.y
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for NpcChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NpcChange::Added(npc) => write!(f, "npc {:?} ({}) added", npc.name, npc.type_),
+            NpcChange::Removed(npc) => write!(f, "npc {:?} ({}) removed", npc.name, npc.type_),
+            NpcChange::Moved {
+                type_,
+                name,
+                from,
+                to,
+            } => write!(
+                f,
+                "npc {name:?} ({type_}) moved from ({}, {}) to ({}, {})",
+                from.0, from.1, to.0, to.1
+            ),
+            NpcChange::MovedHome {
+                type_,
+                name,
+                from,
+                to,
+            } => write!(
+                f,
+                "npc {name:?} ({type_}) home moved from {} to {}",
+                describe_home(from),
+                describe_home(to)
+            ),
+        }
+    }
+}
+
+fn describe_home((home, is_homeless): &(Coordinates, bool)) -> String {
+    if *is_homeless {
+        "none".to_string()
+    } else {
+        format!("({}, {})", home.x, home.y)
+    }
+}
+
+impl std::fmt::Display for TileEntityChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TileEntityChange::Added(entity) => write!(
+                f,
+                "tile entity {} added at ({}, {})",
+                entity.id, entity.position.x, entity.position.y
+            ),
+            TileEntityChange::Removed(entity) => write!(
+                f,
+                "tile entity {} removed from ({}, {})",
+                entity.id, entity.position.x, entity.position.y
+            ),
+            TileEntityChange::Modified { before, after } => write!(
+                f,
+                "tile entity {} at ({}, {}) changed",
+                after.id, before.position.x, before.position.y
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for HeaderChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} -> {}", self.field, self.before, self.after)
+    }
+}
@@ -0,0 +1,599 @@
+use serde::{Deserialize, Serialize};
+
+use super::sections::Section;
+use super::World;
+use crate::reader::ByteReader;
+
+/// One decoded field of a `.wld` file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldAnnotation {
+    pub name: String,
+    pub offset: usize,
+    pub raw: Vec<u8>,
+    pub value: String,
+}
+
+/// Where a part of the file lives, with its fields for the parts we annotate.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SectionLayout {
+    /// `None` for the file header and for pointer table entries past the
+    /// sections this crate knows about.
+    pub section: Option<Section>,
+    /// Index in the pointer table, `None` for the file header.
+    pub pointer_index: Option<usize>,
+    pub start: usize,
+    pub end: usize,
+    pub size: usize,
+    /// Only filled for the file header, the world header and the footer.
+    pub fields: Vec<FieldAnnotation>,
+    /// Set when the fields do not line up with the section boundaries.
+    pub problem: Option<String>,
+}
+
+impl SectionLayout {
+    pub fn name(&self) -> &'static str {
+        match (self.section, self.pointer_index) {
+            (Some(section), _) => section.name(),
+            (None, Some(_)) => "unknown section",
+            (None, None) => "file header",
+        }
+    }
+
+    pub fn field(&self, name: &str) -> Option<&FieldAnnotation> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+}
+
+/// Byte layout of a `.wld` file, see [`World::dissect`].
+///
+/// `Display` prints it as an annotated hex dump.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileLayout {
+    pub file_size: usize,
+    pub pointer_vector: Vec<u32>,
+    /// The file header first, then every section of the pointer table in file order.
+    pub sections: Vec<SectionLayout>,
+}
+
+impl FileLayout {
+    pub fn section(&self, section: Section) -> Option<&SectionLayout> {
+        self.sections.iter().find(|s| s.section == Some(section))
+    }
+
+    /// Parts of the file whose fields drifted from the pointer table.
+    pub fn problems(&self) -> Vec<(&'static str, &str)> {
+        self.sections
+            .iter()
+            .filter_map(|s| s.problem.as_deref().map(|problem| (s.name(), problem)))
+            .collect()
+    }
+}
+
+// Raw bytes beyond this are elided in the hex dump
+const HEX_DUMP_WIDTH: usize = 16;
+
+impl std::fmt::Display for FileLayout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "file size: {} bytes", self.file_size)?;
+        for section in &self.sections {
+            writeln!(
+                f,
+                "{} 0x{:08x}..0x{:08x} ({} bytes)",
+                section.name(),
+                section.start,
+                section.end,
+                section.size
+            )?;
+            if let Some(problem) = &section.problem {
+                writeln!(f, "  !! {problem}")?;
+            }
+            for field in &section.fields {
+                writeln!(
+                    f,
+                    "  0x{:08x}  {:<56} {} = {}",
+                    field.offset,
+                    hex(&field.raw),
+                    field.name,
+                    field.value
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    let mut out: Vec<String> = bytes
+        .iter()
+        .take(HEX_DUMP_WIDTH)
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    if bytes.len() > HEX_DUMP_WIDTH {
+        out.push(format!("..(+{})", bytes.len() - HEX_DUMP_WIDTH));
+    }
+    out.join(" ")
+}
+
+#[derive(Debug, Clone, Copy)]
+enum FieldKind {
+    U8,
+    I8,
+    Bool,
+    I16,
+    U16,
+    I32,
+    U32,
+    U64,
+    F32,
+    F64,
+    Str,
+    Uuid,
+    DateTime,
+    /// The 7 byte "relogic" magic
+    Magic,
+    /// i32 count followed by that many strings
+    Strings,
+    /// i32 count followed by that many i32
+    I32s,
+    /// i16 count followed by that many i32
+    ShortI32s,
+    /// i16 count followed by one bit per entry
+    Bits,
+}
+
+// Fields of the world header section, in the order `World::from_file` reads them
+const WORLD_HEADER_FIELDS: &[(&str, FieldKind)] = &[
+    ("world_name", FieldKind::Str),
+    ("generator_seed", FieldKind::Str),
+    ("generator_version", FieldKind::U64),
+    ("uuid", FieldKind::Uuid),
+    ("id", FieldKind::I32),
+    ("bounds_vec.left", FieldKind::I32),
+    ("bounds_vec.right", FieldKind::I32),
+    ("bounds_vec.top", FieldKind::I32),
+    ("bounds_vec.bottom", FieldKind::I32),
+    ("world_height", FieldKind::I32),
+    ("world_width", FieldKind::I32),
+    ("difficulty_value", FieldKind::I32),
+    ("is_drunk_world", FieldKind::Bool),
+    ("is_for_the_worthy", FieldKind::Bool),
+    ("is_tenth_anniversary", FieldKind::Bool),
+    ("is_the_constant", FieldKind::Bool),
+    ("is_bee_world", FieldKind::Bool),
+    ("is_upside_down", FieldKind::Bool),
+    ("is_trap_world", FieldKind::Bool),
+    ("is_zenith_world", FieldKind::Bool),
+    ("created_on", FieldKind::DateTime),
+    ("moon_style", FieldKind::U8),
+    ("tree_style_separators[0]", FieldKind::I32),
+    ("tree_style_separators[1]", FieldKind::I32),
+    ("tree_style_separators[2]", FieldKind::I32),
+    ("tree_style_properties[0]", FieldKind::I32),
+    ("tree_style_properties[1]", FieldKind::I32),
+    ("tree_style_properties[2]", FieldKind::I32),
+    ("tree_style_properties[3]", FieldKind::I32),
+    ("moss_style_separators[0]", FieldKind::I32),
+    ("moss_style_separators[1]", FieldKind::I32),
+    ("moss_style_separators[2]", FieldKind::I32),
+    ("moss_style_properties[0]", FieldKind::I32),
+    ("moss_style_properties[1]", FieldKind::I32),
+    ("moss_style_properties[2]", FieldKind::I32),
+    ("moss_style_properties[3]", FieldKind::I32),
+    ("snow_background_style", FieldKind::I32),
+    ("jungle_background_style", FieldKind::I32),
+    ("hell_background_style", FieldKind::I32),
+    ("spawn_point_x", FieldKind::I32),
+    ("spawn_point_y", FieldKind::I32),
+    ("underground_level", FieldKind::F64),
+    ("cavern_level", FieldKind::F64),
+    ("current_time", FieldKind::F64),
+    ("is_daytime", FieldKind::Bool),
+    ("moon_phase", FieldKind::U32),
+    ("blood_moon", FieldKind::Bool),
+    ("eclipse", FieldKind::Bool),
+    ("dungeon_point_x", FieldKind::I32),
+    ("dungeon_point_y", FieldKind::I32),
+    ("world_evil_type", FieldKind::Bool),
+    ("defeated_eye_of_cthulhu", FieldKind::Bool),
+    ("defeated_eater_of_worlds", FieldKind::Bool),
+    ("defeated_skeletron", FieldKind::Bool),
+    ("defeated_queen_bee", FieldKind::Bool),
+    ("defeated_the_twins", FieldKind::Bool),
+    ("defeated_the_destroyer", FieldKind::Bool),
+    ("defeated_skeletron_prime", FieldKind::Bool),
+    ("defeated_any_mechanical_boss", FieldKind::Bool),
+    ("defeated_plantera", FieldKind::Bool),
+    ("defeated_golem", FieldKind::Bool),
+    ("defeated_king_slime", FieldKind::Bool),
+    ("saved_goblin_tinkerer", FieldKind::Bool),
+    ("saved_wizard", FieldKind::Bool),
+    ("saved_mechanic", FieldKind::Bool),
+    ("defeated_goblin_army", FieldKind::Bool),
+    ("defeated_clown", FieldKind::Bool),
+    ("defeated_frost_moon", FieldKind::Bool),
+    ("defeated_pirate_invasion", FieldKind::Bool),
+    ("shadow_orbs_smashed_at_least_once", FieldKind::Bool),
+    ("shadow_orbs_spawn_meteorite", FieldKind::Bool),
+    ("shadow_orbs_evil_boss_counter", FieldKind::U8),
+    ("altars_smashed", FieldKind::I32),
+    ("is_hardmode", FieldKind::Bool),
+    ("party_is_doomed", FieldKind::Bool),
+    ("invasion_delay", FieldKind::I32),
+    ("invasion_size", FieldKind::I32),
+    ("invasion_type", FieldKind::I32),
+    ("invasion_position", FieldKind::F64),
+    ("time_left_slime_rain", FieldKind::F64),
+    ("sundial_cooldown", FieldKind::U8),
+    ("is_rain_active", FieldKind::Bool),
+    ("rain_time_left", FieldKind::I32),
+    ("max_rain", FieldKind::F32),
+    ("hardmode_ore_1", FieldKind::I32),
+    ("hardmode_ore_2", FieldKind::I32),
+    ("hardmode_ore_3", FieldKind::I32),
+    ("forest_background", FieldKind::I8),
+    ("corruption_background", FieldKind::I8),
+    ("jungle_background", FieldKind::I8),
+    ("snow_background", FieldKind::I8),
+    ("hallow_background", FieldKind::I8),
+    ("crimson_background", FieldKind::I8),
+    ("desert_background", FieldKind::I8),
+    ("ocean_background", FieldKind::I8),
+    ("cloud_background", FieldKind::I32),
+    ("cloud_number", FieldKind::I16),
+    ("wind_speed", FieldKind::F32),
+    ("angler_today_quest_completed_by", FieldKind::Strings),
+    ("saved_angler", FieldKind::Bool),
+    ("angler_daily_quest_target", FieldKind::I32),
+    ("saved_stylist", FieldKind::Bool),
+    ("saved_tax_collector", FieldKind::Bool),
+    ("saved_golfer", FieldKind::Bool),
+    ("invasion_size_start", FieldKind::I32),
+    ("cultist_delay", FieldKind::I32),
+    ("mob_kills", FieldKind::ShortI32s),
+    ("sundial_is_running", FieldKind::Bool),
+    ("defeated_duke_fishron", FieldKind::Bool),
+    ("defeated_martian_madness", FieldKind::Bool),
+    ("defeated_lunatic_cultist", FieldKind::Bool),
+    ("defeated_moon_lord", FieldKind::Bool),
+    ("defeated_pumpking", FieldKind::Bool),
+    ("defeated_mourning_wood", FieldKind::Bool),
+    ("defeated_ice_queen", FieldKind::Bool),
+    ("defeated_santa_nk1", FieldKind::Bool),
+    ("defeated_everscream", FieldKind::Bool),
+    ("defeated_solar_pillar", FieldKind::Bool),
+    ("defeated_vortex_pillar", FieldKind::Bool),
+    ("defeated_nebula_pillar", FieldKind::Bool),
+    ("defeated_stardust_pillar", FieldKind::Bool),
+    ("lunar_events_pillars_present_solar", FieldKind::Bool),
+    ("lunar_events_pillars_present_vortex", FieldKind::Bool),
+    ("lunar_events_pillars_present_nebula", FieldKind::Bool),
+    ("lunar_events_pillars_present_stardust", FieldKind::Bool),
+    ("lunar_events_are_active", FieldKind::Bool),
+    ("party_center_active", FieldKind::Bool),
+    ("party_natural_active", FieldKind::Bool),
+    ("party_cooldown", FieldKind::I32),
+    ("partying_npcs", FieldKind::I32s),
+    ("is_sandstorm_active", FieldKind::Bool),
+    ("sandstorm_time_left", FieldKind::I32),
+    ("sandstorm_severity", FieldKind::F32),
+    ("sandstorm_intended_severity", FieldKind::F32),
+    ("saved_bartender", FieldKind::Bool),
+    ("old_ones_army_tier_1", FieldKind::Bool),
+    ("old_ones_army_tier_2", FieldKind::Bool),
+    ("old_ones_army_tier_3", FieldKind::Bool),
+    ("mushroom_background", FieldKind::I8),
+    ("underworld_background", FieldKind::I8),
+    ("forest_background_2", FieldKind::I8),
+    ("forest_background_3", FieldKind::I8),
+    ("forest_background_4", FieldKind::I8),
+    ("combat_book_used", FieldKind::Bool),
+    ("lantern_nights_on_cooldown", FieldKind::I32),
+    ("lantern_night_genuine", FieldKind::Bool),
+    ("lantern_night_manual", FieldKind::Bool),
+    ("next_night_is_lantern_night", FieldKind::Bool),
+    ("treetop_variants", FieldKind::I32s),
+    ("halloween_today", FieldKind::Bool),
+    ("christmas_today", FieldKind::Bool),
+    ("ore_1", FieldKind::I32),
+    ("ore_2", FieldKind::I32),
+    ("ore_3", FieldKind::I32),
+    ("ore_4", FieldKind::I32),
+    ("has_cat", FieldKind::Bool),
+    ("has_dog", FieldKind::Bool),
+    ("has_bunny", FieldKind::Bool),
+    ("defeated_empress_of_light", FieldKind::Bool),
+    ("defeated_queen_slime", FieldKind::Bool),
+    ("defeated_deerclops", FieldKind::Bool),
+    ("saved_slime_nerdy", FieldKind::Bool),
+    ("saved_merchant", FieldKind::Bool),
+    ("saved_demolitionist", FieldKind::Bool),
+    ("saved_party_girl", FieldKind::Bool),
+    ("saved_dye_trader", FieldKind::Bool),
+    ("saved_truffle", FieldKind::Bool),
+    ("saved_arms_dealer", FieldKind::Bool),
+    ("saved_nurse", FieldKind::Bool),
+    ("saved_princess", FieldKind::Bool),
+    ("combat_book_2_used", FieldKind::Bool),
+    ("peddler_satchel_used", FieldKind::Bool),
+    ("saved_slime_cool", FieldKind::Bool),
+    ("saved_slime_elder", FieldKind::Bool),
+    ("saved_slime_clumsy", FieldKind::Bool),
+    ("saved_slime_diva", FieldKind::Bool),
+    ("saved_slime_surly", FieldKind::Bool),
+    ("saved_slime_mystic", FieldKind::Bool),
+    ("saved_slime_squire", FieldKind::Bool),
+    ("moondial_is_running", FieldKind::Bool),
+    ("moondial_cooldown", FieldKind::U8),
+];
+
+const FOOTER_FIELDS: &[(&str, FieldKind)] = &[
+    ("footer_marker", FieldKind::Bool),
+    ("world_name", FieldKind::Str),
+    ("id", FieldKind::I32),
+];
+
+impl World {
+    /// Describe the byte layout of a world file without loading it.
+    ///
+    /// Every section of the pointer table is listed with its offsets and size.
+    /// The file header, the world header and the footer are also broken down
+    /// field by field, so a pointer mismatch reported by [`World::from_file`]
+    /// can be traced to the field that drifted. Truncated or corrupted files
+    /// are described as far as they can be read.
+    pub fn dissect(path: &str) -> std::io::Result<FileLayout> {
+        let bytes = std::fs::read(path)?;
+        Ok(dissect_bytes(&bytes))
+    }
+}
+
+fn dissect_bytes(bytes: &[u8]) -> FileLayout {
+    let mut cursor = FieldCursor::new(bytes, 0);
+    for (name, kind) in [
+        ("version", FieldKind::I32),
+        ("magic", FieldKind::Magic),
+        ("savefile_type", FieldKind::U8),
+        ("revision", FieldKind::U32),
+        ("is_favorite", FieldKind::U64),
+    ] {
+        cursor.field(name, kind);
+    }
+
+    let mut pointer_vector = Vec::new();
+    if let Some(count) = cursor.field("pointer_count", FieldKind::U16) {
+        let count = u16::from_le_bytes([count[0], count[1]]);
+        for i in 0..count {
+            match cursor.field(&format!("pointer_vector[{i}]"), FieldKind::U32) {
+                Some(raw) => pointer_vector.push(u32::from_le_bytes(raw.try_into().unwrap())),
+                None => break,
+            }
+        }
+    }
+    cursor.field("tile_frame_important", FieldKind::Bits);
+
+    let mut sections = vec![cursor.finish(None, None, pointer_vector.first().map(|&p| p as usize))];
+    for (index, &start) in pointer_vector.iter().enumerate() {
+        let section = Section::ALL.get(index).copied();
+        let start = start as usize;
+        let next = pointer_vector.get(index + 1).map(|&p| p as usize);
+        let fields = match section {
+            Some(Section::Header) => WORLD_HEADER_FIELDS,
+            Some(Section::Footer) => FOOTER_FIELDS,
+            _ => &[],
+        };
+        if fields.is_empty() {
+            let end = next.unwrap_or(bytes.len());
+            sections.push(SectionLayout {
+                section,
+                pointer_index: Some(index),
+                start,
+                end,
+                size: end.saturating_sub(start),
+                fields: Vec::new(),
+                problem: boundary_problem(start, end, bytes.len()),
+            });
+            continue;
+        }
+
+        let mut cursor = FieldCursor::new(bytes, start);
+        for &(name, kind) in fields {
+            if cursor.field(name, kind).is_none() {
+                break;
+            }
+        }
+        let mut layout = cursor.finish(section, Some(index), next);
+        if layout.problem.is_none() {
+            layout.problem = boundary_problem(start, layout.end, bytes.len());
+        }
+        sections.push(layout);
+    }
+
+    FileLayout {
+        file_size: bytes.len(),
+        pointer_vector,
+        sections,
+    }
+}
+
+fn boundary_problem(start: usize, end: usize, file_size: usize) -> Option<String> {
+    if start > end {
+        Some(format!(
+            "starts at {start}, after the next section at {end}"
+        ))
+    } else if end > file_size {
+        Some(format!(
+            "ends at {end}, past the end of the file ({file_size} bytes)"
+        ))
+    } else {
+        None
+    }
+}
+
+// Reads annotated fields without ever reading past the end of the data
+struct FieldCursor<'a> {
+    bytes: &'a [u8],
+    start: usize,
+    offset: usize,
+    fields: Vec<FieldAnnotation>,
+    problem: Option<String>,
+}
+
+impl<'a> FieldCursor<'a> {
+    fn new(bytes: &'a [u8], start: usize) -> Self {
+        Self {
+            bytes,
+            start,
+            offset: start,
+            fields: Vec::new(),
+            problem: None,
+        }
+    }
+
+    // Returns the raw bytes of the field, or None once the data runs out
+    fn field(&mut self, name: &str, kind: FieldKind) -> Option<&'a [u8]> {
+        if self.problem.is_some() {
+            return None;
+        }
+        let rest = self.bytes.get(self.offset..).unwrap_or(&[]);
+        let Some(size) = field_size(kind, rest) else {
+            self.problem = Some(format!(
+                "field {name} at {} runs past the end of the file",
+                self.offset
+            ));
+            return None;
+        };
+        let raw = &rest[..size];
+        self.fields.push(FieldAnnotation {
+            name: name.to_string(),
+            offset: self.offset,
+            raw: raw.to_vec(),
+            value: decode_value(kind, raw),
+        });
+        self.offset += size;
+        Some(raw)
+    }
+
+    fn finish(
+        self,
+        section: Option<Section>,
+        pointer_index: Option<usize>,
+        next: Option<usize>,
+    ) -> SectionLayout {
+        let mut problem = self.problem;
+        if problem.is_none() {
+            if let Some(next) = next.filter(|&next| next != self.offset) {
+                problem = Some(format!(
+                    "fields end at {} but the next section starts at {next}",
+                    self.offset
+                ));
+            }
+        }
+        SectionLayout {
+            section,
+            pointer_index,
+            start: self.start,
+            end: self.offset,
+            size: self.offset - self.start,
+            fields: self.fields,
+            problem,
+        }
+    }
+}
+
+fn field_size(kind: FieldKind, data: &[u8]) -> Option<usize> {
+    let size = match kind {
+        FieldKind::U8 | FieldKind::I8 | FieldKind::Bool => 1,
+        FieldKind::I16 | FieldKind::U16 => 2,
+        FieldKind::I32 | FieldKind::U32 | FieldKind::F32 => 4,
+        FieldKind::U64 | FieldKind::F64 | FieldKind::DateTime => 8,
+        FieldKind::Uuid => 16,
+        FieldKind::Magic => 7,
+        FieldKind::Str => string_size(data)?,
+        FieldKind::Strings => {
+            let count = i32::from_le_bytes(data.get(..4)?.try_into().unwrap());
+            let mut size = 4;
+            for _ in 0..count.max(0) {
+                size += string_size(data.get(size..)?)?;
+            }
+            size
+        }
+        FieldKind::I32s => {
+            let count = i32::from_le_bytes(data.get(..4)?.try_into().unwrap());
+            4 + count.max(0) as usize * 4
+        }
+        FieldKind::ShortI32s => {
+            let count = i16::from_le_bytes(data.get(..2)?.try_into().unwrap());
+            2 + count.max(0) as usize * 4
+        }
+        FieldKind::Bits => {
+            let count = i16::from_le_bytes(data.get(..2)?.try_into().unwrap());
+            2 + (count.max(0) as usize).div_ceil(8)
+        }
+    };
+    (size <= data.len()).then_some(size)
+}
+
+// Strings are prefixed with their length as a 7-bit encoded integer
+fn string_size(data: &[u8]) -> Option<usize> {
+    let mut length = 0usize;
+    for (index, &byte) in data.iter().enumerate().take(5) {
+        length |= ((byte & 0x7F) as usize) << (7 * index);
+        if byte & 0x80 == 0 {
+            return Some(index + 1 + length);
+        }
+    }
+    None
+}
+
+// `raw` is exactly as long as `field_size` said, so reading it cannot fail
+fn decode_value(kind: FieldKind, raw: &[u8]) -> String {
+    let mut r = ByteReader::new(raw);
+    match kind {
+        FieldKind::U8 => r.u8().to_string(),
+        FieldKind::I8 => r.i8().to_string(),
+        FieldKind::Bool => r.bool().to_string(),
+        FieldKind::I16 => r.i16().to_string(),
+        FieldKind::U16 => r.u16().to_string(),
+        FieldKind::I32 => r.i32().to_string(),
+        FieldKind::U32 => r.u32().to_string(),
+        FieldKind::U64 => r.u64().to_string(),
+        FieldKind::F32 => r.f32().to_string(),
+        FieldKind::F64 => r.f64().to_string(),
+        FieldKind::Str => format!("{:?}", r.string(None)),
+        FieldKind::Uuid => r.uuid(),
+        FieldKind::DateTime => r.datetime(),
+        FieldKind::Magic => format!("{:?}", String::from_utf8_lossy(raw)),
+        FieldKind::Strings => {
+            let count = r.i32();
+            let values: Vec<String> = (0..count).map(|_| r.string(None)).collect();
+            format!("{values:?}")
+        }
+        FieldKind::I32s => {
+            let count = r.i32();
+            let values: Vec<i32> = (0..count).map(|_| r.i32()).collect();
+            summarize(&values)
+        }
+        FieldKind::ShortI32s => {
+            let count = r.i16();
+            let values: Vec<i32> = (0..count).map(|_| r.i32()).collect();
+            summarize(&values)
+        }
+        FieldKind::Bits => {
+            let count = r.i16().max(0) as usize;
+            let set = raw[2..]
+                .iter()
+                .map(|byte| byte.count_ones() as usize)
+                .sum::<usize>();
+            format!("{count} entries, {set} set")
+        }
+    }
+}
+
+// Long lists (e.g. mob_kills) only show their first values
+fn summarize(values: &[i32]) -> String {
+    const SHOWN: usize = 8;
+    if values.len() <= SHOWN {
+        return format!("{values:?}");
+    }
+    let shown: Vec<String> = values[..SHOWN].iter().map(|v| v.to_string()).collect();
+    format!("{} values: [{}, ...]", values.len(), shown.join(", "))
+}
@@ -0,0 +1,423 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::chest::Chest;
+use super::error::EditError;
+use super::pressure_plate::WeighedPressurePlate;
+use super::region::Rect;
+use super::room::Room;
+use super::sections::Section;
+use super::sign::Sign;
+use super::tile::Tile;
+use super::tile_entity::TileEntity;
+use super::World;
+
+/// The part of a list an edit changed, with the index of every item, before
+/// (`removed`) and after (`inserted`) the edit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Splice<T> {
+    pub removed: Vec<(usize, T)>,
+    pub inserted: Vec<(usize, T)>,
+}
+
+impl<T: Clone + PartialEq> Splice<T> {
+    // What lies between the items `before` and `after` start and end with,
+    // `None` when nothing changed
+    fn between(before: &[T], after: &[T]) -> Option<Self> {
+        let start = before.iter().zip(after).take_while(|(a, b)| a == b).count();
+        let end = before[start..]
+            .iter()
+            .rev()
+            .zip(after[start..].iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+        let changed = |list: &[T]| -> Vec<(usize, T)> {
+            list[start..list.len() - end]
+                .iter()
+                .enumerate()
+                .map(|(i, item)| (start + i, item.clone()))
+                .collect()
+        };
+        let (removed, inserted) = (changed(before), changed(after));
+        (!removed.is_empty() || !inserted.is_empty()).then_some(Self { removed, inserted })
+    }
+
+    fn apply(&self, list: &mut Vec<T>) {
+        Self::swap(list, &self.removed, &self.inserted);
+    }
+
+    fn revert(&self, list: &mut Vec<T>) {
+        Self::swap(list, &self.inserted, &self.removed);
+    }
+
+    // Items outside the splice keep their order, so taking `out` from the
+    // back and putting `into` in from the front lands everything at its index
+    fn swap(list: &mut Vec<T>, out: &[(usize, T)], into: &[(usize, T)]) {
+        for (i, _) in out.iter().rev() {
+            list.remove(*i);
+        }
+        for (i, item) in into {
+            list.insert(*i, item.clone());
+        }
+    }
+}
+
+// What the editing methods are about to change while an edit session
+// records: every tile before its first change, and the lists before the
+// first change to any of them
+#[derive(Debug, Default)]
+pub(crate) struct ChangeLog {
+    tiles: BTreeMap<(usize, usize), Tile>,
+    lists: Option<Lists>,
+}
+
+// The lists an edit can change besides the tiles
+#[derive(Debug)]
+struct Lists {
+    chests: Vec<Chest>,
+    signs: Vec<Sign>,
+    tile_entities: Vec<TileEntity>,
+    weighed_pressure_plates: Vec<WeighedPressurePlate>,
+    rooms: Vec<Room>,
+}
+
+impl Lists {
+    fn of(world: &World) -> Self {
+        Self {
+            chests: world.chests.clone(),
+            signs: world.signs.clone(),
+            tile_entities: world.tile_entities.clone(),
+            weighed_pressure_plates: world.weighed_pressure_plates.clone(),
+            rooms: world.rooms.clone(),
+        }
+    }
+
+    fn lengths(world: &World) -> [usize; 5] {
+        [
+            world.chests.len(),
+            world.signs.len(),
+            world.tile_entities.len(),
+            world.weighed_pressure_plates.len(),
+            world.rooms.len(),
+        ]
+    }
+}
+
+// Sections holding the lists `touch_lists` is about
+const LIST_SECTIONS: [Section; 5] = [
+    Section::Chests,
+    Section::Signs,
+    Section::TileEntities,
+    Section::PressurePlates,
+    Section::TownManager,
+];
+
+impl World {
+    // Called by the editing methods before they change tiles in `rect`
+    pub(crate) fn touch_tiles(&mut self, rect: Rect) {
+        self.mark_tiles_dirty(rect);
+        let Some(log) = &mut self.change_log else {
+            return;
+        };
+        let Some(rect) = rect.intersect(&Rect::new(
+            0,
+            0,
+            self.tiles.size.0 as i32,
+            self.tiles.size.1 as i32,
+        )) else {
+            return;
+        };
+        for x in rect.x as usize..rect.right() as usize {
+            for y in rect.y as usize..rect.bottom() as usize {
+                log.tiles
+                    .entry((x, y))
+                    .or_insert_with(|| self.tiles.tiles[x][y].clone());
+            }
+        }
+    }
+
+    // Called by the editing methods before they change chests, signs, tile
+    // entities, weighed pressure plates or rooms
+    pub(crate) fn touch_lists(&mut self) {
+        for section in LIST_SECTIONS {
+            self.mark_dirty(section);
+        }
+        if self
+            .change_log
+            .as_ref()
+            .is_some_and(|log| log.lists.is_none())
+        {
+            let lists = Lists::of(self);
+            if let Some(log) = &mut self.change_log {
+                log.lists = Some(lists);
+            }
+        }
+    }
+}
+
+/// A single recorded change, holding what is needed to undo and redo it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum EditOperation {
+    Tile {
+        x: usize,
+        y: usize,
+        before: Tile,
+        after: Tile,
+    },
+    Chests(Splice<Chest>),
+    Signs(Splice<Sign>),
+    TileEntities(Splice<TileEntity>),
+    WeighedPressurePlates(Splice<WeighedPressurePlate>),
+    Rooms(Splice<Room>),
+}
+
+/// Operations that are undone and redone together.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Transaction {
+    pub name: String,
+    pub operations: Vec<EditOperation>,
+}
+
+/// Edits a [`World`] while recording every change, so it can be undone and redone.
+///
+/// Each edit outside of [`EditSession::begin`] / [`EditSession::commit`] is a
+/// transaction of its own. Changes made to the world outside of the session
+/// are not recorded, and undoing across them may give odd results.
+#[derive(Debug)]
+pub struct EditSession<'w> {
+    world: &'w mut World,
+    done: Vec<Transaction>,
+    undone: Vec<Transaction>,
+    open: Option<Transaction>,
+}
+
+impl<'w> EditSession<'w> {
+    pub fn new(world: &'w mut World) -> Self {
+        Self {
+            world,
+            done: Vec::new(),
+            undone: Vec::new(),
+            open: None,
+        }
+    }
+
+    pub fn world(&self) -> &World {
+        self.world
+    }
+
+    /// Run `edit` on the world and record what it changed.
+    ///
+    /// Everything the editing methods of [`World`] change is recorded, even
+    /// outside of `area`: tiles, chests, signs, tile entities, weighed
+    /// pressure plates and rooms. Writing to the world's fields directly is
+    /// only recorded for tiles inside `area`.
+    ///
+    /// Edits that resize the world, or add or remove list items without
+    /// going through the editing methods, can not be undone. They are an
+    /// error and the session forgets its history, since undoing anything
+    /// done before them could corrupt the world.
+    pub fn edit<R>(
+        &mut self,
+        name: &str,
+        area: Rect,
+        edit: impl FnOnce(&mut World) -> R,
+    ) -> Result<R, EditError> {
+        let size = self.world.tiles.size;
+        let lengths = Lists::lengths(self.world);
+        self.world.change_log = Some(ChangeLog::default());
+        self.world.touch_tiles(area);
+
+        let result = edit(self.world);
+
+        let log = self.world.change_log.take().unwrap_or_default();
+        if self.world.tiles.size != size {
+            self.forget();
+            return Err(EditError::Resized);
+        }
+        let world = &*self.world;
+        let mut operations = Vec::new();
+        for ((x, y), before) in log.tiles {
+            let after = &world.tiles.tiles[x][y];
+            if *after != before {
+                operations.push(EditOperation::Tile {
+                    x,
+                    y,
+                    before,
+                    after: after.clone(),
+                });
+            }
+        }
+        match log.lists {
+            Some(before) => {
+                let splices = [
+                    Splice::between(&before.chests, &world.chests).map(EditOperation::Chests),
+                    Splice::between(&before.signs, &world.signs).map(EditOperation::Signs),
+                    Splice::between(&before.tile_entities, &world.tile_entities)
+                        .map(EditOperation::TileEntities),
+                    Splice::between(
+                        &before.weighed_pressure_plates,
+                        &world.weighed_pressure_plates,
+                    )
+                    .map(EditOperation::WeighedPressurePlates),
+                    Splice::between(&before.rooms, &world.rooms).map(EditOperation::Rooms),
+                ];
+                operations.extend(splices.into_iter().flatten());
+            }
+            None if Lists::lengths(world) != lengths => {
+                self.forget();
+                return Err(EditError::Unrecorded);
+            }
+            None => {}
+        }
+        self.record(name, operations);
+        Ok(result)
+    }
+
+    /// Replace the tile at (`x`, `y`). Does nothing outside the world.
+    pub fn set_tile(&mut self, x: usize, y: usize, tile: Tile) {
+        self.edit("set tile", Rect::new(x as i32, y as i32, 1, 1), |world| {
+            if let Some(old) = world.tiles.tiles.get_mut(x).and_then(|c| c.get_mut(y)) {
+                *old = tile;
+            }
+        })
+        .expect("a single tile is always recorded");
+    }
+
+    /// Start a named transaction, everything edited until [`EditSession::commit`]
+    /// is undone as one step. An open transaction is committed first.
+    pub fn begin(&mut self, name: &str) {
+        self.commit();
+        self.open = Some(Transaction {
+            name: name.to_string(),
+            operations: Vec::new(),
+        });
+    }
+
+    /// Close the open transaction. Empty transactions are dropped.
+    pub fn commit(&mut self) {
+        if let Some(transaction) = self.open.take() {
+            self.push_done(transaction);
+        }
+    }
+
+    /// Undo everything done in the open transaction and drop it.
+    pub fn rollback(&mut self) {
+        if let Some(transaction) = self.open.take() {
+            self.revert(&transaction);
+        }
+    }
+
+    /// Undo the last transaction, committing the open one first. Returns its
+    /// name, `None` when there is nothing to undo.
+    pub fn undo(&mut self) -> Option<String> {
+        self.commit();
+        let transaction = self.done.pop()?;
+        self.revert(&transaction);
+        let name = transaction.name.clone();
+        self.undone.push(transaction);
+        Some(name)
+    }
+
+    /// Redo the last undone transaction and return its name.
+    pub fn redo(&mut self) -> Option<String> {
+        let transaction = self.undone.pop()?;
+        for operation in &transaction.operations {
+            self.apply(operation, true);
+        }
+        self.world.reindex_entities();
+        let name = transaction.name.clone();
+        self.done.push(transaction);
+        Some(name)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.done.is_empty() || self.open.as_ref().is_some_and(|t| !t.operations.is_empty())
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.undone.is_empty()
+    }
+
+    /// The committed transactions, oldest first. Undone ones are not included.
+    pub fn journal(&self) -> &[Transaction] {
+        &self.done
+    }
+
+    pub fn save_journal_as_json(&self, path: &str) -> std::io::Result<()> {
+        let file = std::fs::File::create(path)?;
+        let writer = std::io::BufWriter::new(file);
+        serde_json::to_writer_pretty(writer, &self.done)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+
+    // Drop every transaction, done, undone or open
+    fn forget(&mut self) {
+        self.done.clear();
+        self.undone.clear();
+        self.open = None;
+    }
+
+    fn record(&mut self, name: &str, operations: Vec<EditOperation>) {
+        if operations.is_empty() {
+            return;
+        }
+        self.undone.clear();
+        match &mut self.open {
+            Some(transaction) => transaction.operations.extend(operations),
+            None => self.push_done(Transaction {
+                name: name.to_string(),
+                operations,
+            }),
+        }
+    }
+
+    fn push_done(&mut self, transaction: Transaction) {
+        if !transaction.operations.is_empty() {
+            self.done.push(transaction);
+        }
+    }
+
+    fn revert(&mut self, transaction: &Transaction) {
+        for operation in transaction.operations.iter().rev() {
+            self.apply(operation, false);
+        }
+        self.world.reindex_entities();
+    }
+
+    fn apply(&mut self, operation: &EditOperation, forward: bool) {
+        let world = &mut *self.world;
+        if !matches!(operation, EditOperation::Tile { .. }) {
+            for section in LIST_SECTIONS {
+                world.mark_dirty(section);
+            }
+        }
+        match operation {
+            EditOperation::Tile {
+                x,
+                y,
+                before,
+                after,
+            } => {
+                world.mark_tiles_dirty(Rect::new(*x as i32, *y as i32, 1, 1));
+                world.tiles.tiles[*x][*y] = if forward { after } else { before }.clone();
+            }
+            EditOperation::Chests(splice) if forward => splice.apply(&mut world.chests),
+            EditOperation::Chests(splice) => splice.revert(&mut world.chests),
+            EditOperation::Signs(splice) if forward => splice.apply(&mut world.signs),
+            EditOperation::Signs(splice) => splice.revert(&mut world.signs),
+            EditOperation::TileEntities(splice) if forward => {
+                splice.apply(&mut world.tile_entities)
+            }
+            EditOperation::TileEntities(splice) => splice.revert(&mut world.tile_entities),
+            EditOperation::WeighedPressurePlates(splice) if forward => {
+                splice.apply(&mut world.weighed_pressure_plates)
+            }
+            EditOperation::WeighedPressurePlates(splice) => {
+                splice.revert(&mut world.weighed_pressure_plates)
+            }
+            EditOperation::Rooms(splice) if forward => splice.apply(&mut world.rooms),
+            EditOperation::Rooms(splice) => splice.revert(&mut world.rooms),
+        }
+    }
+}
@@ -0,0 +1,366 @@
+use std::collections::HashMap;
+
+use super::chest::Chest;
+use super::coordinates::Coordinates;
+use super::region::Rect;
+use super::sign::Sign;
+use super::tile_entity::TileEntity;
+use super::World;
+
+// A tile position, or a cell when bucketing
+type Point = (i32, i32);
+
+// Side of the square cells rectangle queries are bucketed by
+const CELL_SIZE: i32 = 64;
+
+/// A chest, sign or tile entity found by [`World::entity_at`] or [`World::entities_in`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Entity<'a> {
+    Chest(&'a Chest),
+    Sign(&'a Sign),
+    TileEntity(&'a TileEntity),
+}
+
+impl Entity<'_> {
+    /// Top-left tile of the object the entity belongs to.
+    pub fn position(&self) -> &Coordinates {
+        match self {
+            Entity::Chest(chest) => &chest.position,
+            Entity::Sign(sign) => &sign.position,
+            Entity::TileEntity(entity) => &entity.position,
+        }
+    }
+}
+
+// Where an entity is stored. Ordered like the game looks them up: chests
+// before signs before tile entities, lower indices first.
+//
+// The index itself stores ids instead of list indices, see `Ids`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Slot {
+    Chest(usize),
+    Sign(usize),
+    TileEntity(usize),
+}
+
+// Ids of the entities of one kind: their index in the list when the index was
+// built, or when they were pushed. Removing an entity only records its id, so
+// the ids of the others stay valid and their list index is their id minus the
+// removed ids before it. Ids keep the order of the list.
+//
+// This makes lookups and removals depend on how many entities were removed
+// instead of how many there are, `World::remove_entities` builds the index
+// again once the removed ones outnumber the others. Keeping list indices
+// themselves would mean shifting every later one on each removal.
+#[derive(Debug, Default)]
+struct Ids {
+    next: usize,
+    removed: Vec<usize>,
+}
+
+impl Ids {
+    fn new(len: usize) -> Self {
+        Self {
+            next: len,
+            removed: Vec::new(),
+        }
+    }
+
+    fn push(&mut self) -> usize {
+        self.next += 1;
+        self.next - 1
+    }
+
+    fn remove(&mut self, id: usize) {
+        let at = self.removed.partition_point(|&removed| removed < id);
+        self.removed.insert(at, id);
+    }
+
+    fn list_index(&self, id: usize) -> usize {
+        id - self.removed.partition_point(|&removed| removed < id)
+    }
+
+    // Ids of the entities in the list, in list order
+    fn live(&self) -> impl Iterator<Item = usize> + '_ {
+        let mut removed = self.removed.iter().peekable();
+        (0..self.next).filter(move |&id| {
+            let gone = removed.peek() == Some(&&id);
+            if gone {
+                removed.next();
+            }
+            !gone
+        })
+    }
+}
+
+// Positions of the chests, signs and tile entities of a world.
+//
+// Built when a world is loaded or created. The editing methods that add or
+// remove one update it in place, methods that rewrite whole lists build it
+// again. Hand edits to `chests`, `signs` or `tile_entities` need
+// [`World::reindex_entities`].
+#[derive(Debug, Default)]
+pub(crate) struct EntityIndex {
+    by_position: HashMap<Point, Slot>,
+    cells: HashMap<Point, Vec<(Slot, Point)>>,
+    chests: Ids,
+    signs: Ids,
+    tile_entities: Ids,
+}
+
+impl EntityIndex {
+    pub(crate) fn build(world: &World) -> Self {
+        let mut index = Self {
+            chests: Ids::new(world.chests.len()),
+            signs: Ids::new(world.signs.len()),
+            tile_entities: Ids::new(world.tile_entities.len()),
+            ..Default::default()
+        };
+        let slots = world
+            .chests
+            .iter()
+            .enumerate()
+            .map(|(i, chest)| (&chest.position, Slot::Chest(i)))
+            .chain(
+                world
+                    .signs
+                    .iter()
+                    .enumerate()
+                    .map(|(i, sign)| (&sign.position, Slot::Sign(i))),
+            )
+            .chain(
+                world
+                    .tile_entities
+                    .iter()
+                    .enumerate()
+                    .map(|(i, entity)| (&entity.position, Slot::TileEntity(i))),
+            );
+        for (position, slot) in slots {
+            index.insert(slot, position);
+        }
+        index
+    }
+
+    fn ids(&self, slot: Slot) -> &Ids {
+        match slot {
+            Slot::Chest(_) => &self.chests,
+            Slot::Sign(_) => &self.signs,
+            Slot::TileEntity(_) => &self.tile_entities,
+        }
+    }
+
+    fn ids_mut(&mut self, slot: Slot) -> &mut Ids {
+        match slot {
+            Slot::Chest(_) => &mut self.chests,
+            Slot::Sign(_) => &mut self.signs,
+            Slot::TileEntity(_) => &mut self.tile_entities,
+        }
+    }
+
+    // The list index of the entity stored with the id in `slot`
+    fn resolve(&self, slot: Slot) -> Slot {
+        match slot {
+            Slot::Chest(id) => Slot::Chest(self.chests.list_index(id)),
+            Slot::Sign(id) => Slot::Sign(self.signs.list_index(id)),
+            Slot::TileEntity(id) => Slot::TileEntity(self.tile_entities.list_index(id)),
+        }
+    }
+
+    fn push(&mut self, slot: fn(usize) -> Slot, position: &Coordinates) {
+        let id = self.ids_mut(slot(0)).push();
+        self.insert(slot(id), position);
+    }
+
+    fn insert(&mut self, slot: Slot, position: &Coordinates) {
+        let key = (position.x, position.y);
+        // the first one wins, like when the game looks them up
+        self.by_position
+            .entry(key)
+            .and_modify(|first| *first = (*first).min(slot))
+            .or_insert(slot);
+        self.cells
+            .entry(cell_of(key.0, key.1))
+            .or_default()
+            .push((slot, key));
+    }
+
+    // Forget the entity with the id in `slot`, the others keep their ids
+    fn remove(&mut self, slot: Slot, position: &Coordinates) {
+        let key = (position.x, position.y);
+        if let Some(cell) = self.cells.get_mut(&cell_of(key.0, key.1)) {
+            cell.retain(|&entry| entry != (slot, key));
+            if self.by_position.get(&key) == Some(&slot) {
+                let next = cell
+                    .iter()
+                    .filter(|(_, other)| *other == key)
+                    .map(|&(other, _)| other)
+                    .min();
+                match next {
+                    Some(next) => self.by_position.insert(key, next),
+                    None => self.by_position.remove(&key),
+                };
+            }
+        }
+        let (Slot::Chest(id) | Slot::Sign(id) | Slot::TileEntity(id)) = slot;
+        self.ids_mut(slot).remove(id);
+    }
+
+    fn removed(&self) -> usize {
+        self.chests.removed.len() + self.signs.removed.len() + self.tile_entities.removed.len()
+    }
+}
+
+// The chests and signs [`World::remove_entities`] took out of the world, in
+// list order. Removed tile entities hold nothing worth handing back.
+#[derive(Debug, Default)]
+pub(crate) struct RemovedEntities {
+    pub chests: Vec<Chest>,
+    pub signs: Vec<Sign>,
+}
+
+impl World {
+    /// The chest, sign or tile entity of the object covering (`x`, `y`),
+    /// without going through the lists. Any tile of a multi-tile object finds it.
+    ///
+    /// Lookups go through an index of every entity position, built when the
+    /// world is loaded and kept up to date by the editing methods. A lookup
+    /// is a hash lookup plus a binary search over the entities removed since
+    /// the index was last built, which is never more than the entities left.
+    /// After editing `chests`, `signs` or `tile_entities` by hand, call
+    /// [`World::reindex_entities`].
+    pub fn entity_at(&self, x: i32, y: i32) -> Option<Entity<'_>> {
+        self.entity(self.slot_at(x, y)?)
+    }
+
+    // Where the entity of the object covering (`x`, `y`) is in its list
+    pub(crate) fn slot_at(&self, x: i32, y: i32) -> Option<Slot> {
+        let origin = self.object_at(x, y)?;
+        let position = Coordinates {
+            x: origin.x,
+            y: origin.y,
+        };
+        let index = &self.entity_index;
+        let slot = index.resolve(*index.by_position.get(&(position.x, position.y))?);
+        self.entity(slot)
+            .is_some_and(|entity| *entity.position() == position)
+            .then_some(slot)
+    }
+
+    /// Chests, signs and tile entities whose top-left tile is inside `rect`,
+    /// using the same index as [`World::entity_at`].
+    pub fn entities_in(&self, rect: Rect) -> Vec<Entity<'_>> {
+        // only cells inside the world can hold anything
+        let Some(rect) = self.clip(rect) else {
+            return Vec::new();
+        };
+        let index = &self.entity_index;
+        let (x0, y0) = cell_of(rect.x, rect.y);
+        let (x1, y1) = cell_of(rect.right() - 1, rect.bottom() - 1);
+        let mut found = Vec::new();
+        for cx in x0..=x1 {
+            for cy in y0..=y1 {
+                for &(slot, _) in index.cells.get(&(cx, cy)).into_iter().flatten() {
+                    if let Some(entity) = self.entity(index.resolve(slot)) {
+                        if rect.contains_position(entity.position()) {
+                            found.push(entity);
+                        }
+                    }
+                }
+            }
+        }
+        found
+    }
+
+    /// Build the entity index again from `chests`, `signs` and
+    /// `tile_entities`. Needed after editing those lists directly, the
+    /// editing methods keep the index up to date themselves.
+    pub fn reindex_entities(&mut self) {
+        self.entity_index = EntityIndex::build(self);
+    }
+
+    pub(crate) fn push_chest(&mut self, chest: Chest) -> &mut Chest {
+        self.touch_lists();
+        self.entity_index.push(Slot::Chest, &chest.position);
+        self.chests.push(chest);
+        self.chests.last_mut().unwrap()
+    }
+
+    pub(crate) fn push_sign(&mut self, sign: Sign) -> &mut Sign {
+        self.touch_lists();
+        self.entity_index.push(Slot::Sign, &sign.position);
+        self.signs.push(sign);
+        self.signs.last_mut().unwrap()
+    }
+
+    pub(crate) fn push_tile_entity(&mut self, entity: TileEntity) -> &mut TileEntity {
+        self.touch_lists();
+        self.entity_index.push(Slot::TileEntity, &entity.position);
+        self.tile_entities.push(entity);
+        self.tile_entities.last_mut().unwrap()
+    }
+
+    // Take out every chest, sign and tile entity positioned where `matches`
+    // holds, keeping the order of the rest and the index up to date
+    pub(crate) fn remove_entities(
+        &mut self,
+        matches: impl Fn(&Coordinates) -> bool,
+    ) -> RemovedEntities {
+        self.touch_lists();
+        let index = &mut self.entity_index;
+        let removed = RemovedEntities {
+            chests: take_matching(&mut self.chests, Slot::Chest, index, |chest| {
+                matches(&chest.position).then_some(&chest.position)
+            }),
+            signs: take_matching(&mut self.signs, Slot::Sign, index, |sign| {
+                matches(&sign.position).then_some(&sign.position)
+            }),
+        };
+        take_matching(&mut self.tile_entities, Slot::TileEntity, index, |entity| {
+            matches(&entity.position).then_some(&entity.position)
+        });
+        // start over once the removed ids outnumber the entities left
+        let left = self.chests.len() + self.signs.len() + self.tile_entities.len();
+        if self.entity_index.removed() > left {
+            self.reindex_entities();
+        }
+        removed
+    }
+
+    fn entity(&self, slot: Slot) -> Option<Entity<'_>> {
+        match slot {
+            Slot::Chest(i) => self.chests.get(i).map(Entity::Chest),
+            Slot::Sign(i) => self.signs.get(i).map(Entity::Sign),
+            Slot::TileEntity(i) => self.tile_entities.get(i).map(Entity::TileEntity),
+        }
+    }
+}
+
+// Takes the entries of `list` that `matches` gives a position for out of it
+// and the index, in one pass over the list
+fn take_matching<T>(
+    list: &mut Vec<T>,
+    slot: fn(usize) -> Slot,
+    index: &mut EntityIndex,
+    matches: impl Fn(&T) -> Option<&Coordinates>,
+) -> Vec<T> {
+    let mut ids = index.ids(slot(0)).live().collect::<Vec<_>>().into_iter();
+    let mut taken = Vec::new();
+    let mut kept = Vec::with_capacity(list.len());
+    for entry in list.drain(..) {
+        // lists edited by hand can be longer than the index knows
+        match (matches(&entry), ids.next()) {
+            (Some(position), Some(id)) => {
+                index.remove(slot(id), position);
+                taken.push(entry);
+            }
+            (Some(_), None) => taken.push(entry),
+            (None, _) => kept.push(entry),
+        }
+    }
+    *list = kept;
+    taken
+}
+
+fn cell_of(x: i32, y: i32) -> (i32, i32) {
+    (x.div_euclid(CELL_SIZE), y.div_euclid(CELL_SIZE))
+}
@@ -65,56 +65,136 @@ pub static WALL_TYPE_NAMES: Lazy<HashMap<u16, &'static str>> = Lazy::new(|| {
     map
 });
 
-// pub struct BlockData {
-//     blends: String,
-//     check_types: String,
-//     color: String,
-//     framed: String,
-//     id: u16,
-//     light: String,
-//     merge_with: String,
-//     name: String,
-//     placement: String,
-//     size: String,
-//     solid: String,
-//     solid_top: String,
-//     special: String,
-//     stone: String,
-//     texture_grid: String,
-// }
+/// A row of `data/block_data.csv`, what the game knows about a block type.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockData {
+    pub id: u16,
+    pub name: String,
+    pub blends: bool,
+    pub check_types: String,
+    pub color: String,
+    /// Frame-important blocks store their frame in the world file.
+    pub framed: bool,
+    pub light: bool,
+    pub merge_with: String,
+    pub placement: String,
+    /// Width and height in tiles of multi-tile objects.
+    pub size: Option<(u16, u16)>,
+    pub solid: bool,
+    pub solid_top: bool,
+    pub special: String,
+    pub stone: bool,
+    pub texture_grid: Option<(u16, u16)>,
+}
+
+impl BlockData {
+    /// Width and height in tiles, 1x1 when the block is not a multi-tile object.
+    pub fn size_or_single(&self) -> (u16, u16) {
+        self.size.unwrap_or((1, 1))
+    }
+}
+
+// block_data.csv is a dataframe with columns:
+// blends,check_types,color,framed,id,light,merge_with,name,placement,size,solid,solid_top,special,stone,texture_grid
+// has empty values for some columns
+// boolean values are stored as "true" or "false", pairs as quoted "x,y"
+pub static BLOCK_DATA: Lazy<HashMap<u16, BlockData>> = Lazy::new(|| {
+    let raw_csv = include_str!("../../data/block_data.csv");
+    let mut map = HashMap::new();
+    for line in raw_csv.lines().skip(1) {
+        let fields = split_csv_line(line);
+        if fields.len() < 15 {
+            continue;
+        }
+        let Ok(id) = fields[4].parse::<u16>() else {
+            continue;
+        };
+        let flag = |i: usize| fields[i] == "true";
+        map.insert(
+            id,
+            BlockData {
+                id,
+                name: fields[7].clone(),
+                blends: flag(0),
+                check_types: fields[1].clone(),
+                color: fields[2].clone(),
+                framed: flag(3),
+                light: flag(5),
+                merge_with: fields[6].clone(),
+                placement: fields[8].clone(),
+                size: parse_pair(&fields[9]),
+                solid: flag(10),
+                solid_top: flag(11),
+                special: fields[12].clone(),
+                stone: flag(13),
+                texture_grid: parse_pair(&fields[14]),
+            },
+        );
+    }
+    map
+});
+
+/// A row of `data/tile_frames.csv`: one style of a frame-important block and
+/// the frame of its top-left tile.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TileFrame {
+    pub id: u16,
+    pub name: String,
+    pub variety: String,
+    pub u: u16,
+    pub v: u16,
+    /// What the style attaches to: `Bottom`, `Top`, `Left`, `Right`, ... or empty.
+    pub anchor: String,
+}
 
-// // block_data.csv is a dataframe with columns:
-// // blends,check_types,color,framed,id,light,merge_with,name,placement,size,solid,solid_top,special,stone,texture_grid
-// // has empty values for some columns
-// // boolean values are stored as "true" or "false"
-// //
-// pub static BLOCK_DATA: Lazy<HashMap<u16, BlockData>> = Lazy::new(|| {
-//     let raw_csv = include_str!("../../data/block_data.csv");
-//     let mut map = HashMap::new();
-//     for line in raw_csv.lines().skip(1) {
-//         let mut parts = line.split(',');
-//         if let (Some(id), Some(name)) = (parts.next(), parts.next()) {
-//             if let Ok(id) = id.parse::<u16>() {
-//                 let block_data = BlockData {
-//                     blends: parts.next().unwrap_or_default().to_string(),
-//                     check_types: parts.next().unwrap_or_default().to_string(),
-//                     color: parts.next().unwrap_or_default().to_string(),
-//                     framed: parts.next().unwrap_or_default().to_string(),
-//                     id,
-//                     light: parts.next().unwrap_or_default().to_string(),
-//                     merge_with: parts.next().unwrap_or_default().to_string(),
-//                     name: name.to_string(),
-//                     placement: parts.next().unwrap_or_default().to_string(),
-//                     size: parts.next().unwrap_or_default().to_string(),
-//                     solid: parts.next().unwrap_or_default().to_string(),
-//                     solid_top: parts.next().unwrap_or_default().to_string(),
-//                     special: parts.next().unwrap_or_default().to_string(),
-//                     stone: parts.next().unwrap_or_default().to_string(),
-//                     texture_grid: parts.next().unwrap_or_default().to_string(),
-//                 };
-//                 map.insert(id, block_data);
-//             }
-//         }
-//     }
-//     map
-// });
+// tile_frames.csv columns: id,name,variety,u,v,anchor
+// styles of the same block are listed in order
+pub static TILE_FRAMES: Lazy<Vec<TileFrame>> = Lazy::new(|| {
+    let raw_csv = include_str!("../../data/tile_frames.csv");
+    let mut frames = Vec::new();
+    for line in raw_csv.lines().skip(1) {
+        let fields = split_csv_line(line);
+        if fields.len() < 6 {
+            continue;
+        }
+        if let (Ok(id), Ok(u), Ok(v)) = (
+            fields[0].parse::<u16>(),
+            fields[3].parse::<u16>(),
+            fields[4].parse::<u16>(),
+        ) {
+            frames.push(TileFrame {
+                id,
+                name: fields[1].clone(),
+                variety: fields[2].clone(),
+                u,
+                v,
+                anchor: fields[5].clone(),
+            });
+        }
+    }
+    frames
+});
+
+// Splits a CSV line on commas outside of quotes, `""` inside quotes is a quote
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                fields.last_mut().unwrap().push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(String::new()),
+            _ => fields.last_mut().unwrap().push(c),
+        }
+    }
+    fields
+}
+
+fn parse_pair(field: &str) -> Option<(u16, u16)> {
+    let (x, y) = field.split_once(',')?;
+    Some((x.trim().parse().ok()?, y.trim().parse().ok()?))
+}
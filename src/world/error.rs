@@ -1,3 +1,6 @@
+use super::patch::PatchConflict;
+use super::region::Rect;
+
 // Custom error for invalid footer
 #[derive(Debug)]
 pub struct InvalidFooterError(pub String);
@@ -9,3 +12,226 @@ impl std::fmt::Display for InvalidFooterError {
 }
 
 impl std::error::Error for InvalidFooterError {}
+
+// Why an object could not be placed
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlacementError {
+    UnknownObject(String),
+    UnknownStyle { object: String, style: usize },
+    OutOfBounds { x: i32, y: i32 },
+    Occupied { x: i32, y: i32 },
+    NoAnchor { object: String, needs: String },
+    LimitReached { object: String, max: usize },
+    InvalidText(SignTextError),
+}
+
+impl std::fmt::Display for PlacementError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlacementError::UnknownObject(object) => {
+                write!(f, "{object} is not a multi-tile object")
+            }
+            PlacementError::UnknownStyle { object, style } => {
+                write!(f, "{object} has no style {style}")
+            }
+            PlacementError::OutOfBounds { x, y } => {
+                write!(f, "({x}, {y}) is outside the world")
+            }
+            PlacementError::Occupied { x, y } => {
+                write!(f, "there is already a block at ({x}, {y})")
+            }
+            PlacementError::NoAnchor { object, needs } => {
+                write!(f, "{object} needs {needs} to hold it")
+            }
+            PlacementError::LimitReached { object, max } => {
+                write!(f, "the world already has the maximum of {max} {object}")
+            }
+            PlacementError::InvalidText(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for PlacementError {}
+
+// Why a region could not be filled
+#[derive(Debug, Clone, PartialEq)]
+pub enum FillError {
+    UnknownBlock(String),
+    UnknownWall(String),
+    FramedBlock(String),
+}
+
+impl std::fmt::Display for FillError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FillError::UnknownBlock(block) => write!(f, "{block} is not a block name"),
+            FillError::UnknownWall(wall) => write!(f, "{wall} is not a wall name"),
+            FillError::FramedBlock(block) => {
+                write!(f, "{block} needs a frame, place it as an object instead")
+            }
+        }
+    }
+}
+
+impl std::error::Error for FillError {}
+
+// Why a schematic could not be pasted
+#[derive(Debug, Clone, PartialEq)]
+pub enum PasteError {
+    ColumnCount {
+        expected: usize,
+        found: usize,
+    },
+    ColumnLength {
+        column: usize,
+        expected: usize,
+        found: usize,
+    },
+    LimitReached {
+        object: String,
+        max: usize,
+    },
+}
+
+impl std::fmt::Display for PasteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PasteError::ColumnCount { expected, found } => write!(
+                f,
+                "schematic is {expected} tiles wide but has {found} columns"
+            ),
+            PasteError::ColumnLength {
+                column,
+                expected,
+                found,
+            } => write!(
+                f,
+                "schematic is {expected} tiles high but column {column} has {found} tiles"
+            ),
+            PasteError::LimitReached { object, max } => {
+                write!(f, "the paste would go over the maximum of {max} {object}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PasteError {}
+
+// Why a region could not be flipped or rotated
+#[derive(Debug, Clone, PartialEq)]
+pub enum TransformError {
+    OutsideWorld,
+    SplitsObject(Rect),
+}
+
+impl std::fmt::Display for TransformError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TransformError::OutsideWorld => write!(f, "the region is outside the world"),
+            TransformError::SplitsObject(object) => write!(
+                f,
+                "the object at ({}, {}) is only partly inside the region",
+                object.x, object.y
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TransformError {}
+
+// Why a world could not be resized
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResizeError {
+    InvalidSize { width: usize, height: usize },
+}
+
+impl std::fmt::Display for ResizeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResizeError::InvalidSize { width, height } => {
+                write!(f, "a world cannot be {width}x{height} tiles")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ResizeError {}
+
+// Why an edit session could not record an edit
+#[derive(Debug, Clone, PartialEq)]
+pub enum EditError {
+    Resized,
+    Unrecorded,
+}
+
+impl std::fmt::Display for EditError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EditError::Resized => write!(f, "the edit resized the world"),
+            EditError::Unrecorded => write!(
+                f,
+                "the edit added or removed list items without the editing methods"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for EditError {}
+
+// Why a text does not fit on a sign
+#[derive(Debug, Clone, PartialEq)]
+pub enum SignTextError {
+    TooLong { length: usize, max: usize },
+    TooManyLines { lines: usize, max: usize },
+}
+
+impl std::fmt::Display for SignTextError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SignTextError::TooLong { length, max } => {
+                write!(
+                    f,
+                    "sign text is {length} characters long, the limit is {max}"
+                )
+            }
+            SignTextError::TooManyLines { lines, max } => {
+                write!(f, "sign text has {lines} lines, the limit is {max}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SignTextError {}
+
+// Why a patch could not be applied
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatchError {
+    UnsupportedVersion(u32),
+    SizeMismatch {
+        expected: (usize, usize),
+        found: (usize, usize),
+    },
+    Conflicts(Vec<PatchConflict>),
+}
+
+impl std::fmt::Display for PatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PatchError::UnsupportedVersion(version) => {
+                write!(f, "patch format version {version} is not supported")
+            }
+            PatchError::SizeMismatch { expected, found } => write!(
+                f,
+                "patch is for a {}x{} world, this one is {}x{}",
+                expected.0, expected.1, found.0, found.1
+            ),
+            PatchError::Conflicts(conflicts) => {
+                write!(f, "patch conflicts with the world: ")?;
+                let conflicts: Vec<String> = conflicts.iter().map(ToString::to_string).collect();
+                write!(f, "{}", conflicts.join(", "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for PatchError {}
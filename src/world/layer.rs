@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+
+use super::World;
+
+// Part of the surface level above which space starts
+const SPACE_FRACTION: f64 = 0.35;
+
+// Rows above the bottom of the world that are the underworld
+const UNDERWORLD_HEIGHT: i32 = 200;
+
+// The compass and depth meter count two feet per tile
+const FEET_PER_TILE: i32 = 2;
+
+/// Depth layers of a world, from the top down, see [`World::layer_at`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Layer {
+    Space,
+    Surface,
+    Underground,
+    Cavern,
+    Underworld,
+}
+
+impl std::fmt::Display for Layer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Layer::Space => write!(f, "Space"),
+            Layer::Surface => write!(f, "Surface"),
+            Layer::Underground => write!(f, "Underground"),
+            Layer::Cavern => write!(f, "Cavern"),
+            Layer::Underworld => write!(f, "Underworld"),
+        }
+    }
+}
+
+impl World {
+    /// The layer row `y` is in.
+    ///
+    /// Space is the top 35% of the sky, the surface reaches down to
+    /// `underground_level` and the underground to `cavern_level`. The last
+    /// 200 rows are the underworld.
+    pub fn layer_at(&self, y: i32) -> Layer {
+        let depth = y as f64;
+        if depth < self.underground_level * SPACE_FRACTION {
+            Layer::Space
+        } else if depth <= self.underground_level {
+            Layer::Surface
+        } else if depth <= self.cavern_level {
+            Layer::Underground
+        } else if y < self.tiles.size.1 as i32 - UNDERWORLD_HEIGHT {
+            Layer::Cavern
+        } else {
+            Layer::Underworld
+        }
+    }
+
+    /// What a compass shows at column `x`: feet east of the middle of the
+    /// world, negative to the west. The game measures from the middle of the
+    /// world, not from the spawn point.
+    pub fn compass_reading(&self, x: i32) -> i32 {
+        x * FEET_PER_TILE - self.tiles.size.0 as i32
+    }
+
+    /// What a depth meter shows at row `y`: feet below the surface level,
+    /// negative above it.
+    pub fn depth_reading(&self, y: i32) -> i32 {
+        (y as f64 * FEET_PER_TILE as f64 - self.underground_level * FEET_PER_TILE as f64) as i32
+    }
+
+    /// The tile a compass and a depth meter reading point to, the first of
+    /// the two tiles each foot reading covers. Readings west or above are negative.
+    pub fn tile_at_reading(&self, compass: i32, depth: i32) -> (i32, i32) {
+        let x = (compass + self.tiles.size.0 as i32).div_euclid(FEET_PER_TILE);
+        let surface = (self.underground_level * FEET_PER_TILE as f64) as i32;
+        let y = (depth + surface).div_euclid(FEET_PER_TILE);
+        (x, y)
+    }
+}
+
+/// Top-left pixel of tile (`x`, `y`), the units of `NPC::position_x`,
+/// `Mob::position_x` and `bounds_vec`.
+pub fn tile_to_pixels(x: i32, y: i32) -> (f32, f32) {
+    (x as f32 * 16.0, y as f32 * 16.0)
+}
+
+/// The tile pixel (`x`, `y`) is in.
+pub fn pixels_to_tile(x: f32, y: f32) -> (i32, i32) {
+    ((x / 16.0).floor() as i32, (y / 16.0).floor() as i32)
+}
@@ -0,0 +1,309 @@
+use std::collections::HashMap;
+
+use once_cell::sync::Lazy;
+
+use super::block_ids;
+use super::entity_index::RemovedEntities;
+use super::enums::{BlockData, TileFrame, BLOCK_DATA, BLOCK_TYPE_NAMES, TILE_FRAMES};
+use super::error::PlacementError;
+use super::region::Rect;
+use super::tile::{FrameImportantData, Tile};
+use super::World;
+
+// Top-left frames of the styles of every block in tile_frames.csv
+static STYLE_ORIGINS: Lazy<HashMap<u16, Vec<(u16, u16)>>> = Lazy::new(|| {
+    let mut origins: HashMap<u16, Vec<(u16, u16)>> = HashMap::new();
+    for frame in TILE_FRAMES.iter() {
+        origins
+            .entry(frame.id)
+            .or_default()
+            .push((frame.u, frame.v));
+    }
+    origins
+});
+
+// Sizes of objects block_data.csv has no size for
+const MISSING_SIZES: [(u16, (u16, u16)); 7] = [
+    (block_ids::ANNOUNCEMENT_BOX, (2, 2)),
+    (block_ids::CONTAINERS2, (2, 2)),
+    (block_ids::DISPLAY_DOLL, (2, 3)),
+    (block_ids::WEAPON_RACK_2, (3, 3)),
+    (block_ids::HAT_RACK, (3, 4)),
+    (block_ids::TATTERED_WOOD_SIGN, (2, 2)),
+    (block_ids::TELEPORTATION_PYLON, (3, 4)),
+];
+
+// Something an object can hang from or stand on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Support {
+    Floor,
+    Ceiling,
+    FloorAndCeiling,
+    Wall,
+    LeftSide,
+    RightSide,
+}
+
+impl Support {
+    fn describe(&self) -> &'static str {
+        match self {
+            Support::Floor => "a floor",
+            Support::Ceiling => "a ceiling",
+            Support::FloorAndCeiling => "a floor and a ceiling",
+            Support::Wall => "a wall behind it",
+            Support::LeftSide => "a block to its left",
+            Support::RightSide => "a block to its right",
+        }
+    }
+}
+
+/// The styles `object` (a block name like `"CONTAINERS"`) can be placed in,
+/// in the order [`World::place_object`] numbers them.
+pub fn object_styles(object: &str) -> Vec<&'static TileFrame> {
+    block_id(object).map_or_else(Vec::new, styles_of)
+}
+
+/// Width and height in tiles of `object`, a block name like `"CONTAINERS"`.
+/// `None` for blocks that are not objects.
+pub fn object_size(object: &str) -> Option<(u16, u16)> {
+    block_id(object)
+        .filter(|id| BLOCK_DATA.get(id).is_some_and(|data| data.framed))
+        .map(block_size)
+}
+
+impl World {
+    /// Place a multi-tile object (chest, door, bed, table, ...) with its
+    /// top-left tile at (`x`, `y`), writing the frame of every tile it covers.
+    /// Returns the tiles it now covers.
+    ///
+    /// `object` is a block name like `"CONTAINERS"` and `style` indexes
+    /// [`object_styles`]. The object needs empty tiles to go in and whatever
+    /// the game anchors it to: a floor under a chest, a ceiling above a
+    /// chandelier, both for a door and so on.
+    ///
+    /// Only the tiles are placed. Objects that carry data, like chests and
+    /// signs, also need their entry added to the world.
+    pub fn place_object(
+        &mut self,
+        x: i32,
+        y: i32,
+        object: &str,
+        style: usize,
+    ) -> Result<Rect, PlacementError> {
+        let id = block_id(object)
+            .filter(|id| BLOCK_DATA.get(id).is_some_and(|data| data.framed))
+            .ok_or_else(|| PlacementError::UnknownObject(object.to_string()))?;
+        let styles = styles_of(id);
+        let (u, v, anchor) = match styles.get(style) {
+            Some(frame) => (frame.u, frame.v, frame.anchor.as_str()),
+            // Objects missing from the frame table only come in their first style
+            None if styles.is_empty() && style == 0 => (0, 0, ""),
+            None => {
+                return Err(PlacementError::UnknownStyle {
+                    object: object.to_string(),
+                    style,
+                })
+            }
+        };
+
+        let data = &BLOCK_DATA[&id];
+        let (width, height) = block_size(id);
+        self.place_frames(
+            Rect::new(x, y, width as i32, height as i32),
+            id,
+            (u, v),
+            frame_steps(data),
+            supports(&data.placement, anchor),
+        )
+    }
+
+    // Writes the frames of an object covering `rect` whose top-left frame is
+    // `frame`, after checking for room and that one of `supports` holds it
+    pub(crate) fn place_frames(
+        &mut self,
+        rect: Rect,
+        id: u16,
+        frame: (u16, u16),
+        steps: (u16, u16),
+        supports: &[Support],
+    ) -> Result<Rect, PlacementError> {
+        let (x, y) = (rect.x, rect.y);
+        if self.clip(rect) != Some(rect) {
+            return Err(PlacementError::OutOfBounds { x, y });
+        }
+        if let Some((bx, by, _)) = self.tiles_in(rect).find(|(_, _, tile)| tile.has_block()) {
+            return Err(PlacementError::Occupied {
+                x: bx as i32,
+                y: by as i32,
+            });
+        }
+        if !supports.is_empty() && !supports.iter().any(|s| self.is_supported(rect, *s)) {
+            let needs: Vec<&str> = supports.iter().map(Support::describe).collect();
+            return Err(PlacementError::NoAnchor {
+                object: BLOCK_TYPE_NAMES.get(&id).unwrap_or(&"object").to_string(),
+                needs: needs.join(" or "),
+            });
+        }
+
+        for (tx, ty, tile) in self.tiles_in_mut(rect) {
+            let (dx, dy) = ((tx as i32 - x) as u16, (ty as i32 - y) as u16);
+            tile.block_id = id;
+            tile.block_active = true;
+            tile.block_shape = 0;
+            tile.block_frame = Some(FrameImportantData::new(
+                frame.0 + dx * steps.0,
+                frame.1 + dy * steps.1,
+            ));
+        }
+        Ok(rect)
+    }
+
+    /// The tiles covered by the multi-tile object that (`x`, `y`) is part of,
+    /// worked out from the tile's frame. `None` when there is no framed block there.
+    pub fn object_at(&self, x: i32, y: i32) -> Option<Rect> {
+        if !self.bounds().contains(x, y) {
+            return None;
+        }
+        let tile = &self.tiles.tiles[x as usize][y as usize];
+        let frame = tile.block_frame.as_ref().filter(|_| tile.has_block())?;
+        let (width, height) = block_size(tile.block_id);
+        let (step_x, step_y) = BLOCK_DATA.get(&tile.block_id).map_or((18, 18), frame_steps);
+        // Styles are not always stacked one object apart, so count from the
+        // top-left frame of the style the tile belongs to
+        let (u, v) = style_origin(tile.block_id, frame.x, frame.y);
+        let column = ((frame.x - u) / step_x) % width;
+        let row = ((frame.y - v) / step_y) % height;
+        Some(Rect::new(
+            x - column as i32,
+            y - row as i32,
+            width as i32,
+            height as i32,
+        ))
+    }
+
+    /// Remove the blocks of the object that (`x`, `y`) is part of, keeping
+    /// walls, liquids and wires, along with its chest, sign, tile entity or
+    /// weighed pressure plate. Returns the tiles it covered.
+    pub fn remove_object(&mut self, x: i32, y: i32) -> Option<Rect> {
+        self.take_object(x, y).map(|(rect, _)| rect)
+    }
+
+    // Like `remove_object`, also handing back the entities it dropped
+    pub(crate) fn take_object(&mut self, x: i32, y: i32) -> Option<(Rect, RemovedEntities)> {
+        let rect = self.object_at(x, y)?;
+        let id = self.tiles.tiles[x as usize][y as usize].block_id;
+        self.touch_lists();
+        for (_, _, tile) in self.tiles_in_mut(rect) {
+            if tile.block_id == id {
+                remove_block(tile);
+            }
+        }
+        self.weighed_pressure_plates
+            .retain(|plate| !rect.contains_position(&plate.position));
+        let removed = self.remove_entities(|position| rect.contains_position(position));
+        Some((rect, removed))
+    }
+
+    fn is_supported(&self, rect: Rect, support: Support) -> bool {
+        let row = |y: i32| (rect.x..rect.right()).all(|x| self.is_solid(x, y));
+        let column = |x: i32| (rect.y..rect.bottom()).all(|y| self.is_solid(x, y));
+        match support {
+            Support::Floor => row(rect.bottom()),
+            Support::Ceiling => row(rect.y - 1),
+            Support::FloorAndCeiling => row(rect.bottom()) && row(rect.y - 1),
+            Support::Wall => self.tiles_in(rect).all(|(_, _, tile)| tile.has_wall()),
+            Support::LeftSide => column(rect.x - 1),
+            Support::RightSide => column(rect.right()),
+        }
+    }
+
+    // Whether the tile at (x, y) can hold up an object, solid tops like tables count
+    fn is_solid(&self, x: i32, y: i32) -> bool {
+        if !self.bounds().contains(x, y) {
+            return false;
+        }
+        let tile = &self.tiles.tiles[x as usize][y as usize];
+        tile.has_block()
+            && BLOCK_DATA
+                .get(&tile.block_id)
+                .is_some_and(|data| data.solid || data.solid_top)
+    }
+}
+
+// Empties the block of `tile`, keeping its wall, liquid and wires
+pub(crate) fn remove_block(tile: &mut Tile) {
+    tile.block_id = u16::MAX;
+    tile.block_active = true;
+    tile.block_shape = 0;
+    tile.block_frame = None;
+    tile.block_paint = None;
+    tile.block_illuminant = false;
+    tile.block_echo = false;
+}
+
+pub(crate) fn block_id(name: &str) -> Option<u16> {
+    BLOCK_TYPE_NAMES
+        .iter()
+        .find(|(&id, &n)| n == name && id != u16::MAX)
+        .map(|(&id, _)| id)
+}
+
+// Width and height in tiles of objects made of `block_id`
+pub(crate) fn block_size(block_id: u16) -> (u16, u16) {
+    if let Some(&(_, size)) = MISSING_SIZES.iter().find(|(id, _)| *id == block_id) {
+        return size;
+    }
+    BLOCK_DATA
+        .get(&block_id)
+        .map_or((1, 1), |data| data.size_or_single())
+}
+
+// Distance between the frames of neighbouring tiles of an object
+fn frame_steps(data: &BlockData) -> (u16, u16) {
+    data.texture_grid.map_or((18, 18), |(w, h)| (w + 2, h + 2))
+}
+
+fn styles_of(id: u16) -> Vec<&'static TileFrame> {
+    TILE_FRAMES.iter().filter(|frame| frame.id == id).collect()
+}
+
+// Top-left frame of the style a tile with frame (`x`, `y`) belongs to: the
+// lowest style row not below it, then the rightmost style in that row not
+// right of it. (0, 0) for blocks without styles.
+fn style_origin(id: u16, x: u16, y: u16) -> (u16, u16) {
+    let Some(origins) = STYLE_ORIGINS.get(&id) else {
+        return (0, 0);
+    };
+    let v = origins
+        .iter()
+        .map(|&(_, v)| v)
+        .filter(|&v| v <= y)
+        .max()
+        .unwrap_or(0);
+    let u = origins
+        .iter()
+        .filter(|&&(u, other)| other == v && u <= x)
+        .map(|&(u, _)| u)
+        .max()
+        .unwrap_or(0);
+    (u, v)
+}
+
+// Any one of the returned supports is enough. The block's placement rule
+// wins over the anchor of the style, which is all some objects have.
+fn supports(placement: &str, anchor: &str) -> &'static [Support] {
+    match (placement, anchor) {
+        ("floor" | "surface" | "floorSurface", _) => &[Support::Floor],
+        ("ceiling", _) => &[Support::Ceiling],
+        ("CFBoth", _) => &[Support::FloorAndCeiling],
+        ("wall", _) => &[Support::Wall],
+        ("wallFloor", _) => &[Support::Wall, Support::Floor],
+        ("wallFloorCeiling", _) => &[Support::Wall, Support::Floor, Support::Ceiling],
+        (_, "Bottom") => &[Support::Floor],
+        (_, "Top") => &[Support::Ceiling],
+        (_, "Left") => &[Support::LeftSide],
+        (_, "Right") => &[Support::RightSide],
+        (_, "Door Left" | "Door Right") => &[Support::FloorAndCeiling],
+        _ => &[],
+    }
+}
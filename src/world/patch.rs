@@ -0,0 +1,339 @@
+use serde::{Deserialize, Serialize};
+
+use super::chest::Chest;
+use super::coordinates::Coordinates;
+use super::diff::{ChestChange, SignChange, TileEntityChange};
+use super::error::PatchError;
+use super::item::ItemStack;
+use super::region::Rect;
+use super::tile::Tile;
+use super::tile_entity::TileEntity;
+use super::World;
+
+/// Version of the patch format, bumped whenever the serialized layout changes.
+pub const PATCH_FORMAT_VERSION: u32 = 1;
+
+/// Map changes that can be shipped as a file and applied to other copies of
+/// a world, see [`World::apply_patch`].
+///
+/// Only what makes up the map is carried: tiles, chests, signs and tile
+/// entities. NPCs and header values like boss kills are progress and are
+/// left alone.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorldPatch {
+    pub version: u32,
+    /// Tile matrix size of the world the patch was made for.
+    pub size: (usize, usize),
+    pub tiles: Vec<TilePatch>,
+    pub chests: Vec<ChestChange>,
+    pub signs: Vec<SignChange>,
+    pub tile_entities: Vec<TileEntityChange>,
+}
+
+/// The tiles of `rect` before and after the change, column by column.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TilePatch {
+    pub rect: Rect,
+    pub before: Vec<Tile>,
+    pub after: Vec<Tile>,
+}
+
+/// Something in the target world that is neither in the state the patch
+/// expects nor already patched.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PatchConflict {
+    Tiles { rect: Rect, conflicting: usize },
+    Chest { position: Coordinates },
+    Sign { position: Coordinates },
+    TileEntity { position: Coordinates },
+}
+
+impl std::fmt::Display for PatchConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PatchConflict::Tiles { rect, conflicting } => {
+                write!(f, "{conflicting} tiles of {rect} were changed")
+            }
+            PatchConflict::Chest { position } => {
+                write!(f, "chest at ({}, {}) was changed", position.x, position.y)
+            }
+            PatchConflict::Sign { position } => {
+                write!(f, "sign at ({}, {}) was changed", position.x, position.y)
+            }
+            PatchConflict::TileEntity { position } => write!(
+                f,
+                "tile entity at ({}, {}) was changed",
+                position.x, position.y
+            ),
+        }
+    }
+}
+
+impl WorldPatch {
+    /// The patch turning the map of `before` into the map of `after`.
+    /// Only the tiles both worlds have are compared.
+    pub fn between(before: &World, after: &World) -> Self {
+        let diff = before.diff(after);
+        let tiles = diff
+            .tiles
+            .iter()
+            .map(|&rect| TilePatch {
+                rect,
+                before: before.tiles_in(rect).map(|(_, _, t)| t.clone()).collect(),
+                after: after.tiles_in(rect).map(|(_, _, t)| t.clone()).collect(),
+            })
+            .collect();
+        Self {
+            version: PATCH_FORMAT_VERSION,
+            size: before.tiles.size,
+            tiles,
+            chests: diff.chests,
+            signs: diff.signs,
+            tile_entities: diff.tile_entities,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+            && self.chests.is_empty()
+            && self.signs.is_empty()
+            && self.tile_entities.is_empty()
+    }
+
+    pub fn read_from_json(path: &str) -> std::io::Result<Self> {
+        let file = std::fs::File::open(path)?;
+        let reader = std::io::BufReader::new(file);
+        let patch = serde_json::from_reader(reader)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        Ok(patch)
+    }
+
+    pub fn save_as_json(&self, path: &str) -> std::io::Result<()> {
+        let file = std::fs::File::create(path)?;
+        let writer = std::io::BufWriter::new(file);
+        serde_json::to_writer(writer, self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+}
+
+impl World {
+    /// Apply `patch`, made with [`WorldPatch::between`] from another copy of
+    /// this world.
+    ///
+    /// Every tile, chest, sign and tile entity the patch touches must either
+    /// be as it was when the patch was made or already patched, otherwise
+    /// nothing is changed and the conflicts are returned. Applying a patch
+    /// twice is harmless.
+    pub fn apply_patch(&mut self, patch: &WorldPatch) -> Result<(), PatchError> {
+        if patch.version != PATCH_FORMAT_VERSION {
+            return Err(PatchError::UnsupportedVersion(patch.version));
+        }
+        if patch.size != self.tiles.size {
+            return Err(PatchError::SizeMismatch {
+                expected: patch.size,
+                found: self.tiles.size,
+            });
+        }
+        let conflicts = self.patch_conflicts(patch);
+        if !conflicts.is_empty() {
+            return Err(PatchError::Conflicts(conflicts));
+        }
+        self.touch_lists();
+
+        for tiles in &patch.tiles {
+            for ((_, _, tile), after) in self.tiles_in_mut(tiles.rect).zip(&tiles.after) {
+                *tile = after.clone();
+            }
+        }
+        for change in &patch.chests {
+            match change {
+                ChestChange::Added(chest) => {
+                    self.chests.retain(|c| c.position != chest.position);
+                    self.chests.push(chest.clone());
+                }
+                ChestChange::Removed(chest) => {
+                    self.chests.retain(|c| c.position != chest.position);
+                }
+                ChestChange::Modified {
+                    position,
+                    name,
+                    items,
+                } => {
+                    let Some(chest) = self.chests.iter_mut().find(|c| c.position == *position)
+                    else {
+                        continue;
+                    };
+                    if let Some((_, after)) = name {
+                        chest.name = after.clone();
+                    }
+                    for item in items {
+                        if chest.contents.len() <= item.slot {
+                            chest.contents.resize(item.slot + 1, None);
+                        }
+                        chest.contents[item.slot] = item.after.clone();
+                    }
+                }
+            }
+        }
+        for change in &patch.signs {
+            match change {
+                SignChange::Added(sign) => {
+                    self.signs.retain(|s| s.position != sign.position);
+                    self.signs.push(sign.clone());
+                }
+                SignChange::Removed(sign) => self.signs.retain(|s| s.position != sign.position),
+                SignChange::TextChanged {
+                    position, after, ..
+                } => {
+                    for sign in self.signs.iter_mut().filter(|s| s.position == *position) {
+                        sign.text = after.clone();
+                    }
+                }
+            }
+        }
+        for change in &patch.tile_entities {
+            match change {
+                TileEntityChange::Added(entity) => {
+                    if self.tile_entities.iter().any(|e| same_entity(e, entity)) {
+                        continue;
+                    }
+                    // ids are per world, keep the patch's one only when it is free
+                    let id = if self.tile_entities.iter().any(|e| e.id == entity.id) {
+                        self.next_tile_entity_id()
+                    } else {
+                        entity.id
+                    };
+                    self.tile_entities.retain(|e| e.position != entity.position);
+                    self.tile_entities.push(TileEntity {
+                        id,
+                        ..entity.clone()
+                    });
+                }
+                TileEntityChange::Removed(entity) => {
+                    self.tile_entities.retain(|e| e.position != entity.position);
+                }
+                TileEntityChange::Modified { after, .. } => {
+                    for entity in self
+                        .tile_entities
+                        .iter_mut()
+                        .filter(|e| e.position == after.position)
+                    {
+                        entity.extra = after.extra.clone();
+                    }
+                }
+            }
+        }
+        self.reindex_entities();
+        Ok(())
+    }
+
+    fn patch_conflicts(&self, patch: &WorldPatch) -> Vec<PatchConflict> {
+        let mut conflicts = Vec::new();
+        for tiles in &patch.tiles {
+            let conflicting = self
+                .tiles_in(tiles.rect)
+                .zip(tiles.before.iter().zip(&tiles.after))
+                .filter(|((_, _, tile), (before, after))| tile != before && tile != after)
+                .count();
+            if conflicting > 0 || tiles.before.len() != tiles.rect.area() {
+                conflicts.push(PatchConflict::Tiles {
+                    rect: tiles.rect,
+                    conflicting,
+                });
+            }
+        }
+
+        let chest_at =
+            |position: &Coordinates| self.chests.iter().find(|c| c.position == *position);
+        for change in &patch.chests {
+            let fits = match change {
+                ChestChange::Added(chest) => chest_at(&chest.position).is_none_or(|c| c == chest),
+                ChestChange::Removed(chest) => chest_at(&chest.position).is_none_or(|c| c == chest),
+                ChestChange::Modified {
+                    position,
+                    name,
+                    items,
+                } => chest_at(position).is_some_and(|chest| {
+                    let name_fits = name.as_ref().is_none_or(|(before, after)| {
+                        chest.name == *before || chest.name == *after
+                    });
+                    name_fits
+                        && items.iter().all(|item| {
+                            let slot = item_in(chest, item.slot);
+                            slot == item.before || slot == item.after
+                        })
+                }),
+            };
+            if !fits {
+                conflicts.push(PatchConflict::Chest {
+                    position: chest_position(change).clone(),
+                });
+            }
+        }
+
+        let sign_at = |position: &Coordinates| self.signs.iter().find(|s| s.position == *position);
+        for change in &patch.signs {
+            let (position, fits) = match change {
+                SignChange::Added(sign) => (
+                    &sign.position,
+                    sign_at(&sign.position).is_none_or(|s| s.text == sign.text),
+                ),
+                SignChange::Removed(sign) => (
+                    &sign.position,
+                    sign_at(&sign.position).is_none_or(|s| s.text == sign.text),
+                ),
+                SignChange::TextChanged {
+                    position,
+                    before,
+                    after,
+                } => (
+                    position,
+                    sign_at(position).is_some_and(|s| s.text == *before || s.text == *after),
+                ),
+            };
+            if !fits {
+                conflicts.push(PatchConflict::Sign {
+                    position: position.clone(),
+                });
+            }
+        }
+
+        let entity_at =
+            |position: &Coordinates| self.tile_entities.iter().find(|e| e.position == *position);
+        for change in &patch.tile_entities {
+            let (position, fits) = match change {
+                TileEntityChange::Added(entity) | TileEntityChange::Removed(entity) => (
+                    &entity.position,
+                    entity_at(&entity.position).is_none_or(|e| same_entity(e, entity)),
+                ),
+                TileEntityChange::Modified { before, after } => (
+                    &before.position,
+                    entity_at(&before.position)
+                        .is_some_and(|e| same_entity(e, before) || same_entity(e, after)),
+                ),
+            };
+            if !fits {
+                conflicts.push(PatchConflict::TileEntity {
+                    position: position.clone(),
+                });
+            }
+        }
+        conflicts
+    }
+}
+
+fn chest_position(change: &ChestChange) -> &Coordinates {
+    match change {
+        ChestChange::Added(chest) | ChestChange::Removed(chest) => &chest.position,
+        ChestChange::Modified { position, .. } => position,
+    }
+}
+
+fn item_in(chest: &Chest, slot: usize) -> Option<ItemStack> {
+    chest.contents.get(slot).cloned().flatten()
+}
+
+// Tile entities of different copies of a world may have different ids
+fn same_entity(a: &TileEntity, b: &TileEntity) -> bool {
+    a.position == b.position && a.extra == b.extra
+}
@@ -0,0 +1,192 @@
+use std::ops::RangeBounds;
+
+use super::enums::LiquidType;
+use super::region::Rect;
+use super::tile::Tile;
+use super::World;
+
+type Predicate<'w> = Box<dyn Fn(&Tile) -> bool + 'w>;
+
+/// Finds the tiles matching every condition added to it, see [`World::query`].
+///
+/// Conditions on where a tile is (`in_rect`, `above_y`, `below_y`) shrink
+/// the area searched, so they make queries faster as well.
+pub struct TileQuery<'w> {
+    world: &'w World,
+    area: Option<Rect>,
+    predicates: Vec<Predicate<'w>>,
+}
+
+impl World {
+    /// Start a query over every tile of the world.
+    ///
+    /// ```ignore
+    /// let hellstone_in_lava = world
+    ///     .query()
+    ///     .block("HELLSTONE")
+    ///     .below_y(world.cavern_level as i32)
+    ///     .with_liquid(LiquidType::Lava)
+    ///     .count();
+    /// ```
+    pub fn query(&self) -> TileQuery<'_> {
+        TileQuery {
+            world: self,
+            area: Some(self.bounds()),
+            predicates: Vec::new(),
+        }
+    }
+}
+
+impl<'w> TileQuery<'w> {
+    /// Only tiles inside `rect`.
+    pub fn in_rect(mut self, rect: Rect) -> Self {
+        self.area = self.area.and_then(|area| area.intersect(&rect));
+        self
+    }
+
+    /// Only tiles deeper than row `y`.
+    pub fn below_y(self, y: i32) -> Self {
+        let bounds = self.world.bounds();
+        self.in_rect(Rect::new(0, y + 1, bounds.width, bounds.height - y - 1))
+    }
+
+    /// Only tiles higher up than row `y`.
+    pub fn above_y(self, y: i32) -> Self {
+        let bounds = self.world.bounds();
+        self.in_rect(Rect::new(0, 0, bounds.width, y))
+    }
+
+    /// Only tiles `predicate` returns true for.
+    pub fn filter(mut self, predicate: impl Fn(&Tile) -> bool + 'w) -> Self {
+        self.predicates.push(Box::new(predicate));
+        self
+    }
+
+    /// Tiles with a block named `name`, like `"HELLSTONE"`.
+    ///
+    /// Panics when there is no block with that name, like [`Tile::set_block_name`].
+    pub fn block(self, name: &str) -> Self {
+        let mut tile = Tile::new();
+        tile.set_block_name(name);
+        self.block_id(tile.block_id)
+    }
+
+    pub fn block_id(self, id: u16) -> Self {
+        self.filter(move |tile| tile.block_id == id)
+    }
+
+    /// Tiles with any block, or with none when `has_block` is false.
+    pub fn has_block(self, has_block: bool) -> Self {
+        self.filter(move |tile| tile.has_block() == has_block)
+    }
+
+    /// Tiles with a wall named `name`, panics when there is no such wall.
+    pub fn wall(self, name: &str) -> Self {
+        let mut tile = Tile::new();
+        tile.set_wall_name(name);
+        self.wall_id(tile.wall_id)
+    }
+
+    pub fn wall_id(self, id: u16) -> Self {
+        self.filter(move |tile| tile.wall_id == id)
+    }
+
+    /// Tiles with any wall, or with none when `has_wall` is false.
+    pub fn has_wall(self, has_wall: bool) -> Self {
+        self.filter(move |tile| tile.has_wall() == has_wall)
+    }
+
+    /// Tiles whose block has paint `color`, `None` matching unpainted blocks.
+    pub fn block_paint(self, color: Option<u8>) -> Self {
+        self.filter(move |tile| tile.has_block() && tile.block_paint == color)
+    }
+
+    /// Tiles whose wall has paint `color`, `None` matching unpainted walls.
+    pub fn wall_paint(self, color: Option<u8>) -> Self {
+        self.filter(move |tile| tile.has_wall() && tile.wall_paint == color)
+    }
+
+    /// Tiles whose block has `shape`: 0 full, 1 half block, 2-5 slopes.
+    pub fn shape(self, shape: u8) -> Self {
+        self.filter(move |tile| tile.has_block() && tile.block_shape == shape)
+    }
+
+    /// Tiles whose block has the frame (`u`, `v`).
+    pub fn frame(self, u: u16, v: u16) -> Self {
+        self.filter(move |tile| {
+            tile.has_block()
+                && tile
+                    .block_frame
+                    .as_ref()
+                    .is_some_and(|frame| frame.x == u && frame.y == v)
+        })
+    }
+
+    /// Tiles whose block is part of a multi-tile object or otherwise stores a frame.
+    pub fn framed(self) -> Self {
+        self.filter(|tile| tile.has_block() && tile.block_frame.is_some())
+    }
+
+    pub fn red_wire(self) -> Self {
+        self.filter(|tile| tile.red_wire)
+    }
+
+    pub fn blue_wire(self) -> Self {
+        self.filter(|tile| tile.blue_wire)
+    }
+
+    pub fn green_wire(self) -> Self {
+        self.filter(|tile| tile.green_wire)
+    }
+
+    pub fn yellow_wire(self) -> Self {
+        self.filter(|tile| tile.yellow_wire)
+    }
+
+    pub fn activator_wire(self) -> Self {
+        self.filter(|tile| tile.activator_wire)
+    }
+
+    /// Tiles with a wire of any color.
+    pub fn any_wire(self) -> Self {
+        self.filter(|tile| tile.red_wire || tile.blue_wire || tile.green_wire || tile.yellow_wire)
+    }
+
+    /// Tiles holding some `liquid`. `LiquidType::NoLiquid` matches dry tiles.
+    pub fn with_liquid(self, liquid: LiquidType) -> Self {
+        self.filter(move |tile| match liquid {
+            LiquidType::NoLiquid => !tile.has_liquid(),
+            _ => tile.has_liquid() && tile.liquid_type == liquid,
+        })
+    }
+
+    /// Tiles holding an amount of liquid (0-255) in `amount`, whatever the liquid.
+    pub fn liquid_amount(self, amount: impl RangeBounds<u8> + 'w) -> Self {
+        self.filter(move |tile| {
+            let held = if tile.has_liquid() {
+                tile.liquid_amount
+            } else {
+                0
+            };
+            amount.contains(&held)
+        })
+    }
+
+    /// Coordinates of the matching tiles, column by column.
+    pub fn coordinates(&self) -> Vec<(usize, usize)> {
+        self.matches().map(|(x, y, _)| (x, y)).collect()
+    }
+
+    pub fn count(&self) -> usize {
+        self.matches().count()
+    }
+
+    /// The matching tiles with their coordinates, column by column.
+    pub fn matches(&self) -> impl Iterator<Item = (usize, usize, &'w Tile)> + '_ {
+        let world: &'w World = self.world;
+        self.area
+            .into_iter()
+            .flat_map(move |area| world.tiles_in(area))
+            .filter(|(_, _, tile)| self.predicates.iter().all(|predicate| predicate(tile)))
+    }
+}
@@ -0,0 +1,205 @@
+use serde::{Deserialize, Serialize};
+
+use super::bestiary::Bestiary;
+use super::journey_powers::JourneyPowers;
+use super::sections::Section;
+use super::tile::{Tile, TileMatrix};
+use super::World;
+use crate::reader::ByteReader;
+
+/// A section [`World::recover`] could not decode.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LostSection {
+    pub section: Section,
+    pub reason: String,
+}
+
+impl std::fmt::Display for LostSection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.section, self.reason)
+    }
+}
+
+/// What [`World::recover`] had to give up on.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RecoveryReport {
+    /// Sections that failed to decode and were replaced with empty defaults.
+    pub lost_sections: Vec<LostSection>,
+    /// Tile columns read from the file. Columns after the first unreadable one are left empty.
+    pub tile_columns_recovered: usize,
+    pub tile_columns_total: usize,
+}
+
+impl RecoveryReport {
+    /// `true` when every section was read as is.
+    pub fn is_complete(&self) -> bool {
+        self.lost_sections.is_empty()
+    }
+
+    pub fn is_lost(&self, section: Section) -> bool {
+        self.lost_sections
+            .iter()
+            .any(|lost| lost.section == section)
+    }
+}
+
+impl World {
+    /// Load as much as possible of a damaged world file.
+    ///
+    /// Unlike [`World::from_file`], every section is decoded on its own
+    /// starting from the pointer table, so a truncated or corrupted section
+    /// only loses that section. Lost sections are left empty and listed in the
+    /// returned report. Only the header is required, since nothing else can be
+    /// decoded without it.
+    ///
+    /// Reading stops with an error at the first byte a section is missing,
+    /// nothing panics, so recovery also works with `panic = "abort"`.
+    ///
+    /// The recovered world usually references things that were lost (e.g.
+    /// chests whose tiles are gone), so running [`World::repair`] on it before
+    /// saving is recommended.
+    pub fn recover(path: &str) -> std::io::Result<(Self, RecoveryReport)> {
+        let bytes = std::fs::read(path)?;
+        let mut r = ByteReader::new(&bytes);
+
+        let (mut world, pointer_vector) = Self::read_header(&mut r).map_err(|e| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("World header could not be read: {e}"),
+            )
+        })?;
+
+        let mut report = RecoveryReport {
+            tile_columns_total: world.world_width as usize,
+            ..RecoveryReport::default()
+        };
+        world.recover_tiles(&bytes, &pointer_vector, &mut report);
+
+        for section in &Section::ALL[Section::Chests.index()..] {
+            let section = *section;
+            let start = pointer_vector[section.index()] as usize;
+            // the footer runs up to the end of the file
+            let end = pointer_vector.get(section.index() + 1).map(|&p| p as usize);
+            if start >= bytes.len() {
+                report.lost_sections.push(LostSection {
+                    section,
+                    reason: format!(
+                        "starts at {start}, past the end of the file ({} bytes)",
+                        bytes.len()
+                    ),
+                });
+                continue;
+            }
+
+            let mut r = ByteReader::new(&bytes);
+            r.seek(start);
+            let reason = match world.read_section(section, &mut r) {
+                Err(e) => Some(e.to_string()),
+                Ok(()) => match end {
+                    Some(end) if r.offset() != end => Some(format!(
+                        "decoding ended at {} but the next section starts at {end}",
+                        r.offset()
+                    )),
+                    _ => None,
+                },
+            };
+            if let Some(reason) = reason {
+                world.reset_section(section);
+                report.lost_sections.push(LostSection { section, reason });
+            }
+        }
+        world.reindex_entities();
+
+        Ok((world, report))
+    }
+
+    // Decode tiles column by column and keep every column read before the
+    // first failure, the rest of the world is left empty
+    fn recover_tiles(&mut self, bytes: &[u8], pointer_vector: &[u32], report: &mut RecoveryReport) {
+        let (width, height) = (self.world_width as usize, self.world_height as usize);
+        let start = pointer_vector[Section::Tiles.index()] as usize;
+        let end = pointer_vector[Section::Chests.index()] as usize;
+
+        let mut r = ByteReader::new(bytes);
+        r.seek(start);
+        let mut tiles = TileMatrix::new((0, 0));
+        let mut failure = None;
+        for x in 0..width {
+            match Self::read_tile_column(&mut r, height, &self.tile_frame_important) {
+                Ok(column) if column.len() == height => tiles.add_column(column),
+                Ok(column) => {
+                    failure = Some(format!(
+                        "column {x} has {} tiles instead of {height}",
+                        column.len()
+                    ));
+                    break;
+                }
+                Err(e) => {
+                    failure = Some(format!("column {x}: {e}"));
+                    break;
+                }
+            }
+        }
+        report.tile_columns_recovered = tiles.tiles.len();
+        if failure.is_none() && r.offset() != end {
+            failure = Some(format!(
+                "decoding ended at {} but the next section starts at {end}",
+                r.offset()
+            ));
+        }
+
+        while tiles.tiles.len() < width {
+            tiles.add_column(vec![Tile::new(); height]);
+        }
+        self.tiles = tiles;
+
+        if let Some(reason) = failure {
+            report.lost_sections.push(LostSection {
+                section: Section::Tiles,
+                reason: format!(
+                    "{reason} ({} of {width} columns recovered)",
+                    report.tile_columns_recovered
+                ),
+            });
+        }
+    }
+
+    fn read_section(&mut self, section: Section, r: &mut ByteReader) -> std::io::Result<()> {
+        match section {
+            Section::Chests => self.read_chests_section(r),
+            Section::Signs => self.read_signs_section(r),
+            Section::Npcs => self.read_npcs_section(r),
+            Section::TileEntities => self.read_tile_entities_section(r),
+            Section::PressurePlates => self.read_pressure_plates_section(r),
+            Section::TownManager => self.read_town_manager_section(r),
+            Section::Bestiary => self.read_bestiary_section(r),
+            Section::JourneyPowers => self.read_journey_powers_section(r),
+            Section::Footer => self.read_footer_section(r),
+            Section::Header | Section::Tiles => {
+                unreachable!("the header and tiles are recovered separately")
+            }
+        }
+    }
+
+    fn reset_section(&mut self, section: Section) {
+        match section {
+            Section::Chests => {
+                self.chests_max_items = 40;
+                self.chests = Vec::new();
+            }
+            Section::Signs => self.signs = Vec::new(),
+            Section::Npcs => {
+                self.shimmered_npcs = Vec::new();
+                self.npcs = Vec::new();
+                self.mobs = Vec::new();
+            }
+            Section::TileEntities => self.tile_entities = Vec::new(),
+            Section::PressurePlates => self.weighed_pressure_plates = Vec::new(),
+            Section::TownManager => self.rooms = Vec::new(),
+            Section::Bestiary => self.bestiary = Bestiary::new(Vec::new(), Vec::new(), Vec::new()),
+            Section::JourneyPowers => self.journey_powers = JourneyPowers::new(),
+            // nothing is kept from these
+            Section::Header | Section::Tiles | Section::Footer => {}
+        }
+    }
+}
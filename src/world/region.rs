@@ -0,0 +1,310 @@
+use serde::{Deserialize, Serialize};
+
+use super::coordinates::Coordinates;
+use super::enums::{LiquidType, WALL_TYPE_NAMES};
+use super::error::FillError;
+use super::objects::{block_id, remove_block};
+use super::tile::Tile;
+use super::World;
+
+/// A rectangle of tiles, `x`/`y` being its top-left corner.
+///
+/// Rectangles may stick out of the world, everything that takes one clips it
+/// to the world bounds first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl Rect {
+    pub fn new(x: i32, y: i32, width: i32, height: i32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Rectangle spanning two corners, both included. Sizes past `i32::MAX`
+    /// are cut to it.
+    pub fn from_corners(x1: i32, y1: i32, x2: i32, y2: i32) -> Self {
+        let span = |a: i32, b: i32| a.abs_diff(b).saturating_add(1).min(i32::MAX as u32) as i32;
+        Self::new(x1.min(x2), y1.min(y2), span(x1, x2), span(y1, y2))
+    }
+
+    /// First column to the right of the rectangle, at most `i32::MAX`.
+    pub fn right(&self) -> i32 {
+        self.x.saturating_add(self.width)
+    }
+
+    /// First row below the rectangle, at most `i32::MAX`.
+    pub fn bottom(&self) -> i32 {
+        self.y.saturating_add(self.height)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.width <= 0 || self.height <= 0
+    }
+
+    /// Number of tiles in the rectangle.
+    pub fn area(&self) -> usize {
+        if self.is_empty() {
+            0
+        } else {
+            self.width as usize * self.height as usize
+        }
+    }
+
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.x && x < self.right() && y >= self.y && y < self.bottom()
+    }
+
+    pub fn contains_position(&self, position: &Coordinates) -> bool {
+        self.contains(position.x, position.y)
+    }
+
+    /// The part of both rectangles that overlaps, if any.
+    pub fn intersect(&self, other: &Rect) -> Option<Rect> {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let rect = Rect::new(
+            x,
+            y,
+            self.right().min(other.right()).saturating_sub(x),
+            self.bottom().min(other.bottom()).saturating_sub(y),
+        );
+        (!rect.is_empty()).then_some(rect)
+    }
+}
+
+impl std::fmt::Display for Rect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}x{} at ({}, {})",
+            self.width, self.height, self.x, self.y
+        )
+    }
+}
+
+impl World {
+    /// Rectangle covering the whole world.
+    pub fn bounds(&self) -> Rect {
+        let (width, height) = self.tiles.size;
+        Rect::new(0, 0, width as i32, height as i32)
+    }
+
+    /// `rect` clipped to the world, `None` when nothing of it is inside.
+    pub fn clip(&self, rect: Rect) -> Option<Rect> {
+        rect.intersect(&self.bounds())
+    }
+
+    /// Tiles inside `rect` with their coordinates, column by column.
+    pub fn tiles_in(&self, rect: Rect) -> impl Iterator<Item = (usize, usize, &Tile)> + '_ {
+        let (x0, y0, x1, y1) = self.clipped_range(rect);
+        self.tiles.tiles[x0..x1]
+            .iter()
+            .enumerate()
+            .flat_map(move |(dx, column)| {
+                column[y0..y1]
+                    .iter()
+                    .enumerate()
+                    .map(move |(dy, tile)| (x0 + dx, y0 + dy, tile))
+            })
+    }
+
+    /// Mutable version of [`World::tiles_in`].
+    pub fn tiles_in_mut(
+        &mut self,
+        rect: Rect,
+    ) -> impl Iterator<Item = (usize, usize, &mut Tile)> + '_ {
+        self.touch_tiles(rect);
+        let (x0, y0, x1, y1) = self.clipped_range(rect);
+        self.tiles.tiles[x0..x1]
+            .iter_mut()
+            .enumerate()
+            .flat_map(move |(dx, column)| {
+                column[y0..y1]
+                    .iter_mut()
+                    .enumerate()
+                    .map(move |(dy, tile)| (x0 + dx, y0 + dy, tile))
+            })
+    }
+
+    /// Fill `rect` with solid `block_name` blocks, keeping walls, liquids and
+    /// wires. Returns how many tiles changed.
+    ///
+    /// Objects that are partly inside `rect` are removed whole, like
+    /// [`World::clear`] does. Blocks that need a frame, like chests and signs,
+    /// are an error, place those with [`World::place_object`] instead.
+    pub fn fill(&mut self, rect: Rect, block_name: &str) -> Result<usize, FillError> {
+        let block_id = self.solid_block_id(block_name)?;
+        let Some(clipped) = self.clip(rect) else {
+            return Ok(0);
+        };
+        let changed = self.remove_objects_in(clipped);
+        Ok(changed
+            + self.edit_tiles(clipped, |tile| {
+                tile.block_id = block_id;
+                tile.block_frame = None;
+                tile.block_paint = None;
+                tile.block_active = true;
+                tile.block_shape = 0;
+                tile.block_illuminant = false;
+                tile.block_echo = false;
+            }))
+    }
+
+    /// Turn every `from` block in `rect` into a `to` block. Returns how many tiles changed.
+    ///
+    /// Like [`World::fill`] this is an error when either block needs a frame,
+    /// so objects are never changed.
+    pub fn replace(&mut self, rect: Rect, from: &str, to: &str) -> Result<usize, FillError> {
+        let from = self.solid_block_id(from)?;
+        let to = self.solid_block_id(to)?;
+        Ok(self.edit_tiles(rect, |tile| {
+            if tile.has_block() && tile.block_id == from {
+                tile.block_id = to;
+            }
+        }))
+    }
+
+    /// Reset every tile in `rect` to empty space. Returns how many tiles changed.
+    ///
+    /// Objects that are partly inside `rect` are removed whole, the tiles they
+    /// cover outside of it keep their walls, liquids and wires. The chests,
+    /// signs, tile entities and weighed pressure plates of everything removed
+    /// are dropped too.
+    pub fn clear(&mut self, rect: Rect) -> usize {
+        let Some(clipped) = self.clip(rect) else {
+            return 0;
+        };
+        self.remove_objects_in(clipped) + self.edit_tiles(clipped, |tile| *tile = Tile::new())
+    }
+
+    // Removes every object with a tile inside `clipped` whole, along with the
+    // chests, signs, tile entities and weighed pressure plates inside
+    // `clipped` or any removed object. The tiles inside `clipped` are left to
+    // the caller, returns how many tiles outside of it were emptied.
+    pub(crate) fn remove_objects_in(&mut self, clipped: Rect) -> usize {
+        let objects = self.objects_at(self.tiles_in(clipped).map(|(x, y, _)| (x as i32, y as i32)));
+        self.touch_lists();
+        self.weighed_pressure_plates
+            .retain(|plate| !clipped.contains_position(&plate.position));
+        self.remove_entities(|position| clipped.contains_position(position));
+        self.remove_objects(&objects, clipped)
+    }
+
+    // Every object covering one of `positions`, with its block
+    pub(crate) fn objects_at(
+        &self,
+        positions: impl IntoIterator<Item = (i32, i32)>,
+    ) -> Vec<(Rect, u16)> {
+        let mut objects: Vec<(Rect, u16)> = Vec::new();
+        for (x, y) in positions {
+            if let Some(object) = self.object_at(x, y) {
+                if !objects.iter().any(|&(known, _)| known == object) {
+                    objects.push((object, self.tiles.tiles[x as usize][y as usize].block_id));
+                }
+            }
+        }
+        objects
+    }
+
+    // Removes the blocks of `objects` outside of `keep`, and the chests,
+    // signs, tile entities and weighed pressure plates inside any of them.
+    // Returns how many tiles were emptied.
+    pub(crate) fn remove_objects(&mut self, objects: &[(Rect, u16)], keep: Rect) -> usize {
+        self.touch_lists();
+        let removed = |position: &Coordinates| {
+            objects
+                .iter()
+                .any(|(object, _)| object.contains_position(position))
+        };
+        self.weighed_pressure_plates
+            .retain(|plate| !removed(&plate.position));
+        self.remove_entities(removed);
+
+        let mut changed = 0;
+        for &(object, id) in objects {
+            for (x, y, tile) in self.tiles_in_mut(object) {
+                let kept = keep.contains(x as i32, y as i32);
+                if !kept && tile.has_block() && tile.block_id == id {
+                    remove_block(tile);
+                    changed += 1;
+                }
+            }
+        }
+        changed
+    }
+
+    // Id of the block `name`, which must exist and not need a frame
+    fn solid_block_id(&self, name: &str) -> Result<u16, FillError> {
+        let id = block_id(name).ok_or_else(|| FillError::UnknownBlock(name.to_string()))?;
+        if self.is_frame_important(id) {
+            return Err(FillError::FramedBlock(name.to_string()));
+        }
+        Ok(id)
+    }
+
+    fn is_frame_important(&self, block_id: u16) -> bool {
+        self.tile_frame_important
+            .get(block_id as usize)
+            .copied()
+            .unwrap_or(false)
+    }
+
+    /// Put `wall_name` walls behind every tile in `rect`. Returns how many tiles changed.
+    pub fn fill_wall(&mut self, rect: Rect, wall_name: &str) -> Result<usize, FillError> {
+        let wall_id = WALL_TYPE_NAMES
+            .iter()
+            .find(|(&id, &name)| name == wall_name && id != u16::MAX)
+            .map(|(&id, _)| id)
+            .ok_or_else(|| FillError::UnknownWall(wall_name.to_string()))?;
+        Ok(self.edit_tiles(rect, |tile| tile.wall_id = wall_id))
+    }
+
+    /// Fill `rect` with `amount` (0-255) of `liquid`, `LiquidType::NoLiquid`
+    /// drains it. Returns how many tiles changed.
+    pub fn fill_liquid(&mut self, rect: Rect, liquid: LiquidType, amount: u8) -> usize {
+        let amount = if liquid == LiquidType::NoLiquid {
+            0
+        } else {
+            amount
+        };
+        self.edit_tiles(rect, |tile| {
+            tile.liquid_type = liquid;
+            tile.liquid_amount = amount;
+        })
+    }
+
+    // Applies `edit` to every tile in `rect` and counts the tiles it changed
+    fn edit_tiles(&mut self, rect: Rect, mut edit: impl FnMut(&mut Tile)) -> usize {
+        let mut changed = 0;
+        for (_, _, tile) in self.tiles_in_mut(rect) {
+            let before = tile.clone();
+            edit(tile);
+            if *tile != before {
+                changed += 1;
+            }
+        }
+        changed
+    }
+
+    // Column and row ranges of `rect` inside the tile matrix, empty when it is outside
+    fn clipped_range(&self, rect: Rect) -> (usize, usize, usize, usize) {
+        match self.clip(rect) {
+            Some(r) => (
+                r.x as usize,
+                r.y as usize,
+                r.right() as usize,
+                r.bottom() as usize,
+            ),
+            None => (0, 0, 0, 0),
+        }
+    }
+}
@@ -0,0 +1,432 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use super::coordinates::Coordinates;
+use super::room::Room;
+use super::sections::Section;
+use super::validation::{is_chest_origin, is_sign_origin, BLOCK_TYPE_COUNT, MOB_KILLS_COUNT};
+use super::World;
+
+/// Which fixes [`World::repair`] is allowed to make. Everything is enabled by default.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepairOptions {
+    /// Remove chests, signs and tile entities whose tiles are gone. Chests
+    /// stored at another tile of their chest are moved to its top-left tile.
+    pub remove_orphans: bool,
+    /// Keep only the first chest, sign and tile entity at each position,
+    /// and give tile entities that share an id a fresh one.
+    pub dedupe: bool,
+    /// Pad or truncate chest contents to `chests_max_items` slots.
+    pub resize_chest_contents: bool,
+    /// Move NPCs and their homes back inside the world.
+    pub clamp_npcs: bool,
+    /// Resize `mob_kills` and `tile_frame_important` to what the game expects.
+    pub resize_counters: bool,
+    /// Rebuild `rooms` from the homes of the NPCs that are not homeless.
+    pub rebuild_rooms: bool,
+}
+
+impl Default for RepairOptions {
+    fn default() -> Self {
+        Self {
+            remove_orphans: true,
+            dedupe: true,
+            resize_chest_contents: true,
+            clamp_npcs: true,
+            resize_counters: true,
+            rebuild_rooms: true,
+        }
+    }
+}
+
+/// A single change made by [`World::repair`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RepairAction {
+    RemovedChest {
+        position: Coordinates,
+        name: String,
+    },
+    RemovedDuplicateChest {
+        position: Coordinates,
+    },
+    MovedChest {
+        name: String,
+        from: Coordinates,
+        to: Coordinates,
+    },
+    ResizedChestContents {
+        position: Coordinates,
+        from: usize,
+        to: usize,
+    },
+    RemovedSign {
+        position: Coordinates,
+    },
+    RemovedDuplicateSign {
+        position: Coordinates,
+    },
+    RemovedTileEntity {
+        id: i32,
+        position: Coordinates,
+    },
+    RemovedDuplicateTileEntity {
+        id: i32,
+        position: Coordinates,
+    },
+    ReassignedTileEntityId {
+        position: Coordinates,
+        from: i32,
+        to: i32,
+    },
+    MovedNpc {
+        name: String,
+        from: (f32, f32),
+        to: (f32, f32),
+    },
+    MovedNpcHome {
+        name: String,
+        from: Coordinates,
+        to: Coordinates,
+    },
+    ResizedMobKills {
+        from: usize,
+        to: usize,
+    },
+    ResizedTileFrameImportant {
+        from: usize,
+        to: usize,
+    },
+    RebuiltRooms {
+        removed: usize,
+        added: usize,
+    },
+}
+
+impl std::fmt::Display for RepairAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RepairAction::RemovedChest { position, name } => write!(
+                f,
+                "removed chest {name:?} at ({}, {}) without a chest tile",
+                position.x, position.y
+            ),
+            RepairAction::RemovedDuplicateChest { position } => write!(
+                f,
+                "removed duplicate chest at ({}, {})",
+                position.x, position.y
+            ),
+            RepairAction::MovedChest { name, from, to } => write!(
+                f,
+                "moved chest {name:?} from ({}, {}) to the top-left tile of its chest at ({}, {})",
+                from.x, from.y, to.x, to.y
+            ),
+            RepairAction::ResizedChestContents { position, from, to } => write!(
+                f,
+                "resized chest at ({}, {}) from {from} to {to} item slots",
+                position.x, position.y
+            ),
+            RepairAction::RemovedSign { position } => write!(
+                f,
+                "removed sign at ({}, {}) without a sign tile",
+                position.x, position.y
+            ),
+            RepairAction::RemovedDuplicateSign { position } => write!(
+                f,
+                "removed duplicate sign at ({}, {})",
+                position.x, position.y
+            ),
+            RepairAction::RemovedTileEntity { id, position } => write!(
+                f,
+                "removed tile entity {id} at ({}, {}) without a matching tile",
+                position.x, position.y
+            ),
+            RepairAction::RemovedDuplicateTileEntity { id, position } => write!(
+                f,
+                "removed duplicate tile entity {id} at ({}, {})",
+                position.x, position.y
+            ),
+            RepairAction::ReassignedTileEntityId { position, from, to } => write!(
+                f,
+                "changed the id of tile entity at ({}, {}) from {from} to {to}",
+                position.x, position.y
+            ),
+            RepairAction::MovedNpc { name, from, to } => write!(
+                f,
+                "moved NPC {name} from ({}, {}) to ({}, {})",
+                from.0, from.1, to.0, to.1
+            ),
+            RepairAction::MovedNpcHome { name, from, to } => write!(
+                f,
+                "moved the home of NPC {name} from ({}, {}) to ({}, {})",
+                from.x, from.y, to.x, to.y
+            ),
+            RepairAction::ResizedMobKills { from, to } => {
+                write!(f, "resized mob_kills from {from} to {to} entries")
+            }
+            RepairAction::ResizedTileFrameImportant { from, to } => {
+                write!(
+                    f,
+                    "resized tile_frame_important from {from} to {to} entries"
+                )
+            }
+            RepairAction::RebuiltRooms { removed, added } => {
+                write!(f, "rebuilt rooms: removed {removed}, added {added}")
+            }
+        }
+    }
+}
+
+impl World {
+    /// Fix the inconsistencies reported by [`World::validate`] that can be
+    /// fixed without guessing, and return what was changed.
+    ///
+    /// Mismatched world dimensions are not touched, since there is no way to
+    /// tell whether the header or the tile data is the wrong one.
+    pub fn repair(&mut self, options: &RepairOptions) -> Vec<RepairAction> {
+        self.touch_lists();
+        let mut log = Vec::new();
+        if options.remove_orphans {
+            self.remove_orphans(&mut log);
+        }
+        if options.dedupe {
+            self.dedupe_positions(&mut log);
+        }
+        if options.resize_chest_contents {
+            self.resize_chest_contents(&mut log);
+        }
+        if options.clamp_npcs {
+            self.mark_dirty(Section::Npcs);
+            self.clamp_npcs(&mut log);
+        }
+        if options.resize_counters {
+            self.mark_dirty(Section::Header);
+            self.resize_counters(&mut log);
+        }
+        if options.rebuild_rooms {
+            self.rebuild_rooms(&mut log);
+        }
+        self.reindex_entities();
+        log
+    }
+
+    fn remove_orphans(&mut self, log: &mut Vec<RepairAction>) {
+        let chests = std::mem::take(&mut self.chests);
+        for mut chest in chests {
+            if self.tile_at(&chest.position).is_some_and(is_chest_origin) {
+                self.chests.push(chest);
+                continue;
+            }
+            // a chest stored at another tile of its chest keeps its items
+            let origin = self
+                .object_at(chest.position.x, chest.position.y)
+                .map(|rect| Coordinates {
+                    x: rect.x,
+                    y: rect.y,
+                })
+                .filter(|origin| self.tile_at(origin).is_some_and(is_chest_origin));
+            match origin {
+                Some(origin) => {
+                    log.push(RepairAction::MovedChest {
+                        name: chest.name.clone(),
+                        from: chest.position.clone(),
+                        to: origin.clone(),
+                    });
+                    chest.position = origin;
+                    self.chests.push(chest);
+                }
+                None => log.push(RepairAction::RemovedChest {
+                    position: chest.position,
+                    name: chest.name,
+                }),
+            }
+        }
+
+        let signs = std::mem::take(&mut self.signs);
+        for sign in signs {
+            if self.tile_at(&sign.position).is_some_and(is_sign_origin) {
+                self.signs.push(sign);
+            } else {
+                log.push(RepairAction::RemovedSign {
+                    position: sign.position,
+                });
+            }
+        }
+
+        let tile_entities = std::mem::take(&mut self.tile_entities);
+        for entity in tile_entities {
+            let expected_block = entity.extra.as_ref().map(|extra| extra.block_id());
+            let found_block = self
+                .tile_at(&entity.position)
+                .filter(|tile| tile.has_block())
+                .map(|tile| tile.block_id);
+            if expected_block.is_some() && expected_block == found_block {
+                self.tile_entities.push(entity);
+            } else {
+                log.push(RepairAction::RemovedTileEntity {
+                    id: entity.id,
+                    position: entity.position,
+                });
+            }
+        }
+    }
+
+    fn dedupe_positions(&mut self, log: &mut Vec<RepairAction>) {
+        let mut seen = HashSet::new();
+        self.chests.retain(|chest| {
+            let first = seen.insert(chest.position.clone());
+            if !first {
+                log.push(RepairAction::RemovedDuplicateChest {
+                    position: chest.position.clone(),
+                });
+            }
+            first
+        });
+
+        let mut seen = HashSet::new();
+        self.signs.retain(|sign| {
+            let first = seen.insert(sign.position.clone());
+            if !first {
+                log.push(RepairAction::RemovedDuplicateSign {
+                    position: sign.position.clone(),
+                });
+            }
+            first
+        });
+
+        let mut seen = HashSet::new();
+        self.tile_entities.retain(|entity| {
+            let first = seen.insert(entity.position.clone());
+            if !first {
+                log.push(RepairAction::RemovedDuplicateTileEntity {
+                    id: entity.id,
+                    position: entity.position.clone(),
+                });
+            }
+            first
+        });
+
+        // The game looks tile entities up by id, so shared ids must go too
+        let mut next_id = self.tile_entities.iter().map(|e| e.id).max().unwrap_or(-1) + 1;
+        let mut seen = HashSet::new();
+        for entity in &mut self.tile_entities {
+            if !seen.insert(entity.id) {
+                log.push(RepairAction::ReassignedTileEntityId {
+                    position: entity.position.clone(),
+                    from: entity.id,
+                    to: next_id,
+                });
+                entity.id = next_id;
+                next_id += 1;
+            }
+        }
+    }
+
+    fn resize_chest_contents(&mut self, log: &mut Vec<RepairAction>) {
+        let slots = self.chests_max_items.max(0) as usize;
+        for chest in &mut self.chests {
+            if chest.contents.len() != slots {
+                log.push(RepairAction::ResizedChestContents {
+                    position: chest.position.clone(),
+                    from: chest.contents.len(),
+                    to: slots,
+                });
+                chest.contents.resize(slots, None);
+            }
+        }
+    }
+
+    fn clamp_npcs(&mut self, log: &mut Vec<RepairAction>) {
+        if self.world_width <= 0 || self.world_height <= 0 {
+            return;
+        }
+        let max_x = (self.world_width * 16 - 1) as f32;
+        let max_y = (self.world_height * 16 - 1) as f32;
+        for npc in &mut self.npcs {
+            let position = (npc.position_x, npc.position_y);
+            // NaN positions end up at 0
+            let clamped = (
+                position.0.max(0.0).min(max_x),
+                position.1.max(0.0).min(max_y),
+            );
+            if clamped != position {
+                npc.position_x = clamped.0;
+                npc.position_y = clamped.1;
+                log.push(RepairAction::MovedNpc {
+                    name: npc.name.clone(),
+                    from: position,
+                    to: clamped,
+                });
+            }
+
+            if npc.is_homeless {
+                continue;
+            }
+            let home = Coordinates {
+                x: npc.home.x.clamp(0, self.world_width - 1),
+                y: npc.home.y.clamp(0, self.world_height - 1),
+            };
+            if home != npc.home {
+                log.push(RepairAction::MovedNpcHome {
+                    name: npc.name.clone(),
+                    from: npc.home.clone(),
+                    to: home.clone(),
+                });
+                npc.home = home;
+            }
+        }
+    }
+
+    fn resize_counters(&mut self, log: &mut Vec<RepairAction>) {
+        // The expected lengths are only known for the version this crate targets
+        if self.version_integer != 279 {
+            return;
+        }
+        if self.mob_kills.len() != MOB_KILLS_COUNT {
+            log.push(RepairAction::ResizedMobKills {
+                from: self.mob_kills.len(),
+                to: MOB_KILLS_COUNT,
+            });
+            self.mob_kills.resize(MOB_KILLS_COUNT, 0);
+        }
+        if self.tile_frame_important.len() != BLOCK_TYPE_COUNT {
+            log.push(RepairAction::ResizedTileFrameImportant {
+                from: self.tile_frame_important.len(),
+                to: BLOCK_TYPE_COUNT,
+            });
+            // Missing entries are taken from the table the game uses
+            let defaults = Self::default_tile_frame_important();
+            let length = self.tile_frame_important.len().min(BLOCK_TYPE_COUNT);
+            self.tile_frame_important.truncate(length);
+            self.tile_frame_important
+                .extend_from_slice(&defaults[length..BLOCK_TYPE_COUNT]);
+        }
+    }
+
+    fn rebuild_rooms(&mut self, log: &mut Vec<RepairAction>) {
+        let mut rooms: Vec<Room> = Vec::new();
+        for npc in &self.npcs {
+            if npc.is_homeless || self.tile_at(&npc.home).is_none() {
+                continue;
+            }
+            // one room per NPC type, like the game's town manager
+            if rooms.iter().all(|room| room.npc != npc.type_) {
+                rooms.push(Room::new(npc.type_, npc.home.clone()));
+            }
+        }
+
+        let same = |a: &Room, b: &Room| a.npc == b.npc && a.position == b.position;
+        let removed = self
+            .rooms
+            .iter()
+            .filter(|old| !rooms.iter().any(|new| same(old, new)))
+            .count();
+        let added = rooms
+            .iter()
+            .filter(|new| !self.rooms.iter().any(|old| same(old, new)))
+            .count();
+        if removed > 0 || added > 0 || rooms.len() != self.rooms.len() {
+            log.push(RepairAction::RebuiltRooms { removed, added });
+        }
+        self.rooms = rooms;
+    }
+}
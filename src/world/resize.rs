@@ -0,0 +1,209 @@
+use serde::{Deserialize, Serialize};
+
+use super::coordinates::Coordinates;
+use super::error::ResizeError;
+use super::layer::pixels_to_tile;
+use super::region::Rect;
+use super::tile::{Tile, TileMatrix};
+use super::{World, MAX_WORLD_HEIGHT, MAX_WORLD_WIDTH};
+
+/// Which part of the world stays in place when it is resized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Anchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl Anchor {
+    // How far the kept part is from the left and top, as halves of the size change
+    fn weights(self) -> (i32, i32) {
+        match self {
+            Anchor::TopLeft => (0, 0),
+            Anchor::Top => (1, 0),
+            Anchor::TopRight => (2, 0),
+            Anchor::Left => (0, 1),
+            Anchor::Center => (1, 1),
+            Anchor::Right => (2, 1),
+            Anchor::BottomLeft => (0, 2),
+            Anchor::Bottom => (1, 2),
+            Anchor::BottomRight => (2, 2),
+        }
+    }
+}
+
+impl World {
+    /// Cut the world down to `rect`, which becomes the whole world.
+    ///
+    /// Everything positional moves along: spawn and dungeon points, the
+    /// underground and cavern levels, chests, signs, tile entities, pressure
+    /// plates, rooms, NPCs and mobs. Whatever ends up outside is dropped, and
+    /// NPCs whose home is gone become homeless. Returns the part of `rect`
+    /// inside the world, `None` when there is none and nothing changed.
+    pub fn crop(&mut self, rect: Rect) -> Option<Rect> {
+        let clipped = self.clip(rect)?;
+        self.reshape(clipped, &Tile::new());
+        Some(clipped)
+    }
+
+    /// Change the size of the world to `new_width` by `new_height` tiles,
+    /// keeping the part of it at `anchor` in place. New tiles are copies of
+    /// `fill`, see [`World::crop`] for what happens to everything else.
+    /// Sizes that are empty or larger than a world file may hold are refused
+    /// and leave the world unchanged.
+    pub fn resize(
+        &mut self,
+        new_width: usize,
+        new_height: usize,
+        anchor: Anchor,
+        fill: &Tile,
+    ) -> Result<(), ResizeError> {
+        if !(1..=MAX_WORLD_WIDTH as usize).contains(&new_width)
+            || !(1..=MAX_WORLD_HEIGHT as usize).contains(&new_height)
+        {
+            return Err(ResizeError::InvalidSize {
+                width: new_width,
+                height: new_height,
+            });
+        }
+        let (width, height) = self.tiles.size;
+        let (wx, wy) = anchor.weights();
+        let source = Rect::new(
+            (width as i32 - new_width as i32) * wx / 2,
+            (height as i32 - new_height as i32) * wy / 2,
+            new_width as i32,
+            new_height as i32,
+        );
+        self.reshape(source, fill);
+        Ok(())
+    }
+
+    // Make `source`, in current tile coordinates, the whole world. Parts of
+    // it outside the current world are filled with `fill`.
+    fn reshape(&mut self, source: Rect, fill: &Tile) {
+        let (width, height) = (source.width.max(0), source.height.max(0));
+        let source = Rect::new(source.x, source.y, width, height);
+        self.touch_lists();
+        let (dx, dy) = (-source.x, -source.y);
+        let moved = |position: &Coordinates| Coordinates {
+            x: position.x + dx,
+            y: position.y + dy,
+        };
+
+        // Objects stay only when all of their tiles do, the others are
+        // removed whole along with their chests, signs and tile entities
+        if let Some(inside) = self.clip(source) {
+            let edges = (inside.x..inside.right())
+                .flat_map(|x| [(x, inside.y), (x, inside.bottom() - 1)])
+                .chain(
+                    (inside.y..inside.bottom())
+                        .flat_map(|y| [(inside.x, y), (inside.right() - 1, y)]),
+                );
+            let cut: Vec<(Rect, u16)> = self
+                .objects_at(edges)
+                .into_iter()
+                .filter(|(object, _)| source.intersect(object) != Some(*object))
+                .collect();
+            self.remove_objects(&cut, Rect::new(0, 0, 0, 0));
+        }
+        self.chests
+            .retain(|chest| source.contains_position(&chest.position));
+        self.signs
+            .retain(|sign| source.contains_position(&sign.position));
+        self.tile_entities
+            .retain(|entity| source.contains_position(&entity.position));
+        self.weighed_pressure_plates
+            .retain(|plate| source.contains_position(&plate.position));
+        self.rooms
+            .retain(|room| source.contains_position(&room.position));
+        for chest in &mut self.chests {
+            chest.position = moved(&chest.position);
+        }
+        for sign in &mut self.signs {
+            sign.position = moved(&sign.position);
+        }
+        for entity in &mut self.tile_entities {
+            entity.position = moved(&entity.position);
+        }
+        for plate in &mut self.weighed_pressure_plates {
+            plate.position = moved(&plate.position);
+        }
+        for room in &mut self.rooms {
+            room.position = moved(&room.position);
+        }
+
+        let mut tiles = TileMatrix::new((0, 0));
+        for x in source.x..source.right() {
+            let column = (source.y..source.bottom())
+                .map(|y| {
+                    if self.bounds().contains(x, y) {
+                        self.tiles.tiles[x as usize][y as usize].clone()
+                    } else {
+                        fill.clone()
+                    }
+                })
+                .collect();
+            tiles.add_column(column);
+        }
+        tiles.size = (width as usize, height as usize);
+        self.tiles = tiles;
+        self.world_width = width;
+        self.world_height = height;
+        self.bounds_vec = vec![0, width * 16, 0, height * 16];
+
+        let clamp_x = |x: i32| x.clamp(0, (width - 1).max(0));
+        let clamp_y = |y: i32| y.clamp(0, (height - 1).max(0));
+        self.spawn_point_x = clamp_x(self.spawn_point_x + dx);
+        self.spawn_point_y = clamp_y(self.spawn_point_y + dy);
+        self.dungeon_point_x = clamp_x(self.dungeon_point_x + dx);
+        self.dungeon_point_y = clamp_y(self.dungeon_point_y + dy);
+        self.underground_level = (self.underground_level + dy as f64).clamp(0.0, height as f64);
+        self.cavern_level = (self.cavern_level + dy as f64).clamp(0.0, height as f64);
+        // tree and moss styles change at these columns
+        for separator in self
+            .environment
+            .tree_style_separators
+            .iter_mut()
+            .chain(&mut self.environment.moss_style_separators)
+        {
+            *separator = (*separator + dx).clamp(0, width);
+        }
+
+        // NPCs and mobs are placed in pixels
+        let bounds = self.bounds();
+        let in_world = |px: f32, py: f32| {
+            let (x, y) = pixels_to_tile(px, py);
+            bounds.contains(x, y)
+        };
+        let (pixels_x, pixels_y) = (dx as f32 * 16.0, dy as f32 * 16.0);
+        let spawn = Coordinates {
+            x: self.spawn_point_x,
+            y: self.spawn_point_y,
+        };
+        self.npcs.retain_mut(|npc| {
+            npc.position_x += pixels_x;
+            npc.position_y += pixels_y;
+            npc.home = moved(&npc.home);
+            if !bounds.contains_position(&npc.home) {
+                npc.home = spawn.clone();
+                npc.is_homeless = true;
+            }
+            in_world(npc.position_x, npc.position_y)
+        });
+        self.mobs.retain_mut(|mob| {
+            mob.position_x += pixels_x;
+            mob.position_y += pixels_y;
+            in_world(mob.position_x, mob.position_y)
+        });
+
+        // the loaded bytes no longer line up with the tiles
+        self.section_cache = None;
+        self.reindex_entities();
+    }
+}
@@ -0,0 +1,303 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::block_ids::*;
+use super::enums::BLOCK_TYPE_NAMES;
+use super::layer::Layer;
+use super::region::Rect;
+use super::World;
+
+// Every ore and gem block the report counts, whichever tiers the world picked
+const RESOURCE_BLOCKS: [u16; 27] = [
+    IRON,
+    COPPER,
+    GOLD,
+    SILVER,
+    DEMONITE,
+    METEORITE,
+    HELLSTONE,
+    SAPPHIRE,
+    RUBY,
+    EMERALD,
+    TOPAZ,
+    AMETHYST,
+    DIAMOND,
+    COBALT,
+    MYTHRIL,
+    ADAMANTITE,
+    TIN,
+    LEAD,
+    TUNGSTEN,
+    PLATINUM,
+    EXPOSED_GEMS,
+    CRIMTANE,
+    CHLOROPHYTE,
+    PALLADIUM,
+    ORICHALCUM,
+    TITANIUM,
+    LUNAR_ORE,
+];
+
+/// How many blocks of one ore or gem a world has, see [`World::resource_report`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResourceCount {
+    pub block_id: u16,
+    pub name: String,
+    pub total: usize,
+    /// Only the layers the block was found in.
+    pub by_layer: BTreeMap<Layer, usize>,
+}
+
+/// One of the ore slots the world generator fills with one of two ores,
+/// like copper or tin for `ore_1`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OreTier {
+    /// Name of the header field, `ore_1` to `ore_4` or `hardmode_ore_1` to `hardmode_ore_3`.
+    pub tier: String,
+    /// `None` while the world has not picked one yet, like hardmode ores
+    /// before the Wall of Flesh.
+    pub block_id: Option<u16>,
+    pub name: String,
+    pub total: usize,
+}
+
+/// Touching blocks of the same ore or gem, sides only like mining them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Deposit {
+    pub block_id: u16,
+    pub name: String,
+    /// Number of blocks.
+    pub size: usize,
+    pub bounds: Rect,
+    /// Average position of the blocks, rounded to a tile.
+    pub center: (i32, i32),
+    /// Tiles from the spawn point to `center`.
+    pub distance_to_spawn: f64,
+}
+
+/// Ores and gems of a world, see [`World::resource_report`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResourceReport {
+    /// Ores and gems the world has, by block id.
+    pub resources: Vec<ResourceCount>,
+    pub tiers: Vec<OreTier>,
+    /// Every deposit, nearest to spawn first.
+    pub deposits: Vec<Deposit>,
+}
+
+impl ResourceReport {
+    /// Number of `block_id` blocks in the world.
+    pub fn total(&self, block_id: u16) -> usize {
+        self.resources
+            .iter()
+            .find(|resource| resource.block_id == block_id)
+            .map_or(0, |resource| resource.total)
+    }
+
+    /// The `n` biggest deposits within `radius` tiles of spawn, biggest first.
+    /// Deposits of the same size are listed nearest first.
+    pub fn richest_deposits(&self, n: usize, radius: f64) -> Vec<&Deposit> {
+        let mut deposits: Vec<&Deposit> = self
+            .deposits
+            .iter()
+            .filter(|deposit| deposit.distance_to_spawn <= radius)
+            .collect();
+        // `deposits` is already nearest first and the sort is stable
+        deposits.sort_by_key(|deposit| std::cmp::Reverse(deposit.size));
+        deposits.truncate(n);
+        deposits
+    }
+
+    pub fn save_as_json(&self, path: &str) -> std::io::Result<()> {
+        let file = std::fs::File::create(path)?;
+        let writer = std::io::BufWriter::new(file);
+        serde_json::to_writer_pretty(writer, self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+}
+
+impl std::fmt::Display for ResourceCount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.name, self.total)?;
+        let layers: Vec<String> = self
+            .by_layer
+            .iter()
+            .map(|(layer, count)| format!("{layer} {count}"))
+            .collect();
+        write!(f, " ({})", layers.join(", "))
+    }
+}
+
+impl std::fmt::Display for OreTier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.block_id {
+            Some(_) => write!(f, "{}: {} ({})", self.tier, self.name, self.total),
+            None => write!(f, "{}: not chosen yet", self.tier),
+        }
+    }
+}
+
+impl std::fmt::Display for Deposit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} x{} at ({}, {}), {:.0} tiles from spawn",
+            self.name, self.size, self.center.0, self.center.1, self.distance_to_spawn
+        )
+    }
+}
+
+impl std::fmt::Display for ResourceReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for tier in &self.tiers {
+            writeln!(f, "{tier}")?;
+        }
+        for resource in &self.resources {
+            writeln!(f, "{resource}")?;
+        }
+        writeln!(f, "{} deposits", self.deposits.len())
+    }
+}
+
+impl World {
+    /// Count every ore and gem block in the world, per layer, and group
+    /// touching blocks of the same kind into deposits.
+    ///
+    /// The tiers list which ores `ore_1` to `ore_4` and `hardmode_ore_1` to
+    /// `hardmode_ore_3` point to. Use [`ResourceReport::richest_deposits`] to
+    /// see what is worth mining near spawn.
+    pub fn resource_report(&self) -> ResourceReport {
+        let (width, height) = self.tiles.size;
+        let mut is_resource = vec![false; u16::MAX as usize + 1];
+        for block in RESOURCE_BLOCKS {
+            is_resource[block as usize] = true;
+        }
+        let tiers = [
+            ("ore_1", self.ore_1),
+            ("ore_2", self.ore_2),
+            ("ore_3", self.ore_3),
+            ("ore_4", self.ore_4),
+            ("hardmode_ore_1", self.hardmode_ore_1),
+            ("hardmode_ore_2", self.hardmode_ore_2),
+            ("hardmode_ore_3", self.hardmode_ore_3),
+        ];
+        // Worlds from mods may pick ores the list does not know about
+        for &(_, id) in &tiers {
+            if let Ok(id) = u16::try_from(id) {
+                is_resource[id as usize] = true;
+            }
+        }
+        let layers: Vec<Layer> = (0..height).map(|y| self.layer_at(y as i32)).collect();
+
+        let mut counts: BTreeMap<u16, BTreeMap<Layer, usize>> = BTreeMap::new();
+        let mut deposits = Vec::new();
+        let mut visited = vec![false; width * height];
+        let mut stack = Vec::new();
+        let (spawn_x, spawn_y) = (self.spawn_point_x as f64, self.spawn_point_y as f64);
+        for x in 0..width {
+            for y in 0..height {
+                let tile = &self.tiles.tiles[x][y];
+                if !tile.has_block() || !is_resource[tile.block_id as usize] {
+                    continue;
+                }
+                *counts
+                    .entry(tile.block_id)
+                    .or_default()
+                    .entry(layers[y])
+                    .or_default() += 1;
+                if visited[x * height + y] {
+                    continue;
+                }
+
+                let block_id = tile.block_id;
+                let (mut size, mut sum_x, mut sum_y) = (0, 0, 0);
+                let (mut min, mut max) = ((x, y), (x, y));
+                visited[x * height + y] = true;
+                stack.push((x, y));
+                while let Some((tx, ty)) = stack.pop() {
+                    size += 1;
+                    sum_x += tx;
+                    sum_y += ty;
+                    min = (min.0.min(tx), min.1.min(ty));
+                    max = (max.0.max(tx), max.1.max(ty));
+                    let neighbours = [
+                        (tx.wrapping_sub(1), ty),
+                        (tx + 1, ty),
+                        (tx, ty.wrapping_sub(1)),
+                        (tx, ty + 1),
+                    ];
+                    for (nx, ny) in neighbours {
+                        if nx >= width || ny >= height || visited[nx * height + ny] {
+                            continue;
+                        }
+                        let next = &self.tiles.tiles[nx][ny];
+                        if next.has_block() && next.block_id == block_id {
+                            visited[nx * height + ny] = true;
+                            stack.push((nx, ny));
+                        }
+                    }
+                }
+
+                let center = (
+                    (sum_x as f64 / size as f64).round() as i32,
+                    (sum_y as f64 / size as f64).round() as i32,
+                );
+                deposits.push(Deposit {
+                    block_id,
+                    name: block_name(block_id),
+                    size,
+                    bounds: Rect::from_corners(
+                        min.0 as i32,
+                        min.1 as i32,
+                        max.0 as i32,
+                        max.1 as i32,
+                    ),
+                    center,
+                    distance_to_spawn: (center.0 as f64 - spawn_x).hypot(center.1 as f64 - spawn_y),
+                });
+            }
+        }
+        deposits.sort_by(|a, b| a.distance_to_spawn.total_cmp(&b.distance_to_spawn));
+
+        let resources: Vec<ResourceCount> = counts
+            .into_iter()
+            .map(|(block_id, by_layer)| ResourceCount {
+                block_id,
+                name: block_name(block_id),
+                total: by_layer.values().sum(),
+                by_layer,
+            })
+            .collect();
+        let tiers = tiers
+            .iter()
+            .map(|&(tier, id)| {
+                let block_id = u16::try_from(id).ok();
+                OreTier {
+                    tier: tier.to_string(),
+                    block_id,
+                    name: block_id.map_or_else(String::new, block_name),
+                    total: block_id.map_or(0, |id| {
+                        resources
+                            .iter()
+                            .find(|resource| resource.block_id == id)
+                            .map_or(0, |resource| resource.total)
+                    }),
+                }
+            })
+            .collect();
+
+        ResourceReport {
+            resources,
+            tiers,
+            deposits,
+        }
+    }
+}
+
+fn block_name(block_id: u16) -> String {
+    BLOCK_TYPE_NAMES
+        .get(&block_id)
+        .copied()
+        .unwrap_or("Unknown Block")
+        .to_string()
+}
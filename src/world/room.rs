@@ -1,7 +1,7 @@
 use super::coordinates::Coordinates;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Room {
     pub npc: i32,
     pub position: Coordinates,
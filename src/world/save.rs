@@ -0,0 +1,163 @@
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Options for [`World::save_as_wld_with_options`](super::World::save_as_wld_with_options).
+///
+/// Saving always goes through a temporary file that is fsynced and then
+/// renamed over the destination, so a crash mid-save never leaves a
+/// half-written world behind.
+#[derive(Debug, Clone, Default)]
+pub struct SaveOptions {
+    /// Copy the previous file to `<path>.bak` before replacing it, like the game does.
+    pub backup: bool,
+    /// Keep this many timestamped copies of previous saves
+    /// (`<path>.<timestamp>.bak`), deleting the oldest ones. 0 disables them.
+    pub timestamped_backups: usize,
+}
+
+impl SaveOptions {
+    /// Options matching the game: a single `.bak` copy of the previous save.
+    pub fn with_backup() -> Self {
+        Self {
+            backup: true,
+            timestamped_backups: 0,
+        }
+    }
+}
+
+pub(crate) fn write_atomically(
+    path: &Path,
+    bytes: &[u8],
+    options: &SaveOptions,
+) -> std::io::Result<()> {
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("Invalid world file path: {}", path.display()),
+            )
+        })?;
+    let directory = parent_directory(path);
+
+    // Write the new world next to the destination so the rename stays on one filesystem
+    let temp_path = directory.join(format!(".{}.{}.tmp", file_name, std::process::id()));
+    let result = write_and_sync(&temp_path, bytes)
+        .and_then(|()| replace_with(path, &temp_path, &directory, file_name, options));
+    if let Err(e) = result {
+        let _ = fs::remove_file(&temp_path);
+        return Err(e);
+    }
+    sync_directory(&directory)
+}
+
+// Back up the file at `path` and rename the temporary file over it
+fn replace_with(
+    path: &Path,
+    temp_path: &Path,
+    directory: &Path,
+    file_name: &str,
+    options: &SaveOptions,
+) -> std::io::Result<()> {
+    if path.exists() {
+        // Keep the permissions of the world being replaced
+        fs::set_permissions(temp_path, fs::metadata(path)?.permissions())?;
+        if options.backup {
+            copy_and_sync(path, &backup_path(path))?;
+        }
+        if options.timestamped_backups > 0 {
+            let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S%.6f");
+            copy_and_sync(
+                path,
+                &directory.join(format!("{file_name}.{timestamp}.bak")),
+            )?;
+            prune_timestamped_backups(directory, file_name, options.timestamped_backups)?;
+        }
+    }
+    fs::rename(temp_path, path)
+}
+
+/// Path of the single backup the game keeps next to a world file.
+pub fn backup_path(path: &Path) -> PathBuf {
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(".bak");
+    PathBuf::from(backup)
+}
+
+/// Timestamped backups of `path`, oldest first.
+pub fn timestamped_backup_paths(path: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("");
+    let directory = parent_directory(path);
+    list_timestamped_backups(&directory, file_name)
+}
+
+fn parent_directory(path: &Path) -> PathBuf {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    }
+}
+
+fn write_and_sync(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let mut file = fs::File::create(path)?;
+    file.write_all(bytes)?;
+    file.sync_all()
+}
+
+fn copy_and_sync(from: &Path, to: &Path) -> std::io::Result<()> {
+    fs::copy(from, to)?;
+    fs::File::open(to)?.sync_all()
+}
+
+fn list_timestamped_backups(directory: &Path, file_name: &str) -> std::io::Result<Vec<PathBuf>> {
+    let prefix = format!("{file_name}.");
+    let mut backups = Vec::new();
+    for entry in fs::read_dir(directory)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let Some(name) = name.to_str() else {
+            continue;
+        };
+        // `<name>.bak` itself is the plain backup, not a timestamped one
+        if let Some(timestamp) = name
+            .strip_prefix(&prefix)
+            .and_then(|rest| rest.strip_suffix(".bak"))
+        {
+            if !timestamp.is_empty() && timestamp.starts_with(|c: char| c.is_ascii_digit()) {
+                backups.push(entry.path());
+            }
+        }
+    }
+    // timestamps sort lexicographically in chronological order
+    backups.sort();
+    Ok(backups)
+}
+
+fn prune_timestamped_backups(
+    directory: &Path,
+    file_name: &str,
+    keep: usize,
+) -> std::io::Result<()> {
+    let backups = list_timestamped_backups(directory, file_name)?;
+    let excess = backups.len().saturating_sub(keep);
+    for old in &backups[..excess] {
+        fs::remove_file(old)?;
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn sync_directory(directory: &Path) -> std::io::Result<()> {
+    // Make the rename itself durable
+    fs::File::open(directory)?.sync_all()
+}
+
+#[cfg(not(unix))]
+fn sync_directory(_directory: &Path) -> std::io::Result<()> {
+    Ok(())
+}
@@ -0,0 +1,400 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use super::chest::Chest;
+use super::coordinates::Coordinates;
+use super::error::PasteError;
+use super::objects::remove_block;
+use super::region::Rect;
+use super::sign::Sign;
+use super::tile::{Tile, TileMatrix};
+use super::tile_entity::TileEntity;
+use super::validation::{MAX_CHESTS, MAX_SIGNS};
+use super::World;
+
+/// A copied piece of a world that can be pasted into any world.
+///
+/// Positions of chests, signs and tile entities are relative to the top-left
+/// corner of the schematic.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Schematic {
+    #[serde(default)]
+    pub name: String,
+    pub tiles: TileMatrix,
+    pub chests: Vec<Chest>,
+    pub signs: Vec<Sign>,
+    pub tile_entities: Vec<TileEntity>,
+}
+
+impl Schematic {
+    pub fn width(&self) -> usize {
+        self.tiles.size.0
+    }
+
+    pub fn height(&self) -> usize {
+        self.tiles.size.1
+    }
+
+    pub fn read_from_json(path: &str) -> std::io::Result<Self> {
+        let file = std::fs::File::open(path)?;
+        let reader = std::io::BufReader::new(file);
+        let schematic = serde_json::from_reader(reader)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        Ok(schematic)
+    }
+
+    pub fn save_as_json(&self, path: &str) -> std::io::Result<()> {
+        let file = std::fs::File::create(path)?;
+        let writer = std::io::BufWriter::new(file);
+        serde_json::to_writer(writer, self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+}
+
+/// How [`World::paste`] combines a schematic with what is already there.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PasteOptions {
+    /// Leave the world untouched where the schematic is empty space.
+    pub skip_air: bool,
+    /// Keep the walls already in the world, only adding walls where there are none.
+    pub keep_walls: bool,
+    /// Replace blocks already in the world. Without it blocks are only pasted
+    /// into empty tiles.
+    pub overwrite: bool,
+}
+
+impl Default for PasteOptions {
+    fn default() -> Self {
+        Self {
+            skip_air: false,
+            keep_walls: false,
+            overwrite: true,
+        }
+    }
+}
+
+impl World {
+    /// Copy the tiles in `rect` along with the chests, signs and tile entities
+    /// placed there. The rectangle is clipped to the world.
+    ///
+    /// Objects that are only partly inside `rect` are left out, so pasting
+    /// the copy never places half an object.
+    pub fn copy(&self, rect: Rect) -> Schematic {
+        let Some(rect) = self.clip(rect) else {
+            return Schematic {
+                name: String::new(),
+                tiles: TileMatrix::new((0, 0)),
+                chests: Vec::new(),
+                signs: Vec::new(),
+                tile_entities: Vec::new(),
+            };
+        };
+
+        // Objects sticking out of the rect are left out, keeping walls,
+        // liquids and wires under them
+        let whole = |position: &Coordinates| {
+            self.object_at(position.x, position.y)
+                .is_none_or(|object| rect.intersect(&object) == Some(object))
+        };
+        let mut tiles = TileMatrix::new((0, 0));
+        for x in rect.x..rect.right() {
+            let column = (rect.y..rect.bottom())
+                .map(|y| {
+                    let mut tile = self.tiles.tiles[x as usize][y as usize].clone();
+                    if !whole(&Coordinates { x, y }) {
+                        remove_block(&mut tile);
+                    }
+                    tile
+                })
+                .collect();
+            tiles.add_column(column);
+        }
+
+        let origin = Coordinates {
+            x: -rect.x,
+            y: -rect.y,
+        };
+        Schematic {
+            name: String::new(),
+            tiles,
+            chests: self
+                .chests
+                .iter()
+                .filter(|chest| rect.contains_position(&chest.position) && whole(&chest.position))
+                .map(|chest| Chest {
+                    position: offset(&chest.position, &origin),
+                    ..chest.clone()
+                })
+                .collect(),
+            signs: self
+                .signs
+                .iter()
+                .filter(|sign| rect.contains_position(&sign.position) && whole(&sign.position))
+                .map(|sign| Sign {
+                    position: offset(&sign.position, &origin),
+                    ..sign.clone()
+                })
+                .collect(),
+            tile_entities: self
+                .tile_entities
+                .iter()
+                .filter(|entity| {
+                    rect.contains_position(&entity.position) && whole(&entity.position)
+                })
+                .map(|entity| TileEntity {
+                    position: offset(&entity.position, &origin),
+                    ..entity.clone()
+                })
+                .collect(),
+        }
+    }
+
+    /// Paste `schematic` with its top-left corner at (`x`, `y`), clipped to the
+    /// world. Returns how many tiles changed.
+    ///
+    /// Objects the paste replaces any tile of are removed whole first, with
+    /// their chests, signs, tile entities and weighed pressure plates, as is
+    /// anything attached to another replaced block. Pasted chests get the
+    /// world's `chests_max_items` slots, dropping items past the last one
+    /// with a warning, and pasted tile entities get new ids.
+    ///
+    /// Nothing is pasted when the schematic's tiles do not match its size or
+    /// the world could end up with more than [`MAX_CHESTS`] chests or
+    /// [`MAX_SIGNS`] signs.
+    pub fn paste(
+        &mut self,
+        schematic: &Schematic,
+        x: i32,
+        y: i32,
+        options: &PasteOptions,
+    ) -> Result<usize, PasteError> {
+        self.check_paste(schematic, x, y, None)?;
+        let target = Rect::new(x, y, schematic.width() as i32, schematic.height() as i32);
+        let Some(clipped) = self.clip(target) else {
+            return Ok(0);
+        };
+        let source = |wx: usize, wy: usize| {
+            &schematic.tiles.tiles[(wx as i32 - x) as usize][(wy as i32 - y) as usize]
+        };
+        let replaces = |before: &Tile, after: &Tile| {
+            after.block_id != before.block_id || after.block_frame != before.block_frame
+        };
+
+        // Objects the paste would cut into go whole, not just the tiles it replaces
+        let objects = self.objects_at(
+            self.tiles_in(clipped)
+                .filter(|&(wx, wy, tile)| {
+                    replaces(tile, &paste_tile(tile, source(wx, wy), options))
+                })
+                .map(|(wx, wy, _)| (wx as i32, wy as i32)),
+        );
+        let mut changed = self.remove_objects(&objects, clipped);
+
+        let mut replaced = HashSet::new();
+        for (wx, wy, tile) in self.tiles_in_mut(clipped) {
+            let mut base = tile.clone();
+            let in_removed = objects
+                .iter()
+                .any(|&(object, id)| object.contains(wx as i32, wy as i32) && tile.block_id == id);
+            if in_removed && tile.has_block() {
+                remove_block(&mut base);
+            }
+            let pasted = paste_tile(&base, source(wx, wy), options);
+            if pasted != *tile {
+                if replaces(tile, &pasted) {
+                    replaced.insert((wx as i32, wy as i32));
+                }
+                *tile = pasted;
+                changed += 1;
+            }
+        }
+
+        // Whatever was attached to a replaced block is gone with it
+        let was_replaced = |position: &Coordinates| replaced.contains(&(position.x, position.y));
+        self.weighed_pressure_plates
+            .retain(|plate| !was_replaced(&plate.position));
+        self.remove_entities(was_replaced);
+
+        // Bring along what sits on a tile that made it into the world
+        let origin = Coordinates { x, y };
+        let landed = |world: &World, position: &Coordinates| {
+            let (Ok(sx), Ok(sy)) = (usize::try_from(position.x), usize::try_from(position.y))
+            else {
+                return false;
+            };
+            let Some(source) = schematic
+                .tiles
+                .tiles
+                .get(sx)
+                .and_then(|column| column.get(sy))
+            else {
+                return false;
+            };
+            let position = offset(position, &origin);
+            clipped.contains_position(&position)
+                && world.tile_at(&position).is_some_and(|tile| {
+                    tile.block_id == source.block_id && tile.block_frame == source.block_frame
+                })
+        };
+        for chest in &schematic.chests {
+            if landed(self, &chest.position) {
+                let position = offset(&chest.position, &origin);
+                self.remove_entities(|other| *other == position);
+                let slots = self.chests_max_items.max(0) as usize;
+                let dropped = chest.contents.iter().skip(slots).flatten().count();
+                if dropped > 0 {
+                    eprintln!(
+                        "⚠️ Warning: Chest pasted at ({}, {}) has {} items past the world's {} slots, they were dropped.",
+                        position.x, position.y, dropped, slots
+                    );
+                }
+                let mut contents = chest.contents.clone();
+                contents.resize(slots, None);
+                self.push_chest(Chest {
+                    position,
+                    name: chest.name.clone(),
+                    contents,
+                });
+            }
+        }
+        for sign in &schematic.signs {
+            if landed(self, &sign.position) {
+                let position = offset(&sign.position, &origin);
+                self.remove_entities(|other| *other == position);
+                self.push_sign(Sign {
+                    position,
+                    ..sign.clone()
+                });
+            }
+        }
+        for entity in &schematic.tile_entities {
+            if landed(self, &entity.position) {
+                let position = offset(&entity.position, &origin);
+                self.remove_entities(|other| *other == position);
+                let id = self.next_tile_entity_id();
+                self.push_tile_entity(TileEntity {
+                    id,
+                    position,
+                    ..entity.clone()
+                });
+            }
+        }
+
+        Ok(changed)
+    }
+
+    // Refuse schematics whose tiles do not match their size, and pastes that
+    // could go over the chest or sign limit. Chests and signs inside
+    // `cleared` are about to be removed and do not count.
+    pub(crate) fn check_paste(
+        &self,
+        schematic: &Schematic,
+        x: i32,
+        y: i32,
+        cleared: Option<Rect>,
+    ) -> Result<(), PasteError> {
+        let (width, height) = schematic.tiles.size;
+        if schematic.tiles.tiles.len() != width {
+            return Err(PasteError::ColumnCount {
+                expected: width,
+                found: schematic.tiles.tiles.len(),
+            });
+        }
+        if let Some((column, tiles)) = schematic
+            .tiles
+            .tiles
+            .iter()
+            .enumerate()
+            .find(|(_, tiles)| tiles.len() != height)
+        {
+            return Err(PasteError::ColumnLength {
+                column,
+                expected: height,
+                found: tiles.len(),
+            });
+        }
+
+        let Some(clipped) = self.clip(Rect::new(x, y, width as i32, height as i32)) else {
+            return Ok(());
+        };
+        let origin = Coordinates { x, y };
+        let incoming = |positions: Vec<&Coordinates>| -> Vec<Coordinates> {
+            positions
+                .into_iter()
+                .map(|position| offset(position, &origin))
+                .filter(|position| clipped.contains_position(position))
+                .collect()
+        };
+        // Whatever sits where an incoming one lands is replaced by it
+        let kept = |positions: Vec<&Coordinates>, incoming: &[Coordinates]| {
+            positions
+                .into_iter()
+                .filter(|position| {
+                    !incoming.contains(position)
+                        && !cleared.is_some_and(|rect| rect.contains_position(position))
+                })
+                .count()
+        };
+
+        let chests = incoming(schematic.chests.iter().map(|c| &c.position).collect());
+        let existing = self.chests.iter().map(|c| &c.position).collect();
+        if !chests.is_empty() && kept(existing, &chests) + chests.len() > MAX_CHESTS {
+            return Err(PasteError::LimitReached {
+                object: "chests".to_string(),
+                max: MAX_CHESTS,
+            });
+        }
+        let signs = incoming(schematic.signs.iter().map(|s| &s.position).collect());
+        let existing = self.signs.iter().map(|s| &s.position).collect();
+        if !signs.is_empty() && kept(existing, &signs) + signs.len() > MAX_SIGNS {
+            return Err(PasteError::LimitReached {
+                object: "signs".to_string(),
+                max: MAX_SIGNS,
+            });
+        }
+        Ok(())
+    }
+
+    /// An id no tile entity in the world uses yet.
+    pub fn next_tile_entity_id(&self) -> i32 {
+        self.tile_entities
+            .iter()
+            .map(|entity| entity.id)
+            .max()
+            .map_or(0, |id| id + 1)
+    }
+}
+
+fn offset(position: &Coordinates, by: &Coordinates) -> Coordinates {
+    Coordinates {
+        x: position.x + by.x,
+        y: position.y + by.y,
+    }
+}
+
+// What a world tile becomes when `source` is pasted over it
+fn paste_tile(existing: &Tile, source: &Tile, options: &PasteOptions) -> Tile {
+    let is_air = !source.has_block()
+        && !source.has_wall()
+        && !source.has_liquid()
+        && !(source.red_wire
+            || source.blue_wire
+            || source.green_wire
+            || source.yellow_wire
+            || source.activator_wire);
+    if options.skip_air && is_air {
+        return existing.clone();
+    }
+    if !options.overwrite && existing.has_block() {
+        return existing.clone();
+    }
+
+    let mut tile = source.clone();
+    if options.keep_walls && existing.has_wall() {
+        tile.wall_id = existing.wall_id;
+        tile.wall_paint = existing.wall_paint;
+        tile.wall_illuminant = existing.wall_illuminant;
+        tile.wall_echo = existing.wall_echo;
+    }
+    tile
+}
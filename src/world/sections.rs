@@ -0,0 +1,143 @@
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+
+/// The 11 sections listed in the pointer table of a `.wld` file, in file order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Section {
+    Header = 0,
+    Tiles = 1,
+    Chests = 2,
+    Signs = 3,
+    Npcs = 4,
+    TileEntities = 5,
+    PressurePlates = 6,
+    TownManager = 7,
+    Bestiary = 8,
+    JourneyPowers = 9,
+    Footer = 10,
+}
+
+impl Section {
+    pub const ALL: [Section; 11] = [
+        Section::Header,
+        Section::Tiles,
+        Section::Chests,
+        Section::Signs,
+        Section::Npcs,
+        Section::TileEntities,
+        Section::PressurePlates,
+        Section::TownManager,
+        Section::Bestiary,
+        Section::JourneyPowers,
+        Section::Footer,
+    ];
+
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Section::Header => "world header",
+            Section::Tiles => "tiles",
+            Section::Chests => "chests",
+            Section::Signs => "signs",
+            Section::Npcs => "NPCs and mobs",
+            Section::TileEntities => "tile entities",
+            Section::PressurePlates => "weighed pressure plates",
+            Section::TownManager => "rooms",
+            Section::Bestiary => "bestiary",
+            Section::JourneyPowers => "journey powers",
+            Section::Footer => "footer",
+        }
+    }
+}
+
+impl std::fmt::Display for Section {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// The bytes of the file a world was loaded from, and which sections and
+/// tile columns were changed since.
+///
+/// The editing methods mark what they change, see [`World::mark_dirty`](super::World::mark_dirty) for
+/// edits made by hand. When saving, everything never marked is copied from
+/// the loaded bytes verbatim without being serialized again, which keeps it
+/// byte-identical, including anything the crate does not model.
+#[derive(Clone, Default)]
+pub struct SectionCache {
+    pub(crate) bytes: Vec<u8>,
+    pub(crate) sections: Vec<Range<usize>>,
+    pub(crate) tile_columns: Vec<Range<usize>>,
+    pub(crate) dirty: [bool; Section::ALL.len()],
+    pub(crate) dirty_columns: Vec<bool>,
+}
+
+impl SectionCache {
+    /// Keep the `bytes` of a freshly loaded world, with the ranges of its
+    /// sections and tile columns.
+    pub(crate) fn capture(
+        bytes: Vec<u8>,
+        pointer_vector: &[u32],
+        footer_end: usize,
+        tile_columns: Vec<Range<usize>>,
+    ) -> Self {
+        let sections = Section::ALL
+            .iter()
+            .map(|section| {
+                let start = pointer_vector[section.index()] as usize;
+                let end = pointer_vector
+                    .get(section.index() + 1)
+                    .map(|&p| p as usize)
+                    .unwrap_or(footer_end);
+                start..end
+            })
+            .collect();
+        Self {
+            bytes,
+            sections,
+            dirty: [false; Section::ALL.len()],
+            dirty_columns: vec![false; tile_columns.len()],
+            tile_columns,
+        }
+    }
+
+    /// Loaded bytes of a section that was never marked dirty.
+    pub(crate) fn unchanged_section(&self, section: Section) -> Option<&[u8]> {
+        let range = self.sections.get(section.index())?;
+        (!self.dirty[section.index()]).then(|| &self.bytes[range.clone()])
+    }
+
+    /// Loaded bytes of a tile column that was never marked dirty.
+    pub(crate) fn unchanged_column(&self, x: usize) -> Option<&[u8]> {
+        let range = self.tile_columns.get(x)?;
+        (!self.dirty[Section::Tiles.index()] && !self.dirty_columns[x])
+            .then(|| &self.bytes[range.clone()])
+    }
+
+    pub(crate) fn mark_section(&mut self, section: Section) {
+        self.dirty[section.index()] = true;
+    }
+
+    pub(crate) fn mark_columns(&mut self, columns: Range<usize>) {
+        let end = columns.end.min(self.dirty_columns.len());
+        for dirty in &mut self.dirty_columns[columns.start.min(end)..end] {
+            *dirty = true;
+        }
+    }
+}
+
+impl std::fmt::Debug for SectionCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // the loaded bytes are huge, only print the section sizes
+        let section_sizes: Vec<usize> = self.sections.iter().map(|s| s.len()).collect();
+        f.debug_struct("SectionCache")
+            .field("section_sizes", &section_sizes)
+            .field("tile_columns", &self.tile_columns.len())
+            .field("dirty", &self.dirty)
+            .finish()
+    }
+}
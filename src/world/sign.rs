@@ -1,8 +1,162 @@
 use super::coordinates::Coordinates;
+use super::entity_index::{Entity, Slot};
+use super::error::{PlacementError, SignTextError};
+use super::validation::{is_sign_origin, MAX_SIGNS};
+use super::World;
 use serde::{Deserialize, Serialize};
 
+/// Most characters the game's sign editor accepts.
+pub const MAX_SIGN_TEXT_LENGTH: usize = 1200;
+/// Most lines the game's sign editor accepts.
+pub const MAX_SIGN_LINES: usize = 10;
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Sign {
     pub text: String,
     pub position: Coordinates,
 }
+
+/// The objects [`World::add_sign`] can place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SignKind {
+    Sign,
+    Tombstone,
+}
+
+impl SignKind {
+    pub fn block_name(&self) -> &'static str {
+        match self {
+            SignKind::Sign => "SIGNS",
+            SignKind::Tombstone => "TOMBSTONES",
+        }
+    }
+}
+
+impl Sign {
+    /// Check that `text` fits on a sign in the game.
+    pub fn validate_text(text: &str) -> Result<(), SignTextError> {
+        let length = text.chars().count();
+        if length > MAX_SIGN_TEXT_LENGTH {
+            return Err(SignTextError::TooLong {
+                length,
+                max: MAX_SIGN_TEXT_LENGTH,
+            });
+        }
+        let lines = text.split('\n').count();
+        if lines > MAX_SIGN_LINES {
+            return Err(SignTextError::TooManyLines {
+                lines,
+                max: MAX_SIGN_LINES,
+            });
+        }
+        Ok(())
+    }
+
+    /// Replace the text, leaving it untouched when the new one does not fit.
+    pub fn set_text(&mut self, text: &str) -> Result<(), SignTextError> {
+        Self::validate_text(text)?;
+        self.text = text.to_string();
+        Ok(())
+    }
+}
+
+impl World {
+    /// Place a sign or tombstone with its top-left tile at (`x`, `y`) and add
+    /// its text, see [`World::place_object`] for the placement rules.
+    pub fn add_sign(
+        &mut self,
+        x: i32,
+        y: i32,
+        kind: SignKind,
+        text: &str,
+    ) -> Result<&mut Sign, PlacementError> {
+        if self.signs.len() >= MAX_SIGNS {
+            return Err(PlacementError::LimitReached {
+                object: "signs".to_string(),
+                max: MAX_SIGNS,
+            });
+        }
+        Sign::validate_text(text).map_err(PlacementError::InvalidText)?;
+        self.place_object(x, y, kind.block_name(), 0)?;
+
+        let position = Coordinates { x, y };
+        // anything left at the spot belonged to an object that is gone
+        self.remove_entities(|other| *other == position);
+        Ok(self.push_sign(Sign {
+            text: text.to_string(),
+            position,
+        }))
+    }
+
+    /// The sign stored in the sign or tombstone that (`x`, `y`) is part of.
+    pub fn sign_at(&self, x: i32, y: i32) -> Option<&Sign> {
+        self.sign_position(x, y)?;
+        match self.entity_at(x, y)? {
+            Entity::Sign(sign) => Some(sign),
+            _ => None,
+        }
+    }
+
+    pub fn sign_at_mut(&mut self, x: i32, y: i32) -> Option<&mut Sign> {
+        self.sign_position(x, y)?;
+        let Slot::Sign(i) = self.slot_at(x, y)? else {
+            return None;
+        };
+        self.touch_lists();
+        self.signs.get_mut(i)
+    }
+
+    /// Remove the sign that (`x`, `y`) is part of along with its tiles.
+    pub fn remove_sign(&mut self, x: i32, y: i32) -> Option<Sign> {
+        self.sign_at(x, y)?;
+        let (_, removed) = self.take_object(x, y)?;
+        removed.signs.into_iter().next()
+    }
+
+    /// Signs whose whole text matches `pattern`, where `*` stands for any
+    /// run of characters and `?` for any single character.
+    pub fn find_signs(&self, pattern: &str) -> Vec<&Sign> {
+        let pattern: Vec<char> = pattern.chars().collect();
+        self.signs
+            .iter()
+            .filter(|sign| {
+                let text: Vec<char> = sign.text.chars().collect();
+                wildcard_match(&pattern, &text)
+            })
+            .collect()
+    }
+
+    // Top-left tile of the sign object at (x, y)
+    fn sign_position(&self, x: i32, y: i32) -> Option<Coordinates> {
+        let rect = self.object_at(x, y)?;
+        let position = Coordinates {
+            x: rect.x,
+            y: rect.y,
+        };
+        self.tile_at(&position)
+            .is_some_and(is_sign_origin)
+            .then_some(position)
+    }
+}
+
+fn wildcard_match(pattern: &[char], text: &[char]) -> bool {
+    // position of the last `*` and how much text it has swallowed so far
+    let (mut p, mut t) = (0, 0);
+    let mut star = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = star {
+            p = star_p + 1;
+            t = star_t + 1;
+            star = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
@@ -0,0 +1,165 @@
+// TEdit `.TEditSch` schematics.
+//
+// The format is the world format in miniature:
+//   name, version, tile_frame_important, width, height,
+//   tiles (column by column, same encoding as the tiles section),
+//   chests, signs, tile entities (same encoding as their world sections),
+//   and a footer repeating name, version, width and height.
+
+use std::io::{Error, ErrorKind};
+
+use super::schematic::Schematic;
+use super::tile::TileMatrix;
+use super::World;
+use crate::reader::ByteReader;
+use crate::writer::ByteWriter;
+
+/// Version written into `.TEditSch` files, the world version the tile encoding matches.
+/// Newer schematics may use tile or entity encodings the reader does not know.
+pub const TEDIT_SCHEMATIC_VERSION: i32 = 279;
+
+/// Oldest version that can be read, the first one (1.4.4) with the tile and
+/// tile entity encoding the world reader implements.
+pub const MIN_TEDIT_SCHEMATIC_VERSION: i32 = 269;
+
+// Chests in schematics written by TEdit always have this many slots
+const TEDIT_CHEST_ITEMS: i16 = 40;
+
+impl Schematic {
+    pub fn read_tedit(path: &str) -> std::io::Result<Self> {
+        let bytes = std::fs::read(path)?;
+        Self::from_tedit_bytes(&bytes)
+    }
+
+    pub fn save_as_tedit(&self, path: &str) -> std::io::Result<()> {
+        std::fs::write(path, self.to_tedit_bytes())
+    }
+
+    /// Decode a `.TEditSch` file. Only the current layout is supported,
+    /// schematics saved by old TEdit releases fail with `InvalidData`.
+    pub fn from_tedit_bytes(bytes: &[u8]) -> std::io::Result<Self> {
+        let mut r = ByteReader::new(bytes);
+        read_schematic(&mut r).map_err(|e| match e.kind() {
+            ErrorKind::UnexpectedEof => Error::new(
+                ErrorKind::InvalidData,
+                format!("Invalid TEdit schematic: {e}"),
+            ),
+            _ => e,
+        })
+    }
+
+    pub fn to_tedit_bytes(&self) -> Vec<u8> {
+        let (width, height) = self.tiles.size;
+        let mut writer = ByteWriter::new();
+        writer.string(&self.name);
+        writer.i32(TEDIT_SCHEMATIC_VERSION);
+
+        let tile_frame_important = World::default_tile_frame_important();
+        writer.i16(tile_frame_important.len() as i16);
+        for chunk in tile_frame_important.chunks(8) {
+            writer.bits(chunk);
+        }
+
+        writer.i32(width as i32);
+        writer.i32(height as i32);
+        for column in &self.tiles.tiles {
+            writer.bytes(World::write_tile_column(column).as_slice());
+        }
+
+        // every chest gets as many slots as the fullest one
+        let slots = self
+            .chests
+            .iter()
+            .map(|chest| chest.contents.len())
+            .max()
+            .unwrap_or(0)
+            .max(TEDIT_CHEST_ITEMS as usize);
+        writer.bytes(World::write_chests(slots as i16, &self.chests).as_slice());
+        writer.bytes(World::write_signs(&self.signs).as_slice());
+        writer.bytes(World::write_tile_entities(&self.tile_entities).as_slice());
+
+        writer.string(&self.name);
+        writer.i32(TEDIT_SCHEMATIC_VERSION);
+        writer.i32(width as i32);
+        writer.i32(height as i32);
+        writer.into_inner()
+    }
+}
+
+fn read_schematic(r: &mut ByteReader) -> std::io::Result<Schematic> {
+    let name = r.try_string(None)?;
+    let version = r.try_i32()?;
+    if version < MIN_TEDIT_SCHEMATIC_VERSION {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "TEdit schematic version {version} is older than the oldest supported version {MIN_TEDIT_SCHEMATIC_VERSION}"
+            ),
+        ));
+    }
+    if version > TEDIT_SCHEMATIC_VERSION {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "TEdit schematic version {version} is newer than the newest supported version {TEDIT_SCHEMATIC_VERSION}"
+            ),
+        ));
+    }
+
+    let tile_frame_important_count = r.try_i16()?;
+    let mut tile_frame_important = vec![];
+    for _ in 0..(tile_frame_important_count.max(0) as usize).div_ceil(8) {
+        tile_frame_important.extend(r.try_bits()?);
+    }
+    tile_frame_important.truncate(tile_frame_important_count.max(0) as usize);
+
+    let width = r.try_i32()?;
+    let height = r.try_i32()?;
+    let max_size = 0..=u16::MAX as i32;
+    if !max_size.contains(&width) || !max_size.contains(&height) {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("Invalid TEdit schematic size {width}x{height}"),
+        ));
+    }
+
+    let mut tiles = TileMatrix::new((0, 0));
+    for _ in 0..width {
+        let column = World::read_tile_column(r, height as usize, &tile_frame_important)?;
+        tiles.add_column(column);
+    }
+    if tiles
+        .tiles
+        .iter()
+        .any(|column| column.len() != height as usize)
+    {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "TEdit schematic tile runs do not match its height",
+        ));
+    }
+    tiles.size = (width as usize, height as usize);
+
+    let (_, chests) = World::read_chests(r)?;
+    let signs = World::read_signs(r)?;
+    let tile_entities = World::read_tile_entities(r)?;
+
+    let footer_matches = r.try_string(None)? == name
+        && r.try_i32()? == version
+        && r.try_i32()? == width
+        && r.try_i32()? == height;
+    if !footer_matches {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("TEdit schematic footer does not match its header (version {version})"),
+        ));
+    }
+
+    Ok(Schematic {
+        name,
+        tiles,
+        chests,
+        signs,
+        tile_entities,
+    })
+}
@@ -1,8 +1,13 @@
+use super::block_ids;
 use super::coordinates::Coordinates;
+use super::error::PlacementError;
 use super::item::ItemStack;
+use super::objects::{block_size, Support};
+use super::region::Rect;
+use super::World;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum TileEntityExtra {
     TargetDummy {
         npc: i16,
@@ -31,7 +36,7 @@ pub enum TileEntityExtra {
     Pylon,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TileEntity {
     pub id: i32,
     pub position: Coordinates,
@@ -47,3 +52,165 @@ impl TileEntity {
         }
     }
 }
+
+impl TileEntityExtra {
+    /// The block a tile entity of this kind is attached to.
+    pub fn block_id(&self) -> u16 {
+        match self {
+            TileEntityExtra::TargetDummy { .. } => block_ids::TARGET_DUMMY,
+            TileEntityExtra::ItemFrame { .. } => block_ids::ITEM_FRAME,
+            TileEntityExtra::LogicSensor { .. } => block_ids::LOGIC_SENSOR,
+            TileEntityExtra::Mannequin { .. } => block_ids::DISPLAY_DOLL,
+            TileEntityExtra::WeaponRack { .. } => block_ids::WEAPON_RACK_2,
+            TileEntityExtra::HatRack { .. } => block_ids::HAT_RACK,
+            TileEntityExtra::Plate { .. } => block_ids::PLATE,
+            TileEntityExtra::Pylon => block_ids::TELEPORTATION_PYLON,
+        }
+    }
+}
+
+/// What a logic sensor checks, numbered like `logic_check`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LogicSensorKind {
+    Day = 1,
+    Night = 2,
+    PlayerAbove = 3,
+    Water = 4,
+    Lava = 5,
+    Honey = 6,
+    Liquid = 7,
+}
+
+/// The biome a teleportation pylon belongs to, in the order of its frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PylonBiome {
+    Forest = 0,
+    Jungle = 1,
+    Hallow = 2,
+    Cavern = 3,
+    Ocean = 4,
+    Desert = 5,
+    Snow = 6,
+    Mushroom = 7,
+    Universal = 8,
+}
+
+impl World {
+    /// Place a 2x2 item frame on a wall with its top-left tile at (`x`, `y`), holding `item`.
+    pub fn place_item_frame(
+        &mut self,
+        x: i32,
+        y: i32,
+        item: ItemStack,
+    ) -> Result<&mut TileEntity, PlacementError> {
+        self.place_tile_entity(x, y, (0, 0), TileEntityExtra::ItemFrame { item })
+    }
+
+    /// Place an empty 2x3 mannequin standing on the floor.
+    pub fn place_mannequin(&mut self, x: i32, y: i32) -> Result<&mut TileEntity, PlacementError> {
+        let extra = TileEntityExtra::Mannequin {
+            items: vec![None; 8],
+            dyes: vec![None; 8],
+        };
+        self.place_tile_entity(x, y, (0, 0), extra)
+    }
+
+    /// Place a 3x3 weapon rack on a wall, holding `item`.
+    pub fn place_weapon_rack(
+        &mut self,
+        x: i32,
+        y: i32,
+        item: ItemStack,
+    ) -> Result<&mut TileEntity, PlacementError> {
+        self.place_tile_entity(x, y, (0, 0), TileEntityExtra::WeaponRack { item })
+    }
+
+    /// Place an empty 3x4 hat rack standing on the floor.
+    pub fn place_hat_rack(&mut self, x: i32, y: i32) -> Result<&mut TileEntity, PlacementError> {
+        let extra = TileEntityExtra::HatRack {
+            items: vec![None; 2],
+            dyes: vec![None; 2],
+        };
+        self.place_tile_entity(x, y, (0, 0), extra)
+    }
+
+    /// Place a food plate holding `item` on a floor or a table.
+    pub fn place_food_plate(
+        &mut self,
+        x: i32,
+        y: i32,
+        item: ItemStack,
+    ) -> Result<&mut TileEntity, PlacementError> {
+        self.place_tile_entity(x, y, (0, 0), TileEntityExtra::Plate { item })
+    }
+
+    /// Place a logic sensor checking `kind`. Sensors need nothing to hold them.
+    pub fn place_logic_sensor(
+        &mut self,
+        x: i32,
+        y: i32,
+        kind: LogicSensorKind,
+    ) -> Result<&mut TileEntity, PlacementError> {
+        let extra = TileEntityExtra::LogicSensor {
+            logic_check: kind as u8,
+            enabled: false,
+        };
+        self.place_tile_entity(x, y, (0, (kind as u16 - 1) * 18), extra)
+    }
+
+    /// Place a 3x4 teleportation pylon of `biome` standing on the floor.
+    pub fn place_pylon(
+        &mut self,
+        x: i32,
+        y: i32,
+        biome: PylonBiome,
+    ) -> Result<&mut TileEntity, PlacementError> {
+        self.place_tile_entity(x, y, (biome as u16 * 54, 0), TileEntityExtra::Pylon)
+    }
+
+    /// Place a 2x3 target dummy standing on the floor.
+    pub fn place_training_dummy(
+        &mut self,
+        x: i32,
+        y: i32,
+    ) -> Result<&mut TileEntity, PlacementError> {
+        self.place_tile_entity(x, y, (0, 0), TileEntityExtra::TargetDummy { npc: -1 })
+    }
+
+    // Places the tiles for `extra` with `frame` on its top-left tile and
+    // registers the tile entity under a fresh id
+    fn place_tile_entity(
+        &mut self,
+        x: i32,
+        y: i32,
+        frame: (u16, u16),
+        extra: TileEntityExtra,
+    ) -> Result<&mut TileEntity, PlacementError> {
+        let (width, height) = block_size(extra.block_id());
+        let rect = Rect::new(x, y, width as i32, height as i32);
+        self.place_frames(rect, extra.block_id(), frame, (18, 18), extra.supports())?;
+
+        let position = Coordinates { x, y };
+        // anything left at the spot belonged to an object that is gone
+        self.remove_entities(|other| *other == position);
+        let id = self.next_tile_entity_id();
+        Ok(self.push_tile_entity(TileEntity::new(id, position, Some(extra))))
+    }
+}
+
+impl TileEntityExtra {
+    // What can hold the object up
+    fn supports(&self) -> &'static [Support] {
+        match self {
+            TileEntityExtra::ItemFrame { .. } | TileEntityExtra::WeaponRack { .. } => {
+                &[Support::Wall]
+            }
+            TileEntityExtra::LogicSensor { .. } => &[],
+            TileEntityExtra::TargetDummy { .. }
+            | TileEntityExtra::Mannequin { .. }
+            | TileEntityExtra::HatRack { .. }
+            | TileEntityExtra::Plate { .. }
+            | TileEntityExtra::Pylon => &[Support::Floor],
+        }
+    }
+}
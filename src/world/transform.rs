@@ -0,0 +1,383 @@
+use super::chest::Chest;
+use super::coordinates::Coordinates;
+use super::error::TransformError;
+use super::objects::block_size;
+use super::pressure_plate::WeighedPressurePlate;
+use super::region::Rect;
+use super::schematic::Schematic;
+use super::sign::Sign;
+use super::tile::{Tile, TileMatrix};
+use super::tile_entity::TileEntity;
+use super::World;
+
+const PLATFORM: u16 = 19;
+
+// Frame x of platform pieces that are each other's mirror image (stairs, ends)
+const PLATFORM_MIRRORS: [(u16, u16); 12] = [
+    (18, 36),
+    (54, 72),
+    (108, 126),
+    (144, 180),
+    (162, 198),
+    (216, 234),
+    (270, 288),
+    (306, 324),
+    (342, 360),
+    (378, 396),
+    (414, 432),
+    (450, 468),
+];
+
+// Objects drawn facing left or right, with the width in pixels of one
+// facing: the frames for the other facing follow right after it
+const DIRECTIONAL_OBJECTS: [(u16, u16); 5] = [
+    (11, 36),  // open doors
+    (15, 18),  // chairs
+    (79, 72),  // beds
+    (90, 72),  // bathtubs
+    (497, 18), // toilets
+];
+
+// Objects whose styles are stacked further apart than their height
+const STYLE_HEIGHTS: [(u16, u16); 2] = [
+    (15, 40),  // chairs
+    (497, 40), // toilets
+];
+
+const FRAME_STEP: u16 = 18;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Transform {
+    FlipHorizontal,
+    FlipVertical,
+    RotateClockwise,
+    RotateCounterClockwise,
+}
+
+impl Schematic {
+    /// Mirror the schematic left to right.
+    ///
+    /// Slopes change direction, platform stairs are mirrored and directional
+    /// furniture (chairs, beds, open doors, ...) turns to face the other way.
+    /// Multi-tile objects stay whole.
+    pub fn flip_horizontal(&mut self) {
+        self.transform(Transform::FlipHorizontal);
+    }
+
+    /// Mirror the schematic top to bottom.
+    ///
+    /// Slopes and platform stairs are mirrored. Half blocks stay half blocks
+    /// and objects stay upright, since the game has no upside down variants.
+    pub fn flip_vertical(&mut self) {
+        self.transform(Transform::FlipVertical);
+    }
+
+    /// Turn the schematic a quarter turn clockwise, swapping its width and height.
+    ///
+    /// Slopes are turned along with it. Half blocks, platforms and multi-tile
+    /// objects cannot lie on their side in the game, so they keep their frames
+    /// and objects taller or wider than one tile usually come out broken.
+    pub fn rotate_clockwise(&mut self) {
+        self.transform(Transform::RotateClockwise);
+    }
+
+    /// Turn the schematic a quarter turn counterclockwise, see [`Schematic::rotate_clockwise`].
+    pub fn rotate_counterclockwise(&mut self) {
+        self.transform(Transform::RotateCounterClockwise);
+    }
+
+    fn transform(&mut self, transform: Transform) {
+        let (width, height) = self.tiles.size;
+        let new_size = match transform {
+            Transform::FlipHorizontal | Transform::FlipVertical => (width, height),
+            Transform::RotateClockwise | Transform::RotateCounterClockwise => (height, width),
+        };
+
+        let tiles = &self.tiles;
+        let move_origin = |position: &Coordinates| moved_origin(tiles, position, transform);
+        let chests: Vec<Chest> = self
+            .chests
+            .iter()
+            .map(|chest| Chest {
+                position: move_origin(&chest.position),
+                ..chest.clone()
+            })
+            .collect();
+        let signs: Vec<Sign> = self
+            .signs
+            .iter()
+            .map(|sign| Sign {
+                position: move_origin(&sign.position),
+                ..sign.clone()
+            })
+            .collect();
+        let tile_entities: Vec<TileEntity> = self
+            .tile_entities
+            .iter()
+            .map(|entity| TileEntity {
+                position: move_origin(&entity.position),
+                ..entity.clone()
+            })
+            .collect();
+
+        let mut tiles = TileMatrix::new((0, 0));
+        for nx in 0..new_size.0 {
+            let column = (0..new_size.1)
+                .map(|ny| {
+                    let (x, y) = match transform {
+                        Transform::FlipHorizontal => (width - 1 - nx, ny),
+                        Transform::FlipVertical => (nx, height - 1 - ny),
+                        Transform::RotateClockwise => (ny, height - 1 - nx),
+                        Transform::RotateCounterClockwise => (width - 1 - ny, nx),
+                    };
+                    transform_tile(&self.tiles.tiles[x][y], transform)
+                })
+                .collect();
+            tiles.add_column(column);
+        }
+        tiles.size = new_size;
+
+        self.tiles = tiles;
+        self.chests = chests;
+        self.signs = signs;
+        self.tile_entities = tile_entities;
+    }
+}
+
+impl World {
+    /// Mirror the tiles in `rect` left to right along with the chests, signs
+    /// and tile entities placed there, see [`Schematic::flip_horizontal`].
+    /// Returns the part of `rect` inside the world.
+    ///
+    /// Objects only partly inside `rect` can not be mirrored with it, they
+    /// are an error and nothing is changed.
+    pub fn flip_horizontal(&mut self, rect: Rect) -> Result<Rect, TransformError> {
+        self.transform_region(rect, Transform::FlipHorizontal)
+    }
+
+    /// Mirror the tiles in `rect` top to bottom, see [`Schematic::flip_vertical`]
+    /// and [`World::flip_horizontal`].
+    pub fn flip_vertical(&mut self, rect: Rect) -> Result<Rect, TransformError> {
+        self.transform_region(rect, Transform::FlipVertical)
+    }
+
+    /// Turn the tiles in `rect` a quarter turn clockwise around its top-left
+    /// corner, see [`Schematic::rotate_clockwise`]. Whatever the turned region
+    /// no longer covers is cleared. Returns the area the region covers now,
+    /// clipped to the world.
+    ///
+    /// Objects only partly inside `rect` or the area it turns into are an
+    /// error and nothing is changed.
+    pub fn rotate_clockwise(&mut self, rect: Rect) -> Result<Rect, TransformError> {
+        self.transform_region(rect, Transform::RotateClockwise)
+    }
+
+    /// Counterclockwise version of [`World::rotate_clockwise`].
+    pub fn rotate_counterclockwise(&mut self, rect: Rect) -> Result<Rect, TransformError> {
+        self.transform_region(rect, Transform::RotateCounterClockwise)
+    }
+
+    // Transforms a copy of the region and writes it back, keeping tile entity ids
+    fn transform_region(
+        &mut self,
+        rect: Rect,
+        transform: Transform,
+    ) -> Result<Rect, TransformError> {
+        let clipped = self.clip(rect).ok_or(TransformError::OutsideWorld)?;
+        let (width, height) = match transform {
+            Transform::FlipHorizontal | Transform::FlipVertical => (clipped.width, clipped.height),
+            Transform::RotateClockwise | Transform::RotateCounterClockwise => {
+                (clipped.height, clipped.width)
+            }
+        };
+        let target = self
+            .clip(Rect::new(clipped.x, clipped.y, width, height))
+            .ok_or(TransformError::OutsideWorld)?;
+        // Clearing an object that sticks out would destroy tiles outside the region
+        for area in [clipped, target] {
+            let split = self
+                .objects_at(self.tiles_in(area).map(|(x, y, _)| (x as i32, y as i32)))
+                .into_iter()
+                .find(|(object, _)| area.intersect(object) != Some(*object));
+            if let Some((object, _)) = split {
+                return Err(TransformError::SplitsObject(object));
+            }
+        }
+
+        self.touch_lists();
+        let mut schematic = self.copy(clipped);
+        // the schematic does not carry weighed pressure plates, they move
+        // along here the same way object origins do
+        let plates: Vec<Coordinates> = self
+            .weighed_pressure_plates
+            .iter()
+            .filter(|plate| clipped.contains_position(&plate.position))
+            .map(|plate| {
+                let relative = Coordinates {
+                    x: plate.position.x - clipped.x,
+                    y: plate.position.y - clipped.y,
+                };
+                moved_origin(&schematic.tiles, &relative, transform)
+            })
+            .collect();
+        schematic.transform(transform);
+        self.clear(clipped);
+        self.clear(target);
+        for (x, y, tile) in self.tiles_in_mut(target) {
+            let (sx, sy) = (x - clipped.x as usize, y - clipped.y as usize);
+            *tile = schematic.tiles.tiles[sx][sy].clone();
+        }
+
+        let origin = |position: &Coordinates| Coordinates {
+            x: position.x + clipped.x,
+            y: position.y + clipped.y,
+        };
+        for chest in schematic.chests {
+            let position = origin(&chest.position);
+            if target.contains_position(&position) {
+                self.push_chest(Chest { position, ..chest });
+            }
+        }
+        for sign in schematic.signs {
+            let position = origin(&sign.position);
+            if target.contains_position(&position) {
+                self.push_sign(Sign { position, ..sign });
+            }
+        }
+        for entity in schematic.tile_entities {
+            let position = origin(&entity.position);
+            if target.contains_position(&position) {
+                self.push_tile_entity(TileEntity { position, ..entity });
+            }
+        }
+        for plate in plates {
+            let position = origin(&plate);
+            if target.contains_position(&position) {
+                self.weighed_pressure_plates
+                    .push(WeighedPressurePlate::new(position));
+            }
+        }
+        Ok(target)
+    }
+}
+
+// Where the top-left corner of the object at `position` of `tiles` ends up
+fn moved_origin(tiles: &TileMatrix, position: &Coordinates, transform: Transform) -> Coordinates {
+    let (w, h) = (tiles.size.0 as i32, tiles.size.1 as i32);
+    let (object_w, object_h) = tiles
+        .tiles
+        .get(position.x.max(0) as usize)
+        .and_then(|column| column.get(position.y.max(0) as usize))
+        .map_or((1, 1), object_size);
+    let (x, y) = (position.x, position.y);
+    let (x, y) = match transform {
+        Transform::FlipHorizontal => (w - x - object_w as i32, y),
+        Transform::FlipVertical => (x, h - y - object_h as i32),
+        Transform::RotateClockwise => (h - 1 - y, x),
+        Transform::RotateCounterClockwise => (y, w - 1 - x),
+    };
+    Coordinates { x, y }
+}
+
+// Width and height in tiles of the object `tile` belongs to
+pub(crate) fn object_size(tile: &Tile) -> (u16, u16) {
+    if tile.block_frame.is_none() {
+        return (1, 1);
+    }
+    block_size(tile.block_id)
+}
+
+fn transform_tile(tile: &Tile, transform: Transform) -> Tile {
+    let mut tile = tile.clone();
+    tile.block_shape = transform_shape(tile.block_shape, transform);
+    if let Some(frame) = tile.block_frame.as_mut() {
+        let (width, height) = block_size(tile.block_id);
+        match transform {
+            Transform::FlipHorizontal => {
+                frame.x = if tile.block_id == PLATFORM {
+                    mirror_platform(frame.x)
+                } else if let Some(&(_, facing)) = DIRECTIONAL_OBJECTS
+                    .iter()
+                    .find(|(id, _)| *id == tile.block_id)
+                {
+                    turn_around(frame.x, facing)
+                } else {
+                    mirror_within(frame.x, width, width * FRAME_STEP)
+                };
+            }
+            Transform::FlipVertical => {
+                if tile.block_id == PLATFORM {
+                    frame.x = mirror_platform(frame.x);
+                } else {
+                    let style_height = STYLE_HEIGHTS
+                        .iter()
+                        .find(|(id, _)| *id == tile.block_id)
+                        .map_or(height * FRAME_STEP, |&(_, style_height)| style_height);
+                    frame.y = mirror_within(frame.y, height, style_height);
+                }
+            }
+            Transform::RotateClockwise | Transform::RotateCounterClockwise => {}
+        }
+    }
+    tile
+}
+
+// Shapes: 0 full, 1 half block, 2-5 slopes with the solid corner at the
+// bottom-left, bottom-right, top-left and top-right
+fn transform_shape(shape: u8, transform: Transform) -> u8 {
+    match (transform, shape) {
+        (Transform::FlipHorizontal, 2) => 3,
+        (Transform::FlipHorizontal, 3) => 2,
+        (Transform::FlipHorizontal, 4) => 5,
+        (Transform::FlipHorizontal, 5) => 4,
+        (Transform::FlipVertical, 2) => 4,
+        (Transform::FlipVertical, 4) => 2,
+        (Transform::FlipVertical, 3) => 5,
+        (Transform::FlipVertical, 5) => 3,
+        (Transform::RotateClockwise, 2) => 4,
+        (Transform::RotateClockwise, 4) => 5,
+        (Transform::RotateClockwise, 5) => 3,
+        (Transform::RotateClockwise, 3) => 2,
+        (Transform::RotateCounterClockwise, 2) => 3,
+        (Transform::RotateCounterClockwise, 3) => 5,
+        (Transform::RotateCounterClockwise, 5) => 4,
+        (Transform::RotateCounterClockwise, 4) => 2,
+        (_, shape) => shape,
+    }
+}
+
+fn mirror_platform(frame_x: u16) -> u16 {
+    for (a, b) in PLATFORM_MIRRORS {
+        if frame_x == a {
+            return b;
+        }
+        if frame_x == b {
+            return a;
+        }
+    }
+    frame_x
+}
+
+// The frame of the tile at the mirrored position inside its object, where
+// `count` tiles make up one style spanning `style_size` pixels
+fn mirror_within(frame: u16, count: u16, style_size: u16) -> u16 {
+    if count <= 1 || style_size == 0 {
+        return frame;
+    }
+    let index = (frame % style_size) / FRAME_STEP;
+    if index >= count {
+        return frame;
+    }
+    frame - index * FRAME_STEP + (count - 1 - index) * FRAME_STEP
+}
+
+// The frame of the same tile of a directional object facing the other way
+fn turn_around(frame_x: u16, facing: u16) -> u16 {
+    let style_start = frame_x - frame_x % (facing * 2);
+    let turned = if frame_x - style_start < facing {
+        frame_x + facing
+    } else {
+        frame_x - facing
+    };
+    mirror_within(turned, facing / FRAME_STEP, facing)
+}
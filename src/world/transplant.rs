@@ -0,0 +1,76 @@
+use super::coordinates::Coordinates;
+use super::error::PasteError;
+use super::pressure_plate::WeighedPressurePlate;
+use super::region::Rect;
+use super::room::Room;
+use super::schematic::PasteOptions;
+use super::sections::Section;
+use super::World;
+
+impl World {
+    /// Copy the tiles in `src_rect` of `source` to this world with their
+    /// top-left corner at (`dst_x`, `dst_y`), bringing along the chests,
+    /// signs, tile entities, weighed pressure plates and rooms placed there.
+    /// Returns the tiles of this world that were replaced.
+    ///
+    /// Whatever this world had in the replaced area is removed first and NPCs
+    /// living there become homeless. Tile entities get ids that are free in
+    /// this world, and NPCs of this world that own a moved room move into it.
+    /// Nothing changes when this world would end up with too many chests or
+    /// signs.
+    pub fn transplant(
+        &mut self,
+        source: &World,
+        src_rect: Rect,
+        dst_x: i32,
+        dst_y: i32,
+    ) -> Result<Option<Rect>, PasteError> {
+        let Some(src) = source.clip(src_rect) else {
+            return Ok(None);
+        };
+        let Some(target) = self.clip(Rect::new(dst_x, dst_y, src.width, src.height)) else {
+            return Ok(None);
+        };
+        let schematic = source.copy(src);
+        self.check_paste(&schematic, dst_x, dst_y, Some(target))?;
+        let moved = |position: &Coordinates| Coordinates {
+            x: position.x - src.x + dst_x,
+            y: position.y - src.y + dst_y,
+        };
+
+        self.touch_lists();
+        self.mark_dirty(Section::Npcs);
+        self.clear(target);
+        self.weighed_pressure_plates
+            .retain(|plate| !target.contains_position(&plate.position));
+        self.rooms
+            .retain(|room| !target.contains_position(&room.position));
+        for npc in &mut self.npcs {
+            if target.contains_position(&npc.home) {
+                npc.is_homeless = true;
+            }
+        }
+        self.paste(&schematic, dst_x, dst_y, &PasteOptions::default())?;
+
+        for plate in &source.weighed_pressure_plates {
+            let position = moved(&plate.position);
+            if src.contains_position(&plate.position) && target.contains_position(&position) {
+                self.weighed_pressure_plates
+                    .push(WeighedPressurePlate::new(position));
+            }
+        }
+        for room in &source.rooms {
+            let position = moved(&room.position);
+            if !src.contains_position(&room.position) || !target.contains_position(&position) {
+                continue;
+            }
+            self.rooms.retain(|r| r.npc != room.npc);
+            self.rooms.push(Room::new(room.npc, position.clone()));
+            for npc in self.npcs.iter_mut().filter(|npc| npc.type_ == room.npc) {
+                npc.home = position.clone();
+                npc.is_homeless = false;
+            }
+        }
+        Ok(Some(target))
+    }
+}
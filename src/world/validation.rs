@@ -0,0 +1,531 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::block_ids::{self, CHEST_BLOCKS, SIGN_BLOCKS};
+use super::coordinates::Coordinates;
+use super::tile::Tile;
+use super::World;
+
+/// Most chests a world can hold (`Main.maxChests`).
+pub const MAX_CHESTS: usize = 8000;
+/// Most signs a world can hold (`Sign.maxSigns`).
+pub const MAX_SIGNS: usize = 1000;
+/// Length of `mob_kills` (one counter per banner) in 1.4.4.9.
+pub const MOB_KILLS_COUNT: usize = 688;
+/// Length of `tile_frame_important` (one entry per block type) in 1.4.4.9.
+pub const BLOCK_TYPE_COUNT: usize = 693;
+
+/// Something in a world that the game assumes but the file does not guarantee.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ValidationIssue {
+    WorldSizeMismatch {
+        world_width: i32,
+        world_height: i32,
+        tiles_size: (usize, usize),
+    },
+    BoundsMismatch {
+        bounds_vec: Vec<i32>,
+        expected: Vec<i32>,
+    },
+    ChestOutOfBounds {
+        index: usize,
+        position: Coordinates,
+    },
+    ChestNotOnChestTile {
+        index: usize,
+        position: Coordinates,
+    },
+    DuplicateChestPosition {
+        position: Coordinates,
+        indices: Vec<usize>,
+    },
+    ChestContentsLength {
+        index: usize,
+        length: usize,
+        expected: usize,
+    },
+    TooManyChests {
+        count: usize,
+        max: usize,
+    },
+    SignOutOfBounds {
+        index: usize,
+        position: Coordinates,
+    },
+    SignNotOnSignTile {
+        index: usize,
+        position: Coordinates,
+    },
+    DuplicateSignPosition {
+        position: Coordinates,
+        indices: Vec<usize>,
+    },
+    TooManySigns {
+        count: usize,
+        max: usize,
+    },
+    TileEntityOutOfBounds {
+        index: usize,
+        id: i32,
+        position: Coordinates,
+    },
+    TileEntityTileMismatch {
+        index: usize,
+        id: i32,
+        position: Coordinates,
+        expected_block: u16,
+        found_block: Option<u16>,
+    },
+    UnknownTileEntity {
+        index: usize,
+        id: i32,
+    },
+    DuplicateTileEntityId {
+        id: i32,
+        indices: Vec<usize>,
+    },
+    DuplicateTileEntityPosition {
+        position: Coordinates,
+        indices: Vec<usize>,
+    },
+    NpcOutOfBounds {
+        index: usize,
+        name: String,
+        position: (f32, f32),
+    },
+    NpcHomeOutOfBounds {
+        index: usize,
+        name: String,
+        home: Coordinates,
+    },
+    RoomOutOfBounds {
+        index: usize,
+        position: Coordinates,
+    },
+    RoomWithoutNpc {
+        index: usize,
+        npc: i32,
+        position: Coordinates,
+    },
+    DuplicateRoom {
+        npc: i32,
+        indices: Vec<usize>,
+    },
+    MobKillsLength {
+        length: usize,
+        expected: usize,
+    },
+    TileFrameImportantLength {
+        length: usize,
+        expected: usize,
+    },
+}
+
+impl std::fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationIssue::WorldSizeMismatch {
+                world_width,
+                world_height,
+                tiles_size,
+            } => write!(
+                f,
+                "world is {world_width}x{world_height} but the tile matrix is {}x{}",
+                tiles_size.0, tiles_size.1
+            ),
+            ValidationIssue::BoundsMismatch {
+                bounds_vec,
+                expected,
+            } => write!(f, "bounds are {bounds_vec:?}, expected {expected:?}"),
+            ValidationIssue::ChestOutOfBounds { index, position } => write!(
+                f,
+                "chest #{index} at ({}, {}) is outside the world",
+                position.x, position.y
+            ),
+            ValidationIssue::ChestNotOnChestTile { index, position } => write!(
+                f,
+                "chest #{index} at ({}, {}) is not on the top-left tile of a chest",
+                position.x, position.y
+            ),
+            ValidationIssue::DuplicateChestPosition { position, indices } => write!(
+                f,
+                "chests {indices:?} share the position ({}, {})",
+                position.x, position.y
+            ),
+            ValidationIssue::ChestContentsLength {
+                index,
+                length,
+                expected,
+            } => write!(
+                f,
+                "chest #{index} has {length} item slots, expected {expected}"
+            ),
+            ValidationIssue::TooManyChests { count, max } => {
+                write!(f, "world has {count} chests, the game supports {max}")
+            }
+            ValidationIssue::SignOutOfBounds { index, position } => write!(
+                f,
+                "sign #{index} at ({}, {}) is outside the world",
+                position.x, position.y
+            ),
+            ValidationIssue::SignNotOnSignTile { index, position } => write!(
+                f,
+                "sign #{index} at ({}, {}) is not on the top-left tile of a sign",
+                position.x, position.y
+            ),
+            ValidationIssue::DuplicateSignPosition { position, indices } => write!(
+                f,
+                "signs {indices:?} share the position ({}, {})",
+                position.x, position.y
+            ),
+            ValidationIssue::TooManySigns { count, max } => {
+                write!(f, "world has {count} signs, the game supports {max}")
+            }
+            ValidationIssue::TileEntityOutOfBounds {
+                index,
+                id,
+                position,
+            } => write!(
+                f,
+                "tile entity #{index} (id {id}) at ({}, {}) is outside the world",
+                position.x, position.y
+            ),
+            ValidationIssue::TileEntityTileMismatch {
+                index,
+                id,
+                position,
+                expected_block,
+                found_block,
+            } => match found_block {
+                Some(found) => write!(
+                    f,
+                    "tile entity #{index} (id {id}) at ({}, {}) expects block {expected_block} but found block {found}",
+                    position.x, position.y
+                ),
+                None => write!(
+                    f,
+                    "tile entity #{index} (id {id}) at ({}, {}) expects block {expected_block} but the tile is empty",
+                    position.x, position.y
+                ),
+            },
+            ValidationIssue::UnknownTileEntity { index, id } => {
+                write!(f, "tile entity #{index} (id {id}) has an unknown type")
+            }
+            ValidationIssue::DuplicateTileEntityId { id, indices } => {
+                write!(f, "tile entities {indices:?} share the id {id}")
+            }
+            ValidationIssue::DuplicateTileEntityPosition { position, indices } => write!(
+                f,
+                "tile entities {indices:?} share the position ({}, {})",
+                position.x, position.y
+            ),
+            ValidationIssue::NpcOutOfBounds {
+                index,
+                name,
+                position,
+            } => write!(
+                f,
+                "NPC #{index} ({name}) at ({}, {}) is outside the world",
+                position.0, position.1
+            ),
+            ValidationIssue::NpcHomeOutOfBounds { index, name, home } => write!(
+                f,
+                "NPC #{index} ({name}) has its home at ({}, {}) outside the world",
+                home.x, home.y
+            ),
+            ValidationIssue::RoomOutOfBounds { index, position } => write!(
+                f,
+                "room #{index} at ({}, {}) is outside the world",
+                position.x, position.y
+            ),
+            ValidationIssue::RoomWithoutNpc {
+                index,
+                npc,
+                position,
+            } => write!(
+                f,
+                "room #{index} at ({}, {}) belongs to NPC type {npc}, but no such NPC lives there",
+                position.x, position.y
+            ),
+            ValidationIssue::DuplicateRoom { npc, indices } => {
+                write!(f, "rooms {indices:?} all belong to NPC type {npc}")
+            }
+            ValidationIssue::MobKillsLength { length, expected } => {
+                write!(f, "mob_kills has {length} entries, expected {expected}")
+            }
+            ValidationIssue::TileFrameImportantLength { length, expected } => write!(
+                f,
+                "tile_frame_important has {length} entries, expected {expected}"
+            ),
+        }
+    }
+}
+
+impl World {
+    /// Check the world for inconsistencies the game does not tolerate.
+    ///
+    /// An empty result means every check passed.
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        self.validate_dimensions(&mut issues);
+        self.validate_chests(&mut issues);
+        self.validate_signs(&mut issues);
+        self.validate_tile_entities(&mut issues);
+        self.validate_npcs(&mut issues);
+        self.validate_counters(&mut issues);
+        issues
+    }
+
+    pub(crate) fn tile_at(&self, position: &Coordinates) -> Option<&Tile> {
+        if position.x < 0 || position.y < 0 {
+            return None;
+        }
+        self.tiles
+            .tiles
+            .get(position.x as usize)
+            .and_then(|column| column.get(position.y as usize))
+    }
+
+    fn validate_dimensions(&self, issues: &mut Vec<ValidationIssue>) {
+        let (width, height) = self.tiles.size;
+        let columns_match = self.tiles.tiles.len() == width
+            && self.tiles.tiles.iter().all(|column| column.len() == height);
+        if !columns_match
+            || width != self.world_width as usize
+            || height != self.world_height as usize
+        {
+            issues.push(ValidationIssue::WorldSizeMismatch {
+                world_width: self.world_width,
+                world_height: self.world_height,
+                tiles_size: self.tiles.size,
+            });
+        }
+
+        let expected = vec![0, self.world_width * 16, 0, self.world_height * 16];
+        if self.bounds_vec != expected {
+            issues.push(ValidationIssue::BoundsMismatch {
+                bounds_vec: self.bounds_vec.clone(),
+                expected,
+            });
+        }
+    }
+
+    fn validate_chests(&self, issues: &mut Vec<ValidationIssue>) {
+        if self.chests.len() > MAX_CHESTS {
+            issues.push(ValidationIssue::TooManyChests {
+                count: self.chests.len(),
+                max: MAX_CHESTS,
+            });
+        }
+
+        for (index, chest) in self.chests.iter().enumerate() {
+            match self.tile_at(&chest.position) {
+                None => issues.push(ValidationIssue::ChestOutOfBounds {
+                    index,
+                    position: chest.position.clone(),
+                }),
+                Some(tile) if !is_chest_origin(tile) => {
+                    issues.push(ValidationIssue::ChestNotOnChestTile {
+                        index,
+                        position: chest.position.clone(),
+                    })
+                }
+                Some(_) => {}
+            }
+            if chest.contents.len() != self.chests_max_items.max(0) as usize {
+                issues.push(ValidationIssue::ChestContentsLength {
+                    index,
+                    length: chest.contents.len(),
+                    expected: self.chests_max_items.max(0) as usize,
+                });
+            }
+        }
+
+        for (position, indices) in duplicates(self.chests.iter().map(|c| &c.position)) {
+            issues.push(ValidationIssue::DuplicateChestPosition { position, indices });
+        }
+    }
+
+    fn validate_signs(&self, issues: &mut Vec<ValidationIssue>) {
+        if self.signs.len() > MAX_SIGNS {
+            issues.push(ValidationIssue::TooManySigns {
+                count: self.signs.len(),
+                max: MAX_SIGNS,
+            });
+        }
+
+        for (index, sign) in self.signs.iter().enumerate() {
+            match self.tile_at(&sign.position) {
+                None => issues.push(ValidationIssue::SignOutOfBounds {
+                    index,
+                    position: sign.position.clone(),
+                }),
+                Some(tile) if !is_sign_origin(tile) => {
+                    issues.push(ValidationIssue::SignNotOnSignTile {
+                        index,
+                        position: sign.position.clone(),
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+
+        for (position, indices) in duplicates(self.signs.iter().map(|s| &s.position)) {
+            issues.push(ValidationIssue::DuplicateSignPosition { position, indices });
+        }
+    }
+
+    fn validate_tile_entities(&self, issues: &mut Vec<ValidationIssue>) {
+        for (index, entity) in self.tile_entities.iter().enumerate() {
+            let Some(extra) = &entity.extra else {
+                issues.push(ValidationIssue::UnknownTileEntity {
+                    index,
+                    id: entity.id,
+                });
+                continue;
+            };
+            let expected_block = extra.block_id();
+            match self.tile_at(&entity.position) {
+                None => issues.push(ValidationIssue::TileEntityOutOfBounds {
+                    index,
+                    id: entity.id,
+                    position: entity.position.clone(),
+                }),
+                Some(tile) if tile.block_id != expected_block => {
+                    issues.push(ValidationIssue::TileEntityTileMismatch {
+                        index,
+                        id: entity.id,
+                        position: entity.position.clone(),
+                        expected_block,
+                        found_block: tile.has_block().then_some(tile.block_id),
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+
+        for (id, indices) in duplicates(self.tile_entities.iter().map(|e| &e.id)) {
+            issues.push(ValidationIssue::DuplicateTileEntityId { id, indices });
+        }
+        for (position, indices) in duplicates(self.tile_entities.iter().map(|e| &e.position)) {
+            issues.push(ValidationIssue::DuplicateTileEntityPosition { position, indices });
+        }
+    }
+
+    fn validate_npcs(&self, issues: &mut Vec<ValidationIssue>) {
+        let pixel_width = self.world_width as f32 * 16.0;
+        let pixel_height = self.world_height as f32 * 16.0;
+        for (index, npc) in self.npcs.iter().enumerate() {
+            if !(0.0..pixel_width).contains(&npc.position_x)
+                || !(0.0..pixel_height).contains(&npc.position_y)
+            {
+                issues.push(ValidationIssue::NpcOutOfBounds {
+                    index,
+                    name: npc.name.clone(),
+                    position: (npc.position_x, npc.position_y),
+                });
+            }
+            if !npc.is_homeless && self.tile_at(&npc.home).is_none() {
+                issues.push(ValidationIssue::NpcHomeOutOfBounds {
+                    index,
+                    name: npc.name.clone(),
+                    home: npc.home.clone(),
+                });
+            }
+        }
+
+        for (index, room) in self.rooms.iter().enumerate() {
+            if self.tile_at(&room.position).is_none() {
+                issues.push(ValidationIssue::RoomOutOfBounds {
+                    index,
+                    position: room.position.clone(),
+                });
+            }
+            let has_npc = self
+                .npcs
+                .iter()
+                .any(|npc| npc.type_ == room.npc && !npc.is_homeless && npc.home == room.position);
+            if !has_npc {
+                issues.push(ValidationIssue::RoomWithoutNpc {
+                    index,
+                    npc: room.npc,
+                    position: room.position.clone(),
+                });
+            }
+        }
+
+        for (npc, indices) in duplicates(self.rooms.iter().map(|r| &r.npc)) {
+            issues.push(ValidationIssue::DuplicateRoom { npc, indices });
+        }
+    }
+
+    fn validate_counters(&self, issues: &mut Vec<ValidationIssue>) {
+        // The expected lengths are only known for the version this crate targets
+        if self.version_integer != 279 {
+            return;
+        }
+        if self.mob_kills.len() != MOB_KILLS_COUNT {
+            issues.push(ValidationIssue::MobKillsLength {
+                length: self.mob_kills.len(),
+                expected: MOB_KILLS_COUNT,
+            });
+        }
+        if self.tile_frame_important.len() != BLOCK_TYPE_COUNT {
+            issues.push(ValidationIssue::TileFrameImportantLength {
+                length: self.tile_frame_important.len(),
+                expected: BLOCK_TYPE_COUNT,
+            });
+        }
+    }
+}
+
+// Chests live on the top-left tile of a 2x2 chest or a 3x2 dresser
+pub(crate) fn is_chest_origin(tile: &Tile) -> bool {
+    if !tile.has_block() || !CHEST_BLOCKS.contains(&tile.block_id) {
+        return false;
+    }
+    let object_width = if tile.block_id == block_ids::DRESSERS {
+        54
+    } else {
+        36
+    };
+    match &tile.block_frame {
+        Some(frame) => frame.x % object_width == 0 && frame.y % 36 == 0,
+        None => true,
+    }
+}
+
+// Signs live on the top-left tile of a 2x2 sign, tombstone or announcement box
+pub(crate) fn is_sign_origin(tile: &Tile) -> bool {
+    if !tile.has_block() || !SIGN_BLOCKS.contains(&tile.block_id) {
+        return false;
+    }
+    match &tile.block_frame {
+        Some(frame) => frame.x % 36 == 0 && frame.y % 36 == 0,
+        None => true,
+    }
+}
+
+// Values that occur more than once, with the indices they occur at
+fn duplicates<'a, T, I>(values: I) -> Vec<(T, Vec<usize>)>
+where
+    T: Clone + Eq + std::hash::Hash + 'a,
+    I: Iterator<Item = &'a T>,
+{
+    let mut seen: HashMap<&T, Vec<usize>> = HashMap::new();
+    let mut order = Vec::new();
+    for (index, value) in values.enumerate() {
+        let indices = seen.entry(value).or_default();
+        if indices.is_empty() {
+            order.push(value);
+        }
+        indices.push(index);
+    }
+    order
+        .into_iter()
+        .filter_map(|value| {
+            let indices = &seen[value];
+            (indices.len() > 1).then(|| (value.clone(), indices.clone()))
+        })
+        .collect()
+}
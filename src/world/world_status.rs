@@ -0,0 +1,203 @@
+use serde::{Deserialize, Serialize};
+
+use super::block_ids;
+use super::World;
+
+// Blocks the game counts towards each alignment, and every block it divides
+// by (`WorldGen.AddUpAlignmentCounts`)
+const GOOD_BLOCKS: [u16; 5] = [
+    block_ids::HALLOWED_ICE,
+    block_ids::HALLOWED_GRASS,
+    block_ids::PEARLSTONE,
+    block_ids::PEARLSAND,
+    block_ids::GOLF_GRASS_HALLOWED,
+];
+const EVIL_BLOCKS: [u16; 5] = [
+    block_ids::CORRUPT_GRASS,
+    block_ids::CORRUPT_ICE,
+    block_ids::EBONSAND,
+    block_ids::EBONSTONE,
+    block_ids::CORRUPT_JUNGLE_GRASS,
+];
+const BLOOD_BLOCKS: [u16; 5] = [
+    block_ids::FLESH_GRASS,
+    block_ids::CRIMSAND,
+    block_ids::CRIMSTONE,
+    block_ids::FLESH_ICE,
+    block_ids::CRIMSON_JUNGLE_GRASS,
+];
+const PURE_BLOCKS: [u16; 6] = [
+    block_ids::GRASS,
+    block_ids::STONE,
+    block_ids::JUNGLE_GRASS,
+    block_ids::SAND,
+    block_ids::ICE_BLOCK,
+    block_ids::MOWED_GOLF_GRASS,
+];
+
+// Blocks down to the surface level count this many times
+const SURFACE_WEIGHT: u64 = 5;
+
+// How far the exact percentages may move before the Dryad says something else
+const THRESHOLD_MARGIN: f64 = 0.5;
+
+/// How the Dryad sums up the world after listing its percentages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DryadDescription {
+    /// No hallow, corruption or crimson at all.
+    Pure,
+    /// Hallow and evil are within 20% of each other.
+    Balanced,
+    /// More hallow than evil.
+    FairyTale,
+    /// Evil is ahead of hallow by more than 20 points.
+    Grim,
+    /// Evil is ahead, but at 5% or less.
+    Close,
+    /// Evil is ahead by up to 20 points.
+    Work,
+}
+
+impl DryadDescription {
+    /// The description the game picks for the percentages it shows.
+    pub fn from_percentages(good: u8, evil: u8, blood: u8) -> Self {
+        let (good, bad) = (good as f64, evil as f64 + blood as f64);
+        if good == 0.0 && bad == 0.0 {
+            DryadDescription::Pure
+        } else if good * 1.2 >= bad && good * 0.8 <= bad {
+            DryadDescription::Balanced
+        } else if good >= bad {
+            DryadDescription::FairyTale
+        } else if bad > good + 20.0 {
+            DryadDescription::Grim
+        } else if bad <= 5.0 {
+            DryadDescription::Close
+        } else {
+            DryadDescription::Work
+        }
+    }
+}
+
+impl std::fmt::Display for DryadDescription {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DryadDescription::Pure => write!(f, "Pure"),
+            DryadDescription::Balanced => write!(f, "Balanced"),
+            DryadDescription::FairyTale => write!(f, "FairyTale"),
+            DryadDescription::Grim => write!(f, "Grim"),
+            DryadDescription::Close => write!(f, "Close"),
+            DryadDescription::Work => write!(f, "Work"),
+        }
+    }
+}
+
+/// Hallow, corruption and crimson of a world as the Dryad reports them,
+/// see [`World::world_status`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorldStatus {
+    /// Percentages the Dryad shows, rounded like the game does.
+    pub good: u8,
+    pub evil: u8,
+    pub blood: u8,
+    /// The same percentages before rounding.
+    pub good_exact: f64,
+    pub evil_exact: f64,
+    pub blood_exact: f64,
+    /// Weighted block counts the percentages come from.
+    pub total_good: u64,
+    pub total_evil: u64,
+    pub total_blood: u64,
+    pub total_solid: u64,
+    pub description: DryadDescription,
+    /// Whether moving any exact percentage by half a point would change
+    /// the rounded numbers enough to change the description.
+    pub near_threshold: bool,
+}
+
+impl World {
+    /// Count hallow, corruption and crimson blocks the way the game does for
+    /// the Dryad's world status.
+    ///
+    /// Only grass, jungle grass, stone, sand and ice of each kind count, plus
+    /// the pure ones and mowed grass for the total. Blocks at or above the surface level
+    /// count five times.
+    pub fn world_status(&self) -> WorldStatus {
+        let (mut good, mut evil, mut blood, mut solid) = (0, 0, 0, 0);
+        for column in &self.tiles.tiles {
+            for (y, tile) in column.iter().enumerate() {
+                if !tile.has_block() {
+                    continue;
+                }
+                let weight = if y as f64 <= self.underground_level {
+                    SURFACE_WEIGHT
+                } else {
+                    1
+                };
+                let id = tile.block_id;
+                if GOOD_BLOCKS.contains(&id) {
+                    good += weight;
+                } else if EVIL_BLOCKS.contains(&id) {
+                    evil += weight;
+                } else if BLOOD_BLOCKS.contains(&id) {
+                    blood += weight;
+                } else if !PURE_BLOCKS.contains(&id) {
+                    continue;
+                }
+                solid += weight;
+            }
+        }
+
+        let exact = |count: u64| {
+            if solid == 0 {
+                0.0
+            } else {
+                count as f64 / solid as f64 * 100.0
+            }
+        };
+        let (good_exact, evil_exact, blood_exact) = (exact(good), exact(evil), exact(blood));
+        let (good_percent, evil_percent, blood_percent) = (
+            dryad_percent(good_exact),
+            dryad_percent(evil_exact),
+            dryad_percent(blood_exact),
+        );
+        let description =
+            DryadDescription::from_percentages(good_percent, evil_percent, blood_percent);
+
+        let shifts = [-THRESHOLD_MARGIN, 0.0, THRESHOLD_MARGIN];
+        let near_threshold = shifts.iter().any(|dg| {
+            shifts.iter().any(|de| {
+                shifts.iter().any(|db| {
+                    DryadDescription::from_percentages(
+                        dryad_percent(good_exact + dg),
+                        dryad_percent(evil_exact + de),
+                        dryad_percent(blood_exact + db),
+                    ) != description
+                })
+            })
+        });
+
+        WorldStatus {
+            good: good_percent,
+            evil: evil_percent,
+            blood: blood_percent,
+            good_exact,
+            evil_exact,
+            blood_exact,
+            total_good: good,
+            total_evil: evil,
+            total_blood: blood,
+            total_solid: solid,
+            description,
+            near_threshold,
+        }
+    }
+}
+
+// The game rounds half to even, and shows any amount at all as at least 1%
+fn dryad_percent(exact: f64) -> u8 {
+    if exact <= 0.0 {
+        0
+    } else {
+        exact.round_ties_even().clamp(1.0, 100.0) as u8
+    }
+}
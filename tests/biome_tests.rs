@@ -0,0 +1,92 @@
+use terraria_world::world::biome::Biome;
+use terraria_world::world::region::Rect;
+use terraria_world::world::World;
+
+#[test]
+fn test_biome_map_uses_layers_without_biome_blocks() {
+    let world = World::new("biomes", "small", "classic", "corruption");
+    let map = world.biome_map(50);
+    assert_eq!(map.size(), (84, 24));
+    assert_eq!(map.biome_at(2100, 20), Some(Biome::Space));
+    assert_eq!(map.biome_at(2100, 300), Some(Biome::Forest));
+    assert_eq!(map.biome_at(100, 300), Some(Biome::Ocean));
+    assert_eq!(map.biome_at(2100, 400), Some(Biome::Underground));
+    assert_eq!(map.biome_at(2100, 700), Some(Biome::Cavern));
+    assert_eq!(map.biome_at(2100, 1150), Some(Biome::Underworld));
+    assert_eq!(map.biome_at(5000, 0), None);
+}
+
+#[test]
+fn test_biome_map_thresholds() {
+    let mut world = World::new("biomes", "small", "classic", "corruption");
+    // 20x20 = 400 corruption blocks, enough on their own
+    world
+        .fill(Rect::new(1000, 200, 20, 20), "EBONSTONE")
+        .unwrap();
+    // 400 corruption cancelled out by 400 hallow leaves a hallow of 0
+    world
+        .fill(Rect::new(2000, 200, 20, 20), "EBONSTONE")
+        .unwrap();
+    world
+        .fill(Rect::new(2020, 200, 20, 20), "PEARLSTONE")
+        .unwrap();
+    // 100 mushroom blocks is just enough
+    world
+        .fill(Rect::new(3000, 600, 10, 10), "MUSHROOM_GRASS")
+        .unwrap();
+    // mowed hallowed grass counts as hallow, 144 of 125
+    world
+        .fill(Rect::new(3500, 300, 12, 12), "GOLF_GRASS_HALLOWED")
+        .unwrap();
+    // sand needs 1500
+    world.fill(Rect::new(1500, 250, 30, 40), "SAND").unwrap();
+    // dungeon bricks only count underground
+    world
+        .fill(Rect::new(500, 250, 20, 20), "BLUE_DUNGEON_BRICK")
+        .unwrap();
+    world
+        .fill(Rect::new(500, 500, 20, 20), "BLUE_DUNGEON_BRICK")
+        .unwrap();
+
+    let map = world.biome_map(10);
+    assert_eq!(map.biome_at(1010, 210), Some(Biome::Corruption));
+    assert_eq!(map.biome_at(2020, 210), Some(Biome::Forest));
+    assert_eq!(map.biome_at(3005, 605), Some(Biome::Mushroom));
+    assert_eq!(map.biome_at(3505, 305), Some(Biome::Hallow));
+    assert_eq!(map.biome_at(1515, 270), Some(Biome::Forest));
+    assert_eq!(map.biome_at(510, 260), Some(Biome::Forest));
+    assert_eq!(map.biome_at(510, 510), Some(Biome::Dungeon));
+
+    world.fill(Rect::new(1500, 250, 40, 40), "SAND").unwrap();
+    assert_eq!(world.biome_map(10).biome_at(1515, 270), Some(Biome::Desert));
+    assert!(map.count(Biome::Corruption) > 0);
+}
+
+#[test]
+fn test_biome_map_counts_evil_plants_and_sunflowers() {
+    let mut world = World::new("biomes", "small", "classic", "corruption");
+    // 200 crimson jungle grass and 200 crimson vines make 400 crimson blocks
+    world
+        .fill(Rect::new(1000, 200, 20, 10), "CRIMSON_JUNGLE_GRASS")
+        .unwrap();
+    world
+        .fill(Rect::new(1000, 210, 20, 10), "CRIMSON_VINES")
+        .unwrap();
+    world
+        .fill(Rect::new(2000, 200, 20, 20), "CORRUPT_JUNGLE_GRASS")
+        .unwrap();
+    assert_eq!(
+        world.biome_map(10).biome_at(1010, 210),
+        Some(Biome::Crimson)
+    );
+    assert_eq!(
+        world.biome_map(10).biome_at(2010, 210),
+        Some(Biome::Corruption)
+    );
+
+    // 24 sunflower tiles take 120 off, leaving 280 of the 300 needed
+    for (_, _, tile) in world.tiles_in_mut(Rect::new(1030, 200, 6, 4)) {
+        tile.set_block_name("SUNFLOWER");
+    }
+    assert_eq!(world.biome_map(10).biome_at(1010, 210), Some(Biome::Forest));
+}
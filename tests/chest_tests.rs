@@ -0,0 +1,88 @@
+use terraria_world::world::chest::Chest;
+use terraria_world::world::coordinates::Coordinates;
+use terraria_world::world::error::PlacementError;
+use terraria_world::world::item::ItemStack;
+use terraria_world::world::region::Rect;
+use terraria_world::world::World;
+
+fn item(type_id: i32) -> ItemStack {
+    ItemStack {
+        quantity: 1,
+        type_id,
+        prefix: 0,
+    }
+}
+
+#[test]
+fn test_add_find_and_remove_chest() {
+    let mut world = World::new("chests", "small", "classic", "corruption");
+    world.fill(Rect::new(10, 20, 10, 1), "STONE").unwrap();
+    world.fill_wall(Rect::new(10, 18, 2, 2), "DIRT").unwrap();
+
+    let chest = world.add_chest(10, 18, 1, "loot").unwrap();
+    assert_eq!(chest.position, Coordinates { x: 10, y: 18 });
+    assert_eq!(chest.contents.len(), 40);
+    assert!(world.validate().is_empty());
+
+    // every tile of the chest finds it
+    for (x, y) in [(10, 18), (11, 18), (10, 19), (11, 19)] {
+        assert_eq!(world.chest_at(x, y).unwrap().name, "loot");
+    }
+    assert!(world.chest_at(12, 19).is_none());
+    assert!(matches!(
+        world.add_chest(11, 18, 0, "overlap"),
+        Err(PlacementError::Occupied { .. })
+    ));
+
+    let removed = world.remove_chest(11, 19).unwrap();
+    assert_eq!(removed.name, "loot");
+    assert!(world.chests.is_empty());
+    assert!(!world.tiles.tiles[11][19].has_block());
+    assert!(world.tiles.tiles[11][19].has_wall());
+    assert!(world.remove_chest(11, 19).is_none());
+}
+
+#[test]
+fn test_insert_chest_item_respects_max_items() {
+    let mut world = World::new("chests", "small", "classic", "corruption");
+    world.fill(Rect::new(10, 20, 10, 1), "STONE").unwrap();
+    world.chests_max_items = 2;
+    world.add_chest(10, 18, 0, "small").unwrap();
+
+    assert_eq!(world.insert_chest_item(11, 19, item(8)), Some(0));
+    assert_eq!(world.insert_chest_item(10, 18, item(9)), Some(1));
+    assert_eq!(world.insert_chest_item(10, 18, item(10)), None);
+    assert!(world.chest_at(10, 18).unwrap().is_full());
+    assert_eq!(world.insert_chest_item(14, 18, item(8)), None);
+}
+
+#[test]
+fn test_chests_with_short_contents_save_and_load() {
+    let mut world = World::new("chests", "small", "classic", "corruption");
+    world.chests.push(Chest {
+        position: Coordinates { x: 10, y: 18 },
+        name: "short".to_string(),
+        contents: vec![Some(item(8))],
+    });
+    world.chests.push(Chest {
+        position: Coordinates { x: 20, y: 18 },
+        name: "long".to_string(),
+        contents: vec![Some(item(9)); 50],
+    });
+
+    let dir = std::env::temp_dir().join(format!("terraria-world-tests-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("Failed to create temp directory");
+    let path = dir.join("short_chests.wld");
+    world
+        .save_as_wld(path.to_str().unwrap())
+        .expect("Failed to save world");
+    let loaded = World::from_file(path.to_str().unwrap()).expect("Failed to load world");
+
+    // every chest comes back with chests_max_items slots
+    assert_eq!(loaded.chests.len(), 2);
+    assert_eq!(loaded.chests[0].contents.len(), 40);
+    assert_eq!(loaded.chests[0].contents[0], Some(item(8)));
+    assert!(loaded.chests[0].contents[1..].iter().all(Option::is_none));
+    assert_eq!(loaded.chests[1].name, "long");
+    assert_eq!(loaded.chests[1].contents, vec![Some(item(9)); 40]);
+}
@@ -0,0 +1,93 @@
+use serde_json::Value;
+use terraria_world::world::diff::{ChestChange, NpcChange, SignChange};
+use terraria_world::world::item::ItemStack;
+use terraria_world::world::region::Rect;
+use terraria_world::world::sign::SignKind;
+use terraria_world::world::World;
+
+fn world_with_floor() -> World {
+    let mut world = World::new("diff", "small", "classic", "corruption");
+    world.fill(Rect::new(0, 20, 40, 1), "STONE").unwrap();
+    world
+}
+
+#[test]
+fn test_identical_worlds_have_no_diff() {
+    let world = world_with_floor();
+    let diff = world.diff(&world_with_floor());
+    assert!(diff.is_empty(), "{diff}");
+    assert_eq!(diff.to_string(), "no differences\n");
+}
+
+#[test]
+fn test_changed_tiles_are_coalesced() {
+    let before = world_with_floor();
+    let mut after = world_with_floor();
+    after.fill(Rect::new(100, 50, 10, 4), "DIRT").unwrap();
+    after.fill(Rect::new(200, 60, 1, 1), "DIRT").unwrap();
+    // an L shape needs two rectangles
+    after.fill(Rect::new(300, 10, 2, 5), "DIRT").unwrap();
+    after.fill(Rect::new(302, 14, 3, 1), "DIRT").unwrap();
+
+    let diff = before.diff(&after);
+    assert_eq!(diff.changed_tiles, 40 + 1 + 10 + 3);
+    assert_eq!(
+        diff.tiles,
+        vec![
+            Rect::new(100, 50, 10, 4),
+            Rect::new(200, 60, 1, 1),
+            Rect::new(300, 10, 2, 5),
+            Rect::new(302, 14, 3, 1),
+        ]
+    );
+    assert!(diff.chests.is_empty() && diff.header.is_empty());
+}
+
+#[test]
+fn test_entity_and_header_changes() {
+    let mut before = world_with_floor();
+    before.add_chest(2, 18, 0, "loot").unwrap();
+    before.add_sign(6, 18, SignKind::Sign, "hello").unwrap();
+    let mut after = world_with_floor();
+    after.add_chest(2, 18, 0, "loot").unwrap();
+    after.add_sign(6, 18, SignKind::Sign, "goodbye").unwrap();
+    after.add_chest(10, 18, 0, "new").unwrap();
+    after.insert_chest_item(
+        2,
+        18,
+        ItemStack {
+            quantity: 5,
+            type_id: 73,
+            prefix: 0,
+        },
+    );
+    after.npcs[0].position_x += 160.0;
+    after.game_progression.defeated_plantera = true;
+
+    let diff = before.diff(&after);
+    assert_eq!(diff.chests.len(), 2);
+    match &diff.chests[0] {
+        ChestChange::Modified { name, items, .. } => {
+            assert!(name.is_none());
+            assert_eq!(items.len(), 1);
+            assert_eq!(items[0].slot, 0);
+            assert_eq!(items[0].after.as_ref().unwrap().type_id, 73);
+        }
+        other => panic!("unexpected change {other:?}"),
+    }
+    assert!(matches!(&diff.chests[1], ChestChange::Added(chest) if chest.name == "new"));
+    assert!(matches!(
+        &diff.signs[..],
+        [SignChange::TextChanged { before, after, .. }] if before == "hello" && after == "goodbye"
+    ));
+    assert!(matches!(&diff.npcs[..], [NpcChange::Moved { .. }]));
+    assert_eq!(diff.header.len(), 1);
+    assert_eq!(diff.header[0].field, "game_progression.defeated_plantera");
+    assert_eq!(diff.header[0].after, Value::Bool(true));
+
+    let text = diff.to_string();
+    assert!(text.contains("chest \"new\" added at (10, 18)"), "{text}");
+    assert!(text.contains("game_progression.defeated_plantera: false -> true"));
+    let json = serde_json::to_value(&diff).unwrap();
+    assert_eq!(json["signs"][0]["TextChanged"]["after"], "goodbye");
+}
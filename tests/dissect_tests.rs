@@ -0,0 +1,139 @@
+use std::fs;
+use std::path::PathBuf;
+use terraria_world::world::sections::Section;
+use terraria_world::world::World;
+
+fn temp_world_path(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("terraria-world-tests-{}", std::process::id()));
+    fs::create_dir_all(&dir).expect("Failed to create temp directory");
+    dir.join(name)
+}
+
+#[test]
+fn test_dissect_world_file() {
+    let world = World::new("dissected", "small", "classic", "corruption");
+    let path = temp_world_path("dissected.wld");
+    let bytes = world.to_wld_bytes();
+    fs::write(&path, &bytes).unwrap();
+
+    let layout = World::dissect(path.to_str().unwrap()).expect("Failed to dissect world");
+    assert!(layout.problems().is_empty(), "{:?}", layout.problems());
+    assert_eq!(layout.file_size, bytes.len());
+    assert_eq!(layout.pointer_vector.len(), 11);
+    // the file header plus every section, covering the whole file without gaps
+    assert_eq!(layout.sections.len(), 12);
+    assert_eq!(layout.sections[0].start, 0);
+    for pair in layout.sections.windows(2) {
+        assert_eq!(pair[0].end, pair[1].start);
+    }
+    assert_eq!(layout.sections.last().unwrap().end, bytes.len());
+
+    let header = layout.section(Section::Header).unwrap();
+    assert_eq!(header.field("world_name").unwrap().value, "\"dissected\"");
+    assert_eq!(header.field("world_width").unwrap().value, "4200");
+    let mob_kills = header.field("mob_kills").unwrap();
+    assert_eq!(mob_kills.raw.len(), 2 + 688 * 4);
+    assert!(layout.section(Section::Tiles).unwrap().fields.is_empty());
+
+    let dump = layout.to_string();
+    assert!(dump.contains("world_height = 1200"));
+}
+
+#[test]
+fn test_dissect_reports_drifted_field() {
+    let world = World::new("drifted", "small", "classic", "corruption");
+    let path = temp_world_path("drifted.wld");
+    let mut bytes = world.to_wld_bytes();
+    fs::write(&path, &bytes).unwrap();
+    let layout = World::dissect(path.to_str().unwrap()).unwrap();
+    let offset = layout
+        .section(Section::Header)
+        .unwrap()
+        .field("mob_kills")
+        .unwrap()
+        .offset;
+
+    // one mob kill counter too few shifts every later header field by 4 bytes
+    bytes[offset..offset + 2].copy_from_slice(&687i16.to_le_bytes());
+    fs::write(&path, &bytes).unwrap();
+    let layout = World::dissect(path.to_str().unwrap()).unwrap();
+    let problems = layout.problems();
+    assert_eq!(problems.len(), 1);
+    assert_eq!(problems[0].0, "world header");
+    assert!(problems[0].1.contains("fields end at"), "{}", problems[0].1);
+
+    // a truncated file is still described as far as it goes
+    fs::write(&path, &bytes[..200]).unwrap();
+    let layout = World::dissect(path.to_str().unwrap()).unwrap();
+    let header = layout.section(Section::Header).unwrap();
+    assert!(header
+        .problem
+        .as_ref()
+        .unwrap()
+        .contains("runs past the end"));
+    assert!(header.field("world_name").is_some());
+}
+
+#[test]
+fn test_dissect_header_fields_match_the_reader() {
+    // distinct values all over the header, so fields read out of order show up
+    let mut world = World::new("fields", "small", "classic", "corruption");
+    world.spawn_point_x = 1234;
+    world.spawn_point_y = 321;
+    world.dungeon_point_x = 777;
+    world.dungeon_point_y = 333;
+    world.game_progression.altars_smashed = 5;
+    world.invasions.invasion_type = 3;
+    world.invasions.cultist_delay = 86400;
+    world.hardmode_ore_1 = 107;
+    world.hardmode_ore_2 = 108;
+    world.hardmode_ore_3 = 111;
+    world.environment.cloud_number = 42;
+    world.angler_daily_quest_target = 17;
+    (world.ore_1, world.ore_2, world.ore_3, world.ore_4) = (166, 167, 168, 169);
+    world.sundial_cooldown = 2;
+    world.moondial_cooldown = 6;
+    let path = temp_world_path("fields.wld");
+    fs::write(&path, world.to_wld_bytes()).unwrap();
+
+    let layout = World::dissect(path.to_str().unwrap()).unwrap();
+    let header = layout.section(Section::Header).unwrap();
+    assert_eq!(
+        header.end,
+        layout.pointer_vector[Section::Tiles.index()] as usize
+    );
+    for (name, value) in [
+        ("spawn_point_x", "1234"),
+        ("spawn_point_y", "321"),
+        ("dungeon_point_x", "777"),
+        ("dungeon_point_y", "333"),
+        ("altars_smashed", "5"),
+        ("invasion_type", "3"),
+        ("cultist_delay", "86400"),
+        ("hardmode_ore_1", "107"),
+        ("hardmode_ore_2", "108"),
+        ("hardmode_ore_3", "111"),
+        ("cloud_number", "42"),
+        ("angler_daily_quest_target", "17"),
+        ("ore_1", "166"),
+        ("ore_2", "167"),
+        ("ore_3", "168"),
+        ("ore_4", "169"),
+        ("sundial_cooldown", "2"),
+        ("moondial_cooldown", "6"),
+    ] {
+        assert_eq!(header.field(name).unwrap().value, value, "{name}");
+    }
+
+    // pointer table entries past the known sections are not the file header
+    let mut bytes = fs::read(&path).unwrap();
+    let count_offset = 24;
+    bytes[count_offset..count_offset + 2].copy_from_slice(&12u16.to_le_bytes());
+    let last = layout.pointer_vector[10].to_le_bytes();
+    bytes.splice(26 + 11 * 4..26 + 11 * 4, last);
+    fs::write(&path, &bytes).unwrap();
+    let layout = World::dissect(path.to_str().unwrap()).unwrap();
+    assert_eq!(layout.sections.len(), 13);
+    assert_eq!(layout.sections[0].name(), "file header");
+    assert_eq!(layout.sections[12].name(), "unknown section");
+}
@@ -0,0 +1,161 @@
+use terraria_world::world::coordinates::Coordinates;
+use terraria_world::world::edit_session::{EditOperation, EditSession};
+use terraria_world::world::error::EditError;
+use terraria_world::world::pressure_plate::WeighedPressurePlate;
+use terraria_world::world::region::Rect;
+use terraria_world::world::tile::Tile;
+use terraria_world::world::World;
+
+#[test]
+fn test_undo_and_redo_edits() {
+    let mut world = World::new("session", "small", "classic", "corruption");
+    world.fill(Rect::new(0, 20, 40, 1), "STONE").unwrap();
+    let original_tiles = world.tiles.tiles[0..40].to_vec();
+
+    let mut session = EditSession::new(&mut world);
+    let floor = Rect::new(0, 18, 40, 3);
+    session
+        .edit("chest", floor, |world| {
+            world.add_chest(10, 18, 0, "loot").unwrap();
+        })
+        .unwrap();
+    session
+        .edit("dig", floor, |world| world.clear(Rect::new(0, 20, 5, 1)))
+        .unwrap();
+    let mut dirt = Tile::new();
+    dirt.set_block_name("DIRT");
+    session.set_tile(30, 10, dirt.clone());
+    assert_eq!(session.journal().len(), 3);
+    assert_eq!(session.world().chests.len(), 1);
+
+    assert_eq!(session.undo().as_deref(), Some("set tile"));
+    assert!(!session.world().tiles.tiles[30][10].has_block());
+    assert_eq!(session.undo().as_deref(), Some("dig"));
+    assert!(session.world().tiles.tiles[2][20].has_block());
+    assert_eq!(session.undo().as_deref(), Some("chest"));
+    assert!(session.world().chests.is_empty());
+    assert!(session.world().chest_at(10, 18).is_none());
+    assert_eq!(session.undo(), None);
+    assert_eq!(session.world().tiles.tiles[0..40], original_tiles[..]);
+
+    assert_eq!(session.redo().as_deref(), Some("chest"));
+    assert_eq!(session.world().chest_at(11, 19).unwrap().name, "loot");
+    // only the changed part of the chest list is recorded, the others keep their place
+    session
+        .edit("second chest", Rect::new(20, 18, 2, 2), |world| {
+            world.add_chest(20, 18, 0, "second").unwrap();
+        })
+        .unwrap();
+    session
+        .edit("take chest", Rect::new(10, 18, 2, 2), |world| {
+            world.remove_chest(10, 18)
+        })
+        .unwrap();
+    assert!(matches!(
+        &session.journal().last().unwrap().operations[..],
+        [.., EditOperation::Chests(splice)] if splice.removed.len() == 1 && splice.inserted.is_empty()
+    ));
+    assert_eq!(session.undo().as_deref(), Some("take chest"));
+    let names: Vec<&str> = session
+        .world()
+        .chests
+        .iter()
+        .map(|c| c.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["loot", "second"]);
+    session.undo();
+    // a new edit drops what could be redone
+    session.set_tile(31, 11, dirt);
+    assert!(!session.can_redo());
+    assert_eq!(session.redo(), None);
+}
+
+#[test]
+fn test_named_transactions_and_journal() {
+    let mut world = World::new("session", "small", "classic", "corruption");
+    let mut session = EditSession::new(&mut world);
+
+    session.begin("build house");
+    session
+        .edit("floor", Rect::new(0, 20, 10, 1), |world| {
+            world.fill(Rect::new(0, 20, 10, 1), "WOOD_BLOCK").unwrap()
+        })
+        .unwrap();
+    session
+        .edit("roof", Rect::new(0, 10, 10, 1), |world| {
+            world.fill(Rect::new(0, 10, 10, 1), "WOOD_BLOCK").unwrap()
+        })
+        .unwrap();
+    session.commit();
+    assert_eq!(session.journal().len(), 1);
+    assert_eq!(session.journal()[0].name, "build house");
+    assert_eq!(session.journal()[0].operations.len(), 20);
+
+    session.begin("mistake");
+    session
+        .edit("wall", Rect::new(0, 11, 1, 9), |world| {
+            world.fill(Rect::new(0, 11, 1, 9), "STONE").unwrap()
+        })
+        .unwrap();
+    session.rollback();
+    assert!(!session.world().tiles.tiles[0][15].has_block());
+    assert_eq!(session.journal().len(), 1);
+
+    let path = std::env::temp_dir().join("terraria_world_journal.json");
+    session
+        .save_journal_as_json(path.to_str().unwrap())
+        .unwrap();
+    let json = std::fs::read_to_string(&path).unwrap();
+    let journal: Vec<terraria_world::world::edit_session::Transaction> =
+        serde_json::from_str(&json).unwrap();
+    assert_eq!(journal, session.journal());
+    assert!(matches!(
+        journal[0].operations[0],
+        EditOperation::Tile { x: 0, y: 20, .. }
+    ));
+
+    assert_eq!(session.undo().as_deref(), Some("build house"));
+    assert!(!session.world().tiles.tiles[5][10].has_block());
+    assert!(!session.world().tiles.tiles[5][20].has_block());
+}
+
+#[test]
+fn test_edits_outside_the_area_are_recorded() {
+    let mut world = World::new("session", "small", "classic", "corruption");
+    world.fill(Rect::new(0, 20, 40, 1), "STONE").unwrap();
+    world.add_chest(10, 18, 0, "loot").unwrap();
+    world
+        .weighed_pressure_plates
+        .push(WeighedPressurePlate::new(Coordinates { x: 10, y: 19 }));
+    let original_tiles = world.tiles.tiles[0..40].to_vec();
+
+    let mut session = EditSession::new(&mut world);
+    // the area only covers the right half of the chest, clear takes it whole
+    session
+        .edit("dig", Rect::new(11, 18, 1, 2), |world| {
+            world.clear(Rect::new(11, 18, 1, 2))
+        })
+        .unwrap();
+    assert!(session.world().chests.is_empty());
+    assert!(session.world().weighed_pressure_plates.is_empty());
+    assert!(!session.world().tiles.tiles[10][18].has_block());
+
+    session.undo();
+    assert_eq!(session.world().tiles.tiles[0..40], original_tiles[..]);
+    assert_eq!(session.world().chest_at(10, 18).unwrap().name, "loot");
+    assert_eq!(session.world().weighed_pressure_plates.len(), 1);
+
+    // changes the session can not see are an error, and end its history
+    session.redo();
+    let result = session.edit("by hand", Rect::new(0, 0, 1, 1), |world| {
+        world
+            .weighed_pressure_plates
+            .push(WeighedPressurePlate::new(Coordinates { x: 0, y: 0 }))
+    });
+    assert_eq!(result, Err(EditError::Unrecorded));
+    assert!(!session.can_undo());
+    let result = session.edit("shrink", Rect::new(0, 0, 1, 1), |world| {
+        world.crop(Rect::new(0, 0, 100, 100))
+    });
+    assert_eq!(result, Err(EditError::Resized));
+}
@@ -0,0 +1,113 @@
+use terraria_world::world::chest::Chest;
+use terraria_world::world::coordinates::Coordinates;
+use terraria_world::world::entity_index::Entity;
+use terraria_world::world::region::Rect;
+use terraria_world::world::sign::SignKind;
+use terraria_world::world::tile_entity::PylonBiome;
+use terraria_world::world::World;
+
+fn furnished_world() -> World {
+    let mut world = World::new("index", "small", "classic", "corruption");
+    world.fill(Rect::new(0, 100, 400, 1), "STONE").unwrap();
+    world.add_chest(10, 98, 0, "first").unwrap();
+    world.add_sign(20, 98, SignKind::Sign, "hello").unwrap();
+    world.place_pylon(200, 96, PylonBiome::Forest).unwrap();
+    world
+}
+
+#[test]
+fn test_entity_at_resolves_every_tile() {
+    let mut world = furnished_world();
+
+    assert!(matches!(world.entity_at(11, 99), Some(Entity::Chest(chest)) if chest.name == "first"));
+    assert!(matches!(world.entity_at(21, 98), Some(Entity::Sign(sign)) if sign.text == "hello"));
+    let pylon = world.entity_at(202, 99).unwrap();
+    assert!(matches!(pylon, Entity::TileEntity(_)));
+    assert_eq!(*pylon.position(), Coordinates { x: 200, y: 96 });
+    assert!(world.entity_at(12, 99).is_none());
+    assert!(world.entity_at(12, 100).is_none());
+
+    // edits through the API keep the index current
+    world.remove_chest(10, 98).unwrap();
+    assert!(world.entity_at(10, 98).is_none());
+    world.add_chest(30, 98, 0, "second").unwrap();
+    assert!(
+        matches!(world.entity_at(31, 98), Some(Entity::Chest(chest)) if chest.name == "second")
+    );
+    world.flip_horizontal(Rect::new(25, 98, 10, 2)).unwrap();
+    assert!(
+        matches!(world.entity_at(28, 99), Some(Entity::Chest(chest)) if chest.name == "second")
+    );
+}
+
+#[test]
+fn test_entities_in_rect_and_hand_edits() {
+    let mut world = furnished_world();
+
+    let found = world.entities_in(Rect::new(0, 0, 100, 200));
+    assert_eq!(found.len(), 2);
+    assert_eq!(world.entities_in(Rect::new(150, 0, 100, 200)).len(), 1);
+    assert!(world.entities_in(Rect::new(11, 98, 5, 5)).is_empty());
+
+    // lists edited by hand need a reindex
+    world.place_object(50, 98, "CONTAINERS", 0).unwrap();
+    world
+        .chests
+        .push(Chest::new(Coordinates { x: 50, y: 98 }, "manual", 40));
+    world.reindex_entities();
+    assert_eq!(world.entities_in(Rect::new(0, 0, 100, 200)).len(), 3);
+    world.chests[0].position = Coordinates { x: 60, y: 60 };
+    world.reindex_entities();
+    assert_eq!(world.entities_in(Rect::new(60, 60, 1, 1)).len(), 1);
+
+    // removing an object through the API shifts the rest of the index
+    world.remove_object(21, 99).unwrap();
+    assert!(world.signs.is_empty());
+    assert!(
+        matches!(world.entity_at(51, 99), Some(Entity::Chest(chest)) if chest.name == "manual")
+    );
+    assert_eq!(world.entities_in(Rect::new(150, 0, 100, 200)).len(), 1);
+}
+
+#[test]
+fn test_index_survives_many_removals_and_loading() {
+    let mut world = World::new("index", "small", "classic", "corruption");
+    world.fill(Rect::new(0, 100, 400, 1), "STONE").unwrap();
+    for i in 0..100 {
+        world
+            .add_chest(i * 3, 98, 0, &format!("chest {i}"))
+            .unwrap();
+    }
+    // every other chest goes, the rest keep their order and stay findable
+    for i in (0..100).step_by(2) {
+        world.remove_chest(i * 3, 98).unwrap();
+    }
+    assert_eq!(world.chests.len(), 50);
+    assert_eq!(world.chests[0].name, "chest 1");
+    for i in (1..100).step_by(2) {
+        assert_eq!(
+            world.chest_at(i * 3 + 1, 99).unwrap().name,
+            format!("chest {i}")
+        );
+        assert!(world.chest_at((i - 1) * 3, 98).is_none());
+    }
+    world.chest_at_mut(3, 98).unwrap().name = "renamed".to_string();
+    assert_eq!(world.chests[0].name, "renamed");
+
+    let path = std::env::temp_dir().join("terraria_world_index.wld");
+    world.save_as_wld(path.to_str().unwrap()).unwrap();
+    let loaded = World::from_file(path.to_str().unwrap()).unwrap();
+    assert_eq!(loaded.chest_at(298, 99).unwrap().name, "chest 99");
+    assert_eq!(loaded.entities_in(Rect::new(0, 0, 400, 200)).len(), 50);
+}
+
+#[test]
+fn test_entities_in_huge_rect_is_clipped() {
+    let world = furnished_world();
+    let everything = Rect::new(i32::MIN, i32::MIN, i32::MAX, i32::MAX);
+    assert!(world.entities_in(everything).is_empty());
+    assert_eq!(
+        world.entities_in(Rect::new(0, 0, i32::MAX, i32::MAX)).len(),
+        3
+    );
+}
@@ -0,0 +1,42 @@
+use terraria_world::world::layer::{pixels_to_tile, tile_to_pixels, Layer};
+use terraria_world::world::World;
+
+#[test]
+fn test_layer_boundaries() {
+    // small worlds: surface at 337, caverns at 451, 1200 rows
+    let world = World::new("layers", "small", "classic", "corruption");
+    assert_eq!(world.layer_at(0), Layer::Space);
+    assert_eq!(world.layer_at(117), Layer::Space);
+    assert_eq!(world.layer_at(118), Layer::Surface);
+    assert_eq!(world.layer_at(337), Layer::Surface);
+    assert_eq!(world.layer_at(338), Layer::Underground);
+    assert_eq!(world.layer_at(451), Layer::Underground);
+    assert_eq!(world.layer_at(452), Layer::Cavern);
+    assert_eq!(world.layer_at(999), Layer::Cavern);
+    assert_eq!(world.layer_at(1000), Layer::Underworld);
+    assert!(Layer::Surface < Layer::Cavern);
+}
+
+#[test]
+fn test_compass_and_depth_readings() {
+    let world = World::new("layers", "small", "classic", "corruption");
+    assert_eq!(world.compass_reading(2100), 0);
+    assert_eq!(world.compass_reading(2150), 100);
+    assert_eq!(world.compass_reading(2000), -200);
+    assert_eq!(world.depth_reading(337), 0);
+    assert_eq!(world.depth_reading(400), 126);
+    assert_eq!(world.depth_reading(300), -74);
+
+    for (x, y) in [(2150, 400), (12, 1150), (4199, 3)] {
+        let (compass, depth) = (world.compass_reading(x), world.depth_reading(y));
+        assert_eq!(world.tile_at_reading(compass, depth), (x, y));
+    }
+    // odd readings fall between two tiles
+    assert_eq!(world.tile_at_reading(101, -73), (2150, 300));
+
+    let guide = &world.npcs[0];
+    let (x, y) = pixels_to_tile(guide.position_x, guide.position_y);
+    assert_eq!((x, y), (2100, 595));
+    assert_eq!(tile_to_pixels(x, y), (33600.0, 9520.0));
+    assert_eq!(pixels_to_tile(-1.0, 15.9), (-1, 0));
+}
@@ -0,0 +1,139 @@
+use terraria_world::world::enums::{BLOCK_DATA, BLOCK_TYPE_NAMES};
+use terraria_world::world::error::PlacementError;
+use terraria_world::world::objects::{object_size, object_styles};
+use terraria_world::world::region::Rect;
+use terraria_world::world::tile::FrameImportantData;
+use terraria_world::world::World;
+
+#[test]
+fn test_place_object_writes_frames() {
+    let mut world = World::new("objects", "small", "classic", "corruption");
+    world.fill(Rect::new(10, 20, 10, 1), "STONE").unwrap();
+
+    let style = &object_styles("CONTAINERS")[1];
+    let placed = world.place_object(10, 18, "CONTAINERS", 1);
+    assert_eq!(placed, Ok(Rect::new(10, 18, 2, 2)));
+    let tiles = &world.tiles.tiles;
+    assert_eq!(tiles[10][18].get_block_name(), "CONTAINERS");
+    assert_eq!(
+        tiles[10][18].block_frame,
+        Some(FrameImportantData::new(style.u, style.v))
+    );
+    assert_eq!(
+        tiles[11][19].block_frame,
+        Some(FrameImportantData::new(style.u + 18, style.v + 18))
+    );
+
+    // a door needs a floor and a ceiling
+    let door = Rect::new(14, 17, 1, 3);
+    assert!(matches!(
+        world.place_object(door.x, door.y, "CLOSED_DOOR", 0),
+        Err(PlacementError::NoAnchor { .. })
+    ));
+    world.fill(Rect::new(14, 16, 1, 1), "STONE").unwrap();
+    assert_eq!(
+        world.place_object(door.x, door.y, "CLOSED_DOOR", 0),
+        Ok(door)
+    );
+    assert_eq!(
+        world.tiles.tiles[14][19].block_frame,
+        Some(FrameImportantData::new(0, 36))
+    );
+}
+
+#[test]
+fn test_place_object_rejections() {
+    let mut world = World::new("objects", "small", "classic", "corruption");
+    world.fill(Rect::new(10, 20, 10, 1), "STONE").unwrap();
+
+    assert_eq!(
+        world.place_object(10, 19, "STONE", 0),
+        Err(PlacementError::UnknownObject("STONE".to_string()))
+    );
+    assert!(matches!(
+        world.place_object(10, 18, "CONTAINERS", 100_000),
+        Err(PlacementError::UnknownStyle { .. })
+    ));
+    assert_eq!(
+        world.place_object(10, 19, "CONTAINERS", 0),
+        Err(PlacementError::Occupied { x: 10, y: 20 })
+    );
+    assert_eq!(
+        world.place_object(-1, 18, "CONTAINERS", 0),
+        Err(PlacementError::OutOfBounds { x: -1, y: 18 })
+    );
+    assert!(matches!(
+        world.place_object(10, 10, "CONTAINERS", 0),
+        Err(PlacementError::NoAnchor { .. })
+    ));
+    // nothing was placed by the failed attempts
+    assert!(!world.tiles.tiles[10][18].has_block());
+}
+
+#[test]
+fn test_place_every_chest_and_dresser() {
+    let mut world = World::new("objects", "small", "classic", "corruption");
+    world.fill(Rect::new(0, 20, 4000, 1), "STONE").unwrap();
+
+    let mut x = 10;
+    for (name, width) in [("CONTAINERS", 2), ("CONTAINERS2", 2), ("DRESSERS", 3)] {
+        let styles = object_styles(name).len();
+        assert!(styles > 0, "{name} has no styles");
+        for style in 0..styles {
+            let placed = world.place_object(x, 18, name, style);
+            assert_eq!(placed, Ok(Rect::new(x, 18, width, 2)), "{name} {style}");
+            assert_eq!(world.object_at(x + 1, 19), placed.ok());
+            x += width + 1;
+        }
+    }
+}
+
+#[test]
+fn test_object_at_finds_every_style() {
+    let mut world = World::new("objects", "small", "classic", "corruption");
+    let (x, y) = (100, 100);
+
+    let mut names: Vec<&str> = BLOCK_DATA
+        .values()
+        .filter(|data| data.framed)
+        .filter_map(|data| BLOCK_TYPE_NAMES.get(&data.id).copied())
+        .collect();
+    names.sort();
+    let mut checked = 0;
+    for name in names {
+        let (width, height) = object_size(name).unwrap();
+        let (width, height) = (width as i32, height as i32);
+        let rect = Rect::new(x, y, width, height);
+        for style in 0..object_styles(name).len() {
+            // a hole with walls in a block of stone holds anything
+            let cage = Rect::new(x - 1, y - 1, width + 2, height + 2);
+            world.clear(cage);
+            world.fill(cage, "STONE").unwrap();
+            world.clear(rect);
+            world.fill_wall(rect, "STONE").unwrap();
+
+            assert_eq!(
+                world.place_object(x, y, name, style),
+                Ok(rect),
+                "{name} {style}"
+            );
+            for tx in rect.x..rect.right() {
+                for ty in rect.y..rect.bottom() {
+                    assert_eq!(
+                        world.object_at(tx, ty),
+                        Some(rect),
+                        "{name} {style} ({tx}, {ty})"
+                    );
+                }
+            }
+            assert_eq!(
+                world.remove_object(x + width - 1, y + height - 1),
+                Some(rect)
+            );
+            assert!(world.tiles_in(rect).all(|(_, _, tile)| !tile.has_block()));
+            assert!(world.tiles.tiles[x as usize - 1][y as usize - 1].has_block());
+            checked += 1;
+        }
+    }
+    assert!(checked > 1000, "only {checked} styles checked");
+}
@@ -0,0 +1,74 @@
+use terraria_world::world::error::PatchError;
+use terraria_world::world::patch::{PatchConflict, WorldPatch};
+use terraria_world::world::region::Rect;
+use terraria_world::world::sign::SignKind;
+use terraria_world::world::World;
+
+fn world_with_floor() -> World {
+    let mut world = World::new("patch", "small", "classic", "corruption");
+    world.fill(Rect::new(0, 20, 40, 1), "STONE").unwrap();
+    world.add_sign(6, 18, SignKind::Sign, "welcome").unwrap();
+    world
+}
+
+fn fixed_world() -> World {
+    let mut world = world_with_floor();
+    world.fill(Rect::new(50, 30, 5, 2), "STONE").unwrap();
+    world.add_chest(10, 18, 0, "reward").unwrap();
+    world.sign_at_mut(6, 18).unwrap().text = "welcome back".to_string();
+    world
+}
+
+#[test]
+fn test_patch_applies_map_fixes_and_keeps_progress() {
+    let patch = WorldPatch::between(&world_with_floor(), &fixed_world());
+    let path = std::env::temp_dir().join("terraria_world_patch_test.json");
+    let path = path.to_str().unwrap();
+    patch.save_as_json(path).unwrap();
+    let patch = WorldPatch::read_from_json(path).unwrap();
+    std::fs::remove_file(path).unwrap();
+
+    let mut player_copy = world_with_floor();
+    player_copy.game_progression.defeated_plantera = true;
+    player_copy.apply_patch(&patch).unwrap();
+
+    assert!(player_copy.game_progression.defeated_plantera);
+    assert!(player_copy.diff(&fixed_world()).tiles.is_empty());
+    assert_eq!(player_copy.chest_at(10, 18).unwrap().name, "reward");
+    assert_eq!(player_copy.sign_at(6, 18).unwrap().text, "welcome back");
+    // applying it again changes nothing
+    player_copy.apply_patch(&patch).unwrap();
+    assert_eq!(player_copy.chests.len(), 1);
+}
+
+#[test]
+fn test_conflicts_leave_the_world_untouched() {
+    let patch = WorldPatch::between(&world_with_floor(), &fixed_world());
+    let mut player_copy = world_with_floor();
+    player_copy.fill(Rect::new(52, 31, 1, 1), "DIRT").unwrap();
+    player_copy.sign_at_mut(6, 18).unwrap().text = "mine now".to_string();
+
+    let Err(PatchError::Conflicts(conflicts)) = player_copy.apply_patch(&patch) else {
+        panic!("expected conflicts");
+    };
+    assert_eq!(
+        conflicts,
+        vec![
+            PatchConflict::Tiles {
+                rect: Rect::new(50, 30, 5, 2),
+                conflicting: 1,
+            },
+            PatchConflict::Sign {
+                position: player_copy.sign_at(6, 18).unwrap().position.clone(),
+            },
+        ]
+    );
+    assert!(!player_copy.tiles.tiles[50][30].has_block());
+    assert!(player_copy.chests.is_empty());
+
+    let mut other_size = World::new("patch", "medium", "classic", "corruption");
+    assert!(matches!(
+        other_size.apply_patch(&patch),
+        Err(PatchError::SizeMismatch { .. })
+    ));
+}
@@ -0,0 +1,81 @@
+use terraria_world::world::enums::LiquidType;
+use terraria_world::world::region::Rect;
+use terraria_world::world::World;
+
+fn world_with_lava_pool() -> World {
+    let mut world = World::new("query", "small", "classic", "corruption");
+    world
+        .fill(Rect::new(100, 1000, 20, 5), "HELLSTONE")
+        .unwrap();
+    world.fill(Rect::new(100, 200, 20, 5), "HELLSTONE").unwrap();
+    world.fill_liquid(Rect::new(100, 1000, 10, 5), LiquidType::Lava, 255);
+    world.fill_liquid(Rect::new(110, 1000, 10, 5), LiquidType::Lava, 100);
+    world
+}
+
+#[test]
+fn test_query_combines_predicates() {
+    let world = world_with_lava_pool();
+    let cavern = world.cavern_level as i32;
+    assert_eq!(world.query().block("HELLSTONE").count(), 200);
+    assert_eq!(
+        world.query().block("HELLSTONE").below_y(cavern).count(),
+        100
+    );
+    assert_eq!(
+        world
+            .query()
+            .block("HELLSTONE")
+            .below_y(cavern)
+            .with_liquid(LiquidType::Lava)
+            .liquid_amount(200..)
+            .count(),
+        50
+    );
+    assert_eq!(
+        world
+            .query()
+            .block("HELLSTONE")
+            .above_y(cavern)
+            .in_rect(Rect::new(118, 0, 10, 1200))
+            .coordinates()
+            .len(),
+        10
+    );
+    assert_eq!(world.query().below_y(5000).count(), 0);
+}
+
+#[test]
+fn test_query_walls_wires_shapes_and_frames() {
+    let mut world = World::new("query", "small", "classic", "corruption");
+    world.fill(Rect::new(0, 20, 40, 1), "STONE").unwrap();
+    world.fill_wall(Rect::new(0, 10, 4, 4), "WOOD").unwrap();
+    world.tiles.tiles[3][20].block_shape = 2;
+    world.tiles.tiles[3][20].block_paint = Some(5);
+    world.tiles.tiles[7][15].red_wire = true;
+    world.tiles.tiles[8][15].yellow_wire = true;
+    world.add_chest(10, 18, 0, "loot").unwrap();
+
+    assert_eq!(world.query().wall("WOOD").count(), 16);
+    assert_eq!(
+        world
+            .query()
+            .has_wall(true)
+            .in_rect(Rect::new(0, 10, 2, 2))
+            .count(),
+        4
+    );
+    assert_eq!(world.query().shape(2).coordinates(), vec![(3, 20)]);
+    assert_eq!(world.query().block_paint(Some(5)).count(), 1);
+    assert_eq!(world.query().red_wire().coordinates(), vec![(7, 15)]);
+    assert_eq!(world.query().any_wire().count(), 2);
+    assert_eq!(world.query().framed().count(), 4);
+    assert_eq!(world.query().frame(0, 0).coordinates(), vec![(10, 18)]);
+    assert_eq!(
+        world
+            .query()
+            .filter(|tile| tile.get_block_name() == "CONTAINERS")
+            .count(),
+        4
+    );
+}
@@ -0,0 +1,137 @@
+use std::fs;
+use std::path::PathBuf;
+use terraria_world::reader::ByteReader;
+use terraria_world::world::sections::Section;
+use terraria_world::world::World;
+
+fn temp_world_path(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("terraria-world-tests-{}", std::process::id()));
+    fs::create_dir_all(&dir).expect("Failed to create temp directory");
+    dir.join(name)
+}
+
+// Section offsets from the pointer table right after the file header
+fn section_pointers(bytes: &[u8]) -> Vec<usize> {
+    let count = u16::from_le_bytes([bytes[24], bytes[25]]) as usize;
+    (0..count)
+        .map(|i| {
+            let start = 26 + i * 4;
+            u32::from_le_bytes(bytes[start..start + 4].try_into().unwrap()) as usize
+        })
+        .collect()
+}
+
+fn saved_world(name: &str) -> (World, Vec<u8>) {
+    let mut world = World::new(name, "small", "classic", "corruption");
+    world.set_block(3, 300, "STONE");
+    world.set_block(4000, 300, "STONE");
+    let bytes = world.to_wld_bytes();
+    (world, bytes)
+}
+
+#[test]
+fn test_recover_intact_world() {
+    let (world, bytes) = saved_world("intact");
+    let path = temp_world_path("intact.wld");
+    fs::write(&path, &bytes).unwrap();
+
+    let (recovered, report) = World::recover(path.to_str().unwrap()).expect("Failed to recover");
+    assert!(report.is_complete(), "{:?}", report.lost_sections);
+    assert_eq!(report.tile_columns_recovered, 4200);
+    assert_eq!(recovered.npcs.len(), world.npcs.len());
+    assert_eq!(recovered.to_wld_bytes(), bytes);
+}
+
+#[test]
+fn test_recover_truncated_world() {
+    let (_, bytes) = saved_world("truncated");
+    let pointers = section_pointers(&bytes);
+    let tiles_length = pointers[Section::Chests.index()] - pointers[Section::Tiles.index()];
+    let cut = pointers[Section::Tiles.index()] + tiles_length / 2;
+
+    let path = temp_world_path("truncated.wld");
+    fs::write(&path, &bytes[..cut]).unwrap();
+
+    let (recovered, report) = World::recover(path.to_str().unwrap()).expect("Failed to recover");
+    for lost in &report.lost_sections {
+        println!("{lost}");
+    }
+    assert!(report.is_lost(Section::Tiles));
+    assert!(report.is_lost(Section::Npcs));
+    assert!(report.is_lost(Section::Footer));
+    assert!(report.tile_columns_recovered > 0 && report.tile_columns_recovered < 4200);
+    assert_eq!(recovered.world_name, "truncated");
+    assert_eq!(recovered.tiles.size, (4200, 1200));
+    assert_eq!(recovered.tiles.tiles[3][300].get_block_name(), "STONE");
+    assert!(!recovered.tiles.tiles[4000][300].has_block());
+    assert!(recovered.npcs.is_empty());
+
+    // loading it normally fails instead of panicking
+    let error = World::from_file(path.to_str().unwrap()).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn test_recover_skips_corrupted_section() {
+    let (world, mut bytes) = saved_world("corrupted");
+    let pointers = section_pointers(&bytes);
+    // an NPC record claims a name far longer than the file
+    let npcs = pointers[Section::Npcs.index()];
+    bytes[npcs + 9..npcs + 14].copy_from_slice(&[0xFF, 0xFF, 0xFF, 0xFF, 0x0F]);
+
+    let path = temp_world_path("corrupted.wld");
+    fs::write(&path, &bytes).unwrap();
+
+    let (recovered, report) = World::recover(path.to_str().unwrap()).expect("Failed to recover");
+    let lost: Vec<Section> = report.lost_sections.iter().map(|l| l.section).collect();
+    assert_eq!(lost, vec![Section::Npcs]);
+    assert!(recovered.npcs.is_empty());
+    assert_eq!(recovered.tiles.tiles[4000][300].get_block_name(), "STONE");
+    assert_eq!(recovered.bestiary.kills.len(), world.bestiary.kills.len());
+}
+
+#[test]
+fn test_overlong_string_length_is_an_error() {
+    // a length prefix close to u64::MAX must not overflow the bounds check
+    let prefix = [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01];
+    let error = ByteReader::new(&prefix).try_string(None).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::UnexpectedEof);
+
+    let (_, mut bytes) = saved_world("overlong");
+    let pointers = section_pointers(&bytes);
+    let npcs = pointers[Section::Npcs.index()];
+    bytes[npcs + 9..npcs + 19].copy_from_slice(&prefix);
+    let path = temp_world_path("overlong.wld");
+    fs::write(&path, &bytes).unwrap();
+
+    let (_, report) = World::recover(path.to_str().unwrap()).expect("Failed to recover");
+    assert!(report.is_lost(Section::Npcs));
+}
+
+#[test]
+fn test_impossible_world_size_is_an_error() {
+    let (_, mut bytes) = saved_world("negative");
+    let path = temp_world_path("negative.wld");
+    fs::write(&path, &bytes).unwrap();
+    let layout = World::dissect(path.to_str().unwrap()).unwrap();
+    let height = layout
+        .section(Section::Header)
+        .unwrap()
+        .field("world_height")
+        .unwrap()
+        .offset;
+    bytes[height..height + 4].copy_from_slice(&(-1i32).to_le_bytes());
+    fs::write(&path, &bytes).unwrap();
+
+    // an error rather than a capacity overflow panic
+    let error = World::from_file(path.to_str().unwrap()).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    assert!(World::recover(path.to_str().unwrap()).is_err());
+
+    // sizes far beyond the largest world are refused before allocating tiles
+    bytes[height..height + 4].copy_from_slice(&60_000i32.to_le_bytes());
+    fs::write(&path, &bytes).unwrap();
+    let error = World::from_file(path.to_str().unwrap()).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    assert!(World::recover(path.to_str().unwrap()).is_err());
+}
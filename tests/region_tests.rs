@@ -0,0 +1,143 @@
+use terraria_world::world::coordinates::Coordinates;
+use terraria_world::world::enums::LiquidType;
+use terraria_world::world::error::FillError;
+use terraria_world::world::pressure_plate::WeighedPressurePlate;
+use terraria_world::world::region::Rect;
+use terraria_world::world::World;
+
+#[test]
+fn test_rect_geometry() {
+    let rect = Rect::from_corners(5, 8, 2, 3);
+    assert_eq!(rect, Rect::new(2, 3, 4, 6));
+    assert_eq!(rect.area(), 24);
+    assert!(rect.contains(5, 8));
+    assert!(!rect.contains(6, 8));
+    assert_eq!(
+        rect.intersect(&Rect::new(4, 0, 10, 4)),
+        Some(Rect::new(4, 3, 2, 1))
+    );
+    assert_eq!(rect.intersect(&Rect::new(6, 0, 1, 1)), None);
+
+    // extreme corners and sizes saturate instead of overflowing
+    let huge = Rect::from_corners(i32::MIN, i32::MIN, i32::MAX, i32::MAX);
+    assert_eq!(huge, Rect::new(i32::MIN, i32::MIN, i32::MAX, i32::MAX));
+    assert_eq!(Rect::new(i32::MAX, 0, i32::MAX, 1).right(), i32::MAX);
+    assert_eq!(
+        Rect::new(0, 0, i32::MAX, i32::MAX).intersect(&Rect::new(i32::MAX, 0, -5, 1)),
+        None
+    );
+    let world = World::new("region", "small", "classic", "corruption");
+    assert_eq!(
+        world.clip(Rect::new(1, 1, i32::MAX, i32::MAX)),
+        Some(Rect::new(1, 1, 4199, 1199))
+    );
+}
+
+#[test]
+fn test_fill_rejects_unknown_names_and_resets_coatings() {
+    let mut world = World::new("region", "small", "classic", "corruption");
+    let rect = Rect::new(0, 0, 2, 2);
+    assert_eq!(
+        world.fill(rect, "NOT_A_BLOCK"),
+        Err(FillError::UnknownBlock("NOT_A_BLOCK".to_string()))
+    );
+    assert_eq!(
+        world.replace(rect, "STONE", "NOT_A_BLOCK"),
+        Err(FillError::UnknownBlock("NOT_A_BLOCK".to_string()))
+    );
+    assert_eq!(
+        world.fill_wall(rect, "NOT_A_WALL"),
+        Err(FillError::UnknownWall("NOT_A_WALL".to_string()))
+    );
+
+    world.fill(rect, "DIRT").unwrap();
+    for (_, _, tile) in world.tiles_in_mut(rect) {
+        tile.block_paint = Some(3);
+        tile.block_illuminant = true;
+        tile.block_echo = true;
+    }
+    assert_eq!(world.fill(rect, "STONE"), Ok(4));
+    assert!(world
+        .tiles_in(rect)
+        .all(|(_, _, tile)| tile.block_paint.is_none()
+            && !tile.block_illuminant
+            && !tile.block_echo));
+}
+
+#[test]
+fn test_region_edits_clip_to_world() {
+    let mut world = World::new("region", "small", "classic", "corruption");
+    let corner = Rect::new(-5, -5, 10, 10);
+
+    assert_eq!(world.fill(corner, "STONE"), Ok(25));
+    assert_eq!(world.tiles_in(corner).count(), 25);
+    assert!(world
+        .tiles_in(corner)
+        .all(|(x, y, tile)| x < 5 && y < 5 && tile.get_block_name() == "STONE"));
+    // filling again changes nothing
+    assert_eq!(world.fill(corner, "STONE"), Ok(0));
+
+    assert_eq!(world.replace(world.bounds(), "STONE", "DIRT"), Ok(25));
+    assert_eq!(world.tiles.tiles[4][4].get_block_name(), "DIRT");
+
+    assert_eq!(world.fill_wall(Rect::new(0, 0, 2, 2), "STONE"), Ok(4));
+    assert_eq!(
+        world.fill_liquid(Rect::new(0, 0, 1, 3), LiquidType::Water, 255),
+        3
+    );
+    assert!(world.tiles.tiles[0][2].has_liquid());
+
+    for (x, y, tile) in world.tiles_in_mut(Rect::new(0, 0, 1, 1)) {
+        assert_eq!((x, y), (0, 0));
+        tile.red_wire = true;
+    }
+    assert!(world.tiles.tiles[0][0].red_wire);
+
+    // out of the world entirely
+    assert_eq!(world.fill(Rect::new(5000, 0, 10, 10), "STONE"), Ok(0));
+    assert_eq!(world.tiles_in(Rect::new(0, -20, 5, 10)).count(), 0);
+}
+
+#[test]
+fn test_clear_removes_containers() {
+    let mut world = World::new("region", "small", "classic", "corruption");
+    world.fill(Rect::new(100, 102, 6, 1), "STONE").unwrap();
+    world.add_chest(100, 100, 0, "").unwrap();
+    world
+        .weighed_pressure_plates
+        .push(WeighedPressurePlate::new(Coordinates { x: 103, y: 101 }));
+
+    // framed blocks only go in through place_object
+    let framed = Err(FillError::FramedBlock("CONTAINERS".to_string()));
+    assert_eq!(world.fill(Rect::new(104, 100, 2, 2), "CONTAINERS"), framed);
+    assert_eq!(
+        world.replace(Rect::new(100, 102, 6, 1), "STONE", "CONTAINERS"),
+        framed
+    );
+
+    // the rect only covers the right half of the chest
+    assert_eq!(world.clear(Rect::new(101, 100, 3, 2)), 4);
+    assert!(world.chests.is_empty());
+    assert!(world.weighed_pressure_plates.is_empty());
+    assert!(!world.tiles.tiles[100][100].has_block());
+    assert!(world.tiles.tiles[100][102].has_block());
+}
+
+#[test]
+fn test_fill_removes_objects_whole() {
+    let mut world = World::new("region", "small", "classic", "corruption");
+    world.fill(Rect::new(100, 102, 6, 1), "STONE").unwrap();
+    world.add_chest(100, 100, 0, "").unwrap();
+    world
+        .weighed_pressure_plates
+        .push(WeighedPressurePlate::new(Coordinates { x: 100, y: 101 }));
+
+    // the right half of the chest is filled, the left half goes with it
+    assert_eq!(world.fill(Rect::new(101, 100, 1, 2), "DIRT"), Ok(4));
+    assert!(world.chests.is_empty());
+    assert!(world.weighed_pressure_plates.is_empty());
+    assert!(!world.tiles.tiles[100][100].has_block());
+    assert!(!world.tiles.tiles[100][101].has_block());
+    assert_eq!(world.tiles.tiles[101][100].get_block_name(), "DIRT");
+    assert!(world.validate().is_empty());
+}
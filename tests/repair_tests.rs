@@ -0,0 +1,117 @@
+use terraria_world::world::chest::Chest;
+use terraria_world::world::coordinates::Coordinates;
+use terraria_world::world::item::ItemStack;
+use terraria_world::world::region::Rect;
+use terraria_world::world::repair::{RepairAction, RepairOptions};
+use terraria_world::world::sign::Sign;
+use terraria_world::world::World;
+
+#[test]
+fn test_repair_fixes_validation_issues() {
+    let mut world = World::new("repair", "small", "classic", "corruption");
+    world.chests_max_items = 40;
+    world.set_block(100, 100, "CONTAINERS");
+    for _ in 0..2 {
+        world.chests.push(Chest {
+            position: Coordinates { x: 100, y: 100 },
+            name: "loot".to_string(),
+            contents: vec![None; 20],
+        });
+    }
+    world.signs.push(Sign {
+        text: "hello".to_string(),
+        position: Coordinates { x: 5000, y: 10 },
+    });
+    world.npcs[0].position_x = -500.0;
+    world.npcs[0].is_homeless = false;
+    world.npcs[0].home = Coordinates { x: 10, y: 9000 };
+    world.mob_kills.truncate(10);
+    world.tile_frame_important.truncate(600);
+    assert!(!world.validate().is_empty());
+
+    let log = world.repair(&RepairOptions::default());
+    for action in &log {
+        println!("{action}");
+    }
+    assert!(log.contains(&RepairAction::RemovedDuplicateChest {
+        position: Coordinates { x: 100, y: 100 },
+    }));
+    assert!(log.contains(&RepairAction::RemovedSign {
+        position: Coordinates { x: 5000, y: 10 },
+    }));
+    assert!(log.contains(&RepairAction::MovedNpcHome {
+        name: world.npcs[0].name.clone(),
+        from: Coordinates { x: 10, y: 9000 },
+        to: Coordinates { x: 10, y: 1199 },
+    }));
+    assert!(log.contains(&RepairAction::RebuiltRooms {
+        removed: 0,
+        added: 1,
+    }));
+
+    assert_eq!(world.chests.len(), 1);
+    assert_eq!(world.chests[0].contents.len(), 40);
+    assert_eq!(world.npcs[0].position_x, 0.0);
+    assert_eq!(
+        world.tile_frame_important,
+        World::default_tile_frame_important()
+    );
+    let issues = world.validate();
+    assert!(issues.is_empty(), "Unexpected issues: {issues:?}");
+
+    // a repaired world has nothing left to repair
+    assert!(world.repair(&RepairOptions::default()).is_empty());
+}
+
+#[test]
+fn test_repair_respects_options() {
+    let mut world = World::new("repair", "small", "classic", "corruption");
+    world.mob_kills.truncate(10);
+    world.signs.push(Sign {
+        text: String::new(),
+        position: Coordinates { x: 1, y: 1 },
+    });
+
+    let options = RepairOptions {
+        remove_orphans: false,
+        ..RepairOptions::default()
+    };
+    let log = world.repair(&options);
+    assert_eq!(
+        log,
+        vec![RepairAction::ResizedMobKills { from: 10, to: 688 }]
+    );
+    assert_eq!(world.signs.len(), 1);
+}
+
+#[test]
+fn test_repair_moves_chests_to_the_top_left_tile() {
+    let mut world = World::new("repair", "small", "classic", "corruption");
+    world.fill(Rect::new(0, 100, 40, 1), "STONE").unwrap();
+    world.add_chest(10, 98, 0, "loot").unwrap();
+    world.chests[0].contents[0] = Some(ItemStack {
+        quantity: 1,
+        type_id: 4,
+        prefix: 0,
+    });
+    world.chests[0].position = Coordinates { x: 11, y: 99 };
+    // no chest tile anywhere near this one
+    world
+        .chests
+        .push(Chest::new(Coordinates { x: 30, y: 98 }, "lost", 40));
+
+    let log = world.repair(&RepairOptions::default());
+    assert!(log.contains(&RepairAction::MovedChest {
+        name: "loot".to_string(),
+        from: Coordinates { x: 11, y: 99 },
+        to: Coordinates { x: 10, y: 98 },
+    }));
+    assert!(log.contains(&RepairAction::RemovedChest {
+        position: Coordinates { x: 30, y: 98 },
+        name: "lost".to_string(),
+    }));
+    assert_eq!(world.chests.len(), 1);
+    let chest = world.chest_at(11, 99).unwrap();
+    assert_eq!(chest.position, Coordinates { x: 10, y: 98 });
+    assert_eq!(chest.contents[0].as_ref().unwrap().type_id, 4);
+}
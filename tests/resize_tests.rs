@@ -0,0 +1,95 @@
+use terraria_world::world::error::ResizeError;
+use terraria_world::world::region::Rect;
+use terraria_world::world::resize::Anchor;
+use terraria_world::world::sign::SignKind;
+use terraria_world::world::tile::Tile;
+use terraria_world::world::World;
+
+fn arena_world() -> World {
+    let mut world = World::new("resize", "small", "classic", "corruption");
+    world.fill(Rect::new(1000, 400, 100, 1), "STONE").unwrap();
+    world.add_chest(1010, 398, 0, "kept").unwrap();
+    // straddles the right edge of the arena
+    world.add_chest(1049, 398, 0, "cut").unwrap();
+    world
+        .add_sign(1080, 398, SignKind::Sign, "outside")
+        .unwrap();
+    world
+}
+
+#[test]
+fn test_crop_moves_and_drops_positional_data() {
+    let mut world = arena_world();
+    let guide = world.npcs[0].clone();
+    world.spawn_point_x = 1020;
+    world.spawn_point_y = 390;
+
+    let arena = Rect::new(1000, 300, 50, 120);
+    assert_eq!(world.crop(arena), Some(arena));
+
+    assert_eq!((world.world_width, world.world_height), (50, 120));
+    assert_eq!(world.tiles.size, (50, 120));
+    assert_eq!(world.bounds_vec, vec![0, 50 * 16, 0, 120 * 16]);
+    assert_eq!((world.spawn_point_x, world.spawn_point_y), (20, 90));
+    assert_eq!(world.underground_level, 37.0);
+    assert!(world.tiles.tiles[0][100].has_block());
+
+    assert_eq!(world.chests.len(), 1);
+    assert_eq!(world.chest_at(10, 98).unwrap().name, "kept");
+    assert!(world.signs.is_empty());
+    // the guide stood in the middle of the world
+    assert!(!world.npcs.iter().any(|npc| npc.name == guide.name));
+    assert!(world.validate().is_empty(), "{:?}", world.validate());
+
+    assert_eq!(world.crop(Rect::new(100, 0, 10, 10)), None);
+}
+
+#[test]
+fn test_resize_grows_and_shrinks_around_the_anchor() {
+    let mut world = World::new("resize", "small", "classic", "corruption");
+    world.crop(Rect::new(0, 0, 100, 60));
+    world.fill(Rect::new(0, 50, 100, 1), "STONE").unwrap();
+    world.add_sign(10, 48, SignKind::Tombstone, "rip").unwrap();
+
+    let mut dirt = Tile::new();
+    dirt.set_block_name("DIRT");
+    world.resize(120, 80, Anchor::Bottom, &dirt).unwrap();
+    assert_eq!(world.tiles.size, (120, 80));
+    // 10 new columns on each side, 20 new rows on top
+    assert!(world.tiles.tiles[10][70].has_block());
+    assert_eq!(world.tiles.tiles[0][70].block_id, dirt.block_id);
+    assert_eq!(world.tiles.tiles[10][19].block_id, dirt.block_id);
+    assert!(!world.tiles.tiles[10][20].has_block());
+    assert_eq!(world.sign_at(20, 68).unwrap().text, "rip");
+
+    world.resize(100, 60, Anchor::Bottom, &dirt).unwrap();
+    assert_eq!(world.tiles.size, (100, 60));
+    assert!(world.tiles.tiles[0][50].has_block());
+    assert_eq!(world.sign_at(10, 48).unwrap().text, "rip");
+    assert!(world.validate().is_empty(), "{:?}", world.validate());
+
+    for (width, height) in [(0, 60), (100, 0), (100_000, 60), (100, usize::MAX)] {
+        assert_eq!(
+            world.resize(width, height, Anchor::Center, &dirt),
+            Err(ResizeError::InvalidSize { width, height })
+        );
+    }
+    assert_eq!(world.tiles.size, (100, 60));
+}
+
+#[test]
+fn test_crop_removes_cut_objects_and_moves_separators() {
+    let mut world = arena_world();
+    world.environment.tree_style_separators = vec![500, 1020, 3000];
+    world.environment.moss_style_separators = vec![1010, 1040, 1060];
+
+    world.crop(Rect::new(1000, 300, 50, 120));
+    // the left half of the cut chest is gone with its chest
+    assert!(!world.tiles.tiles[49][98].has_block());
+    assert!(!world.tiles.tiles[49][99].has_block());
+    assert!(world.tiles.tiles[49][100].has_block());
+    assert_eq!(world.chests.len(), 1);
+    assert_eq!(world.environment.tree_style_separators, vec![0, 20, 50]);
+    assert_eq!(world.environment.moss_style_separators, vec![10, 40, 50]);
+    assert!(world.validate().is_empty(), "{:?}", world.validate());
+}
@@ -0,0 +1,69 @@
+use terraria_world::world::block_ids;
+use terraria_world::world::layer::Layer;
+use terraria_world::world::region::Rect;
+use terraria_world::world::World;
+
+#[test]
+fn test_resource_report_counts_by_layer_and_tier() {
+    let mut world = World::new("resources", "small", "classic", "corruption");
+    assert!(world.resource_report().resources.is_empty());
+
+    // ore_1 is copper, hardmode ores are not chosen yet
+    world.fill(Rect::new(100, 200, 5, 2), "COPPER").unwrap();
+    world.fill(Rect::new(100, 400, 4, 5), "COPPER").unwrap();
+    world.fill(Rect::new(100, 700, 3, 3), "DIAMOND").unwrap();
+    world.fill(Rect::new(100, 800, 10, 10), "STONE").unwrap();
+
+    let report = world.resource_report();
+    assert_eq!(report.resources.len(), 2);
+    assert_eq!(report.total(block_ids::COPPER), 30);
+    assert_eq!(report.total(block_ids::DIAMOND), 9);
+    assert_eq!(report.total(block_ids::STONE), 0);
+    let copper = &report.resources[0];
+    assert_eq!(copper.by_layer.get(&Layer::Surface), Some(&10));
+    assert_eq!(copper.by_layer.get(&Layer::Underground), Some(&20));
+    assert_eq!(copper.by_layer.get(&Layer::Cavern), None);
+
+    assert_eq!(report.tiers.len(), 7);
+    assert_eq!(report.tiers[0].tier, "ore_1");
+    assert_eq!(report.tiers[0].block_id, Some(block_ids::COPPER));
+    assert_eq!(report.tiers[0].total, 30);
+    assert_eq!(report.tiers[1].block_id, Some(block_ids::IRON));
+    assert_eq!(report.tiers[1].total, 0);
+    assert_eq!(report.tiers[4].block_id, None);
+}
+
+#[test]
+fn test_deposits_near_spawn() {
+    // spawn is at (2100, 600)
+    let mut world = World::new("resources", "small", "classic", "corruption");
+    world.fill(Rect::new(2110, 600, 3, 3), "GOLD").unwrap();
+    // touching, but a different ore
+    world.fill(Rect::new(2113, 600, 1, 1), "IRON").unwrap();
+    // only touches the first deposit diagonally
+    world.fill(Rect::new(2113, 603, 2, 2), "GOLD").unwrap();
+    world
+        .fill(Rect::new(2200, 600, 10, 10), "PLATINUM")
+        .unwrap();
+    world
+        .fill(Rect::new(3000, 600, 20, 20), "TITANIUM")
+        .unwrap();
+
+    let report = world.resource_report();
+    assert_eq!(report.deposits.len(), 5);
+    let nearest = &report.deposits[0];
+    assert_eq!(nearest.block_id, block_ids::GOLD);
+    assert_eq!(nearest.size, 9);
+    assert_eq!(nearest.bounds, Rect::new(2110, 600, 3, 3));
+    assert_eq!(nearest.center, (2111, 601));
+
+    let richest = report.richest_deposits(2, 200.0);
+    assert_eq!(richest.len(), 2);
+    assert_eq!(richest[0].block_id, block_ids::PLATINUM);
+    assert_eq!(richest[0].size, 100);
+    assert_eq!(richest[1].size, 9);
+    assert_eq!(
+        report.richest_deposits(1, 1000.0)[0].block_id,
+        block_ids::TITANIUM
+    );
+}
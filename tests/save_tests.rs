@@ -0,0 +1,134 @@
+use std::fs;
+use std::path::PathBuf;
+use terraria_world::world::region::Rect;
+use terraria_world::world::save::{backup_path, timestamped_backup_paths, SaveOptions};
+use terraria_world::world::sections::Section;
+use terraria_world::world::World;
+
+fn temp_world_path(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("terraria-world-tests-{}", std::process::id()));
+    fs::create_dir_all(&dir).expect("Failed to create temp directory");
+    dir.join(name)
+}
+
+#[test]
+fn test_unchanged_world_saves_byte_identical() {
+    let path = temp_world_path("unchanged.wld");
+    let world = World::new("unchanged", "small", "classic", "corruption");
+    world
+        .save_as_wld(path.to_str().unwrap())
+        .expect("Failed to save world");
+
+    let loaded = World::from_file(path.to_str().unwrap()).expect("Failed to load world");
+    assert!(loaded.dirty_sections().is_empty());
+    assert!(loaded.dirty_columns().is_empty());
+
+    let original = fs::read(&path).expect("Failed to read saved world");
+    assert_eq!(loaded.to_wld_bytes(), original);
+}
+
+#[test]
+fn test_incremental_save_only_rewrites_dirty_data() {
+    let path = temp_world_path("incremental.wld");
+    let world = World::new("incremental", "small", "classic", "corruption");
+    world
+        .save_as_wld(path.to_str().unwrap())
+        .expect("Failed to save world");
+
+    let mut loaded = World::from_file(path.to_str().unwrap()).expect("Failed to load world");
+    loaded.set_block(10, 500, "STONE");
+    // edits by hand are only saved once marked
+    loaded.spawn_point_x += 1;
+    assert_eq!(loaded.dirty_sections(), vec![Section::Tiles]);
+    loaded.mark_dirty(Section::Header);
+
+    assert_eq!(loaded.dirty_columns(), vec![10]);
+    assert_eq!(
+        loaded.dirty_sections(),
+        vec![Section::Header, Section::Tiles]
+    );
+
+    let out_path = temp_world_path("incremental.out.wld");
+    loaded
+        .save_as_wld(out_path.to_str().unwrap())
+        .expect("Failed to save world");
+    let reloaded = World::from_file(out_path.to_str().unwrap()).expect("Failed to reload world");
+    assert_eq!(reloaded.tiles.tiles[10][500].get_block_name(), "STONE");
+    assert_eq!(reloaded.spawn_point_x, loaded.spawn_point_x);
+    assert!(reloaded.dirty_sections().is_empty());
+
+    // lists edited through the API are marked too
+    let mut reloaded = reloaded;
+    reloaded.fill(Rect::new(0, 600, 4, 1), "STONE").unwrap();
+    reloaded.add_chest(0, 598, 0, "loot").unwrap();
+    assert_eq!(reloaded.dirty_columns(), vec![0, 1, 2, 3]);
+    assert!(reloaded.dirty_sections().contains(&Section::Chests));
+    assert!(!reloaded.dirty_sections().contains(&Section::Npcs));
+}
+
+#[test]
+fn test_save_keeps_backups() {
+    let path = temp_world_path("backups.wld");
+    let path_str = path.to_str().unwrap();
+    let mut world = World::new("backups", "small", "classic", "corruption");
+
+    let options = SaveOptions {
+        backup: true,
+        timestamped_backups: 2,
+    };
+    world.spawn_point_x = 1;
+    world.save_as_wld_with_options(path_str, &options).unwrap();
+    assert!(!backup_path(&path).exists());
+
+    let first_save = fs::read(&path).unwrap();
+    world.spawn_point_x = 2;
+    world.save_as_wld_with_options(path_str, &options).unwrap();
+    assert_eq!(fs::read(backup_path(&path)).unwrap(), first_save);
+
+    for spawn_point_x in 3..6 {
+        world.spawn_point_x = spawn_point_x;
+        world.save_as_wld_with_options(path_str, &options).unwrap();
+    }
+    assert_eq!(timestamped_backup_paths(&path).unwrap().len(), 2);
+    assert_eq!(World::from_file(path_str).unwrap().spawn_point_x, 5);
+
+    // no temporary files are left behind
+    let leftovers = fs::read_dir(path.parent().unwrap())
+        .unwrap()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_string_lossy().ends_with(".tmp"))
+        .count();
+    assert_eq!(leftovers, 0);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_save_keeps_permissions_and_cleans_up_on_backup_failure() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let path = temp_world_path("permissions").join("permissions.wld");
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let path_str = path.to_str().unwrap();
+    let world = World::new("permissions", "small", "classic", "corruption");
+    world.save_as_wld(path_str).unwrap();
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o640)).unwrap();
+    world.save_as_wld(path_str).unwrap();
+    assert_eq!(
+        fs::metadata(&path).unwrap().permissions().mode() & 0o777,
+        0o640
+    );
+
+    // a directory in the way of the backup makes copying it fail
+    fs::create_dir_all(backup_path(&path)).unwrap();
+    let before = fs::read(&path).unwrap();
+    assert!(world
+        .save_as_wld_with_options(path_str, &SaveOptions::with_backup())
+        .is_err());
+    assert_eq!(fs::read(&path).unwrap(), before);
+    let leftovers = fs::read_dir(path.parent().unwrap())
+        .unwrap()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_string_lossy().ends_with(".tmp"))
+        .count();
+    assert_eq!(leftovers, 0);
+}
@@ -0,0 +1,212 @@
+use terraria_world::world::chest::Chest;
+use terraria_world::world::coordinates::Coordinates;
+use terraria_world::world::error::PasteError;
+use terraria_world::world::item::ItemStack;
+use terraria_world::world::pressure_plate::WeighedPressurePlate;
+use terraria_world::world::region::Rect;
+use terraria_world::world::schematic::{PasteOptions, Schematic};
+use terraria_world::world::sign::Sign;
+use terraria_world::world::tile_entity::{LogicSensorKind, PylonBiome};
+use terraria_world::world::validation::MAX_CHESTS;
+use terraria_world::world::World;
+
+fn build(world: &mut World) {
+    world.fill(Rect::new(100, 100, 6, 1), "STONE").unwrap();
+    world.fill_wall(Rect::new(100, 95, 6, 5), "STONE").unwrap();
+    world.place_object(101, 98, "CONTAINERS", 0).unwrap();
+    world.chests.push(Chest {
+        position: Coordinates { x: 101, y: 98 },
+        name: "loot".to_string(),
+        contents: Vec::new(),
+    });
+    world.place_object(104, 98, "SIGNS", 0).unwrap();
+    world.signs.push(Sign {
+        text: "welcome".to_string(),
+        position: Coordinates { x: 104, y: 98 },
+    });
+    world
+        .place_logic_sensor(103, 99, LogicSensorKind::Day)
+        .unwrap();
+}
+
+#[test]
+fn test_copy_and_paste_between_worlds() {
+    let mut source = World::new("source", "small", "classic", "corruption");
+    build(&mut source);
+    let schematic = source.copy(Rect::new(100, 95, 6, 6));
+    assert_eq!((schematic.width(), schematic.height()), (6, 6));
+    assert_eq!(schematic.chests[0].position, Coordinates { x: 1, y: 3 });
+    assert_eq!(schematic.signs[0].position, Coordinates { x: 4, y: 3 });
+
+    // schematics survive a serde roundtrip
+    let json = serde_json::to_string(&schematic).unwrap();
+    let schematic: Schematic = serde_json::from_str(&json).unwrap();
+
+    let mut target = World::new("target", "small", "classic", "corruption");
+    target.fill(Rect::new(1, 5, 3, 1), "STONE").unwrap();
+    target.place_pylon(1, 1, PylonBiome::Forest).unwrap();
+    let changed = target
+        .paste(&schematic, 2000, 400, &PasteOptions::default())
+        .unwrap();
+    assert!(changed > 0);
+
+    assert_eq!(target.tiles.tiles[2001][403].get_block_name(), "CONTAINERS");
+    assert_eq!(target.chests[0].position, Coordinates { x: 2001, y: 403 });
+    assert_eq!(target.chests[0].name, "loot");
+    assert_eq!(target.signs[0].text, "welcome");
+    // pasted tile entities get an id that is not in use yet
+    assert_eq!(target.tile_entities.len(), 2);
+    assert_eq!(target.tile_entities[1].id, 1);
+    assert_eq!(
+        target.tile_entities[1].position,
+        Coordinates { x: 2003, y: 404 }
+    );
+    assert!(target.validate().is_empty(), "{:?}", target.validate());
+}
+
+#[test]
+fn test_paste_options() {
+    let mut source = World::new("source", "small", "classic", "corruption");
+    source.fill(Rect::new(10, 10, 1, 3), "STONE").unwrap();
+    let schematic = source.copy(Rect::new(10, 10, 2, 3));
+
+    let mut target = World::new("target", "small", "classic", "corruption");
+    target.fill(Rect::new(50, 50, 2, 3), "DIRT").unwrap();
+    target.fill_wall(Rect::new(50, 50, 2, 3), "DIRT").unwrap();
+
+    // without overwriting, the existing dirt stays
+    let keep = PasteOptions {
+        overwrite: false,
+        ..PasteOptions::default()
+    };
+    assert_eq!(target.paste(&schematic, 50, 50, &keep), Ok(0));
+
+    // skipping air leaves the second column alone, keeping walls leaves the dirt walls
+    let options = PasteOptions {
+        skip_air: true,
+        keep_walls: true,
+        overwrite: true,
+    };
+    assert_eq!(target.paste(&schematic, 50, 50, &options), Ok(3));
+    assert_eq!(target.tiles.tiles[50][50].get_block_name(), "STONE");
+    assert_eq!(target.tiles.tiles[50][50].get_wall_name(), "DIRT");
+    assert_eq!(target.tiles.tiles[51][50].get_block_name(), "DIRT");
+
+    // pasting partly outside the world is clipped
+    assert_eq!(
+        target.paste(&schematic, 0, -1, &PasteOptions::default()),
+        Ok(2)
+    );
+
+    // entries outside the schematic are left behind
+    let mut schematic = schematic;
+    schematic.chests.push(Chest {
+        position: Coordinates { x: -1, y: 0 },
+        name: "outside".to_string(),
+        contents: Vec::new(),
+    });
+    target
+        .paste(&schematic, 60, 60, &PasteOptions::default())
+        .unwrap();
+    assert!(target.chests.is_empty());
+}
+
+#[test]
+fn test_objects_are_never_split() {
+    let mut source = World::new("source", "small", "classic", "corruption");
+    build(&mut source);
+    // the rect only has the right half of the chest
+    let schematic = source.copy(Rect::new(102, 95, 4, 6));
+    assert!(schematic.chests.is_empty());
+    assert!(!schematic.tiles.tiles[0][3].has_block());
+    assert_eq!(schematic.tiles.tiles[0][3].get_wall_name(), "STONE");
+    assert_eq!(schematic.signs.len(), 1);
+
+    let mut target = World::new("target", "small", "classic", "corruption");
+    target.fill(Rect::new(10, 20, 4, 1), "STONE").unwrap();
+    target.add_chest(10, 18, 0, "loot").unwrap();
+    target
+        .weighed_pressure_plates
+        .push(WeighedPressurePlate::new(Coordinates { x: 10, y: 19 }));
+    let mut dirt = World::new("dirt", "small", "classic", "corruption");
+    dirt.fill(Rect::new(0, 0, 1, 2), "DIRT").unwrap();
+
+    // pasting over the right half of the chest takes the left half too
+    let changed = target.paste(
+        &dirt.copy(Rect::new(0, 0, 1, 2)),
+        11,
+        18,
+        &PasteOptions::default(),
+    );
+    assert_eq!(changed, Ok(4));
+    assert!(!target.tiles.tiles[10][18].has_block());
+    assert_eq!(target.tiles.tiles[11][18].get_block_name(), "DIRT");
+    assert!(target.chests.is_empty());
+    assert!(target.weighed_pressure_plates.is_empty());
+    assert!(target.validate().is_empty(), "{:?}", target.validate());
+}
+
+#[test]
+fn test_paste_rejects_bad_schematics_and_limits() {
+    let mut source = World::new("source", "small", "classic", "corruption");
+    build(&mut source);
+    let schematic = source.copy(Rect::new(100, 95, 6, 6));
+    let mut target = World::new("target", "small", "classic", "corruption");
+
+    // tiles that do not match the size are refused instead of read past
+    let mut wide = schematic.clone();
+    wide.tiles.size.0 = 7;
+    assert_eq!(
+        target.paste(&wide, 10, 10, &PasteOptions::default()),
+        Err(PasteError::ColumnCount {
+            expected: 7,
+            found: 6
+        })
+    );
+    let mut short = schematic.clone();
+    short.tiles.tiles[2].pop();
+    assert_eq!(
+        target.paste(&short, 10, 10, &PasteOptions::default()),
+        Err(PasteError::ColumnLength {
+            column: 2,
+            expected: 6,
+            found: 5
+        })
+    );
+
+    // chests get the world's slots, items past them are dropped
+    let mut full = schematic.clone();
+    let item = ItemStack {
+        quantity: 1,
+        type_id: 8,
+        prefix: 0,
+    };
+    full.chests[0].contents = vec![Some(item.clone()); 50];
+    target.chests_max_items = 40;
+    target
+        .paste(&full, 10, 10, &PasteOptions::default())
+        .unwrap();
+    assert_eq!(target.chests[0].contents, vec![Some(item); 40]);
+
+    // pasting the same chest again replaces it, one more would go over the limit
+    let mut crowded = World::new("crowded", "small", "classic", "corruption");
+    crowded.chests = (0..MAX_CHESTS as i32)
+        .map(|i| Chest {
+            position: Coordinates {
+                x: i % 4000,
+                y: 800 + i / 4000 * 2,
+            },
+            name: String::new(),
+            contents: Vec::new(),
+        })
+        .collect();
+    assert_eq!(
+        crowded.paste(&schematic, 10, 10, &PasteOptions::default()),
+        Err(PasteError::LimitReached {
+            object: "chests".to_string(),
+            max: MAX_CHESTS
+        })
+    );
+    assert!(!crowded.tiles.tiles[10][10].has_wall());
+    assert_eq!(crowded.chests.len(), MAX_CHESTS);
+}
@@ -0,0 +1,76 @@
+use terraria_world::world::error::{PlacementError, SignTextError};
+use terraria_world::world::region::Rect;
+use terraria_world::world::sign::{Sign, SignKind};
+use terraria_world::world::World;
+
+#[test]
+fn test_add_find_and_remove_signs() {
+    let mut world = World::new("signs", "small", "classic", "corruption");
+    world.fill(Rect::new(10, 20, 20, 1), "STONE").unwrap();
+
+    world
+        .add_sign(10, 18, SignKind::Sign, "Welcome to level 1")
+        .unwrap();
+    world
+        .add_sign(14, 18, SignKind::Tombstone, "Here lies the guide")
+        .unwrap();
+    world
+        .add_sign(18, 18, SignKind::Sign, "Welcome to level 2")
+        .unwrap();
+    assert!(world.validate().is_empty());
+
+    assert_eq!(world.sign_at(15, 19).unwrap().text, "Here lies the guide");
+    assert!(world.sign_at(16, 19).is_none());
+    assert_eq!(world.tiles.tiles[15][19].get_block_name(), "TOMBSTONES");
+
+    let welcome: Vec<&str> = world
+        .find_signs("Welcome to level ?")
+        .iter()
+        .map(|sign| sign.text.as_str())
+        .collect();
+    assert_eq!(welcome, vec!["Welcome to level 1", "Welcome to level 2"]);
+    assert_eq!(world.find_signs("*guide").len(), 1);
+    assert_eq!(world.find_signs("*").len(), 3);
+    assert!(world.find_signs("level").is_empty());
+
+    let sign = world.sign_at_mut(11, 18).unwrap();
+    sign.set_text("Go right\nthen down").unwrap();
+    assert_eq!(world.sign_at(10, 18).unwrap().text, "Go right\nthen down");
+
+    let removed = world.remove_sign(19, 19).unwrap();
+    assert_eq!(removed.text, "Welcome to level 2");
+    assert_eq!(world.signs.len(), 2);
+    assert!(!world.tiles.tiles[18][18].has_block());
+}
+
+#[test]
+fn test_sign_text_limits() {
+    let mut world = World::new("signs", "small", "classic", "corruption");
+    world.fill(Rect::new(10, 20, 20, 1), "STONE").unwrap();
+
+    let long = "a".repeat(1201);
+    assert_eq!(
+        Sign::validate_text(&long),
+        Err(SignTextError::TooLong {
+            length: 1201,
+            max: 1200
+        })
+    );
+    let tall = "line\n".repeat(10);
+    assert_eq!(
+        Sign::validate_text(&tall),
+        Err(SignTextError::TooManyLines { lines: 11, max: 10 })
+    );
+    assert!(Sign::validate_text(&"a".repeat(1200)).is_ok());
+
+    assert!(matches!(
+        world.add_sign(10, 18, SignKind::Sign, &long),
+        Err(PlacementError::InvalidText(_))
+    ));
+    // nothing is placed for a rejected text
+    assert!(!world.tiles.tiles[10][18].has_block());
+
+    let sign = world.add_sign(10, 18, SignKind::Sign, "short").unwrap();
+    assert!(sign.set_text(&tall).is_err());
+    assert_eq!(sign.text, "short");
+}
@@ -0,0 +1,78 @@
+use terraria_world::world::chest::Chest;
+use terraria_world::world::coordinates::Coordinates;
+use terraria_world::world::item::ItemStack;
+use terraria_world::world::region::Rect;
+use terraria_world::world::schematic::{PasteOptions, Schematic};
+use terraria_world::world::sign::Sign;
+use terraria_world::world::tedit::{MIN_TEDIT_SCHEMATIC_VERSION, TEDIT_SCHEMATIC_VERSION};
+use terraria_world::world::World;
+
+#[test]
+fn test_tedit_schematic_roundtrip() {
+    let mut world = World::new("tedit", "small", "classic", "corruption");
+    world.fill(Rect::new(10, 10, 8, 1), "STONE").unwrap();
+    world.fill_wall(Rect::new(10, 5, 8, 5), "DIRT").unwrap();
+    world.place_object(11, 8, "CONTAINERS", 0).unwrap();
+    world.place_object(15, 8, "SIGNS", 0).unwrap();
+    world.tiles.tiles[12][9].red_wire = true;
+    world.tiles.tiles[14][9].block_shape = 2;
+    world.chests.push(Chest {
+        position: Coordinates { x: 11, y: 8 },
+        name: "stash".to_string(),
+        contents: vec![Some(ItemStack {
+            quantity: 5,
+            type_id: 8,
+            prefix: 0,
+        })],
+    });
+    world.signs.push(Sign {
+        text: "hi".to_string(),
+        position: Coordinates { x: 15, y: 8 },
+    });
+
+    let mut schematic = world.copy(Rect::new(10, 5, 8, 6));
+    schematic.name = "hut".to_string();
+    let bytes = schematic.to_tedit_bytes();
+    let loaded = Schematic::from_tedit_bytes(&bytes).expect("Failed to read schematic");
+
+    assert_eq!(loaded.name, "hut");
+    assert_eq!(loaded.tiles.size, (8, 6));
+    assert_eq!(loaded.tiles.tiles, schematic.tiles.tiles);
+    assert_eq!(loaded.chests[0].contents.len(), 40);
+    assert_eq!(
+        loaded.chests[0].contents[0],
+        schematic.chests[0].contents[0]
+    );
+    assert_eq!(loaded.signs, schematic.signs);
+    assert_eq!(loaded.to_tedit_bytes(), bytes);
+
+    let mut target = World::new("target", "small", "classic", "corruption");
+    target
+        .paste(&loaded, 100, 100, &PasteOptions::default())
+        .unwrap();
+    assert_eq!(target.tiles.tiles[101][103].get_block_name(), "CONTAINERS");
+    assert_eq!(target.chests[0].position, Coordinates { x: 101, y: 103 });
+}
+
+#[test]
+fn test_tedit_rejects_garbage() {
+    let schematic =
+        World::new("tedit", "small", "classic", "corruption").copy(Rect::new(0, 0, 3, 3));
+    let bytes = schematic.to_tedit_bytes();
+    assert!(Schematic::from_tedit_bytes(&bytes[..bytes.len() - 3]).is_err());
+    assert!(Schematic::from_tedit_bytes(b"not a schematic").is_err());
+
+    // the version follows the length-prefixed name
+    let mut old = bytes.clone();
+    let at = 1 + schematic.name.len();
+    old[at..at + 4].copy_from_slice(&(MIN_TEDIT_SCHEMATIC_VERSION - 1).to_le_bytes());
+    let error = Schematic::from_tedit_bytes(&old).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    assert!(error.to_string().contains("older"), "{error}");
+
+    let mut new = bytes.clone();
+    new[at..at + 4].copy_from_slice(&(TEDIT_SCHEMATIC_VERSION + 1).to_le_bytes());
+    let error = Schematic::from_tedit_bytes(&new).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    assert!(error.to_string().contains("newer"), "{error}");
+}
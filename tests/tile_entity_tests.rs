@@ -0,0 +1,78 @@
+use terraria_world::world::coordinates::Coordinates;
+use terraria_world::world::error::PlacementError;
+use terraria_world::world::item::ItemStack;
+use terraria_world::world::region::Rect;
+use terraria_world::world::tile::FrameImportantData;
+use terraria_world::world::tile_entity::{LogicSensorKind, PylonBiome, TileEntityExtra};
+use terraria_world::world::World;
+
+fn sword() -> ItemStack {
+    ItemStack {
+        quantity: 1,
+        type_id: 4,
+        prefix: 0,
+    }
+}
+
+#[test]
+fn test_place_museum_pieces() {
+    let mut world = World::new("museum", "small", "classic", "corruption");
+    world.fill(Rect::new(10, 30, 40, 1), "STONE").unwrap();
+    world.fill_wall(Rect::new(10, 20, 40, 10), "DIRT").unwrap();
+
+    world.place_item_frame(10, 22, sword()).unwrap();
+    world.place_mannequin(13, 27).unwrap();
+    world.place_weapon_rack(16, 22, sword()).unwrap();
+    world.place_hat_rack(20, 26).unwrap();
+    world.place_food_plate(24, 29, sword()).unwrap();
+    world
+        .place_logic_sensor(26, 25, LogicSensorKind::Night)
+        .unwrap();
+    world.place_pylon(28, 26, PylonBiome::Ocean).unwrap();
+    let dummy = world.place_training_dummy(32, 27).unwrap();
+    assert!(matches!(
+        dummy.extra,
+        Some(TileEntityExtra::TargetDummy { npc: -1 })
+    ));
+
+    let ids: Vec<i32> = world.tile_entities.iter().map(|entity| entity.id).collect();
+    assert_eq!(ids, (0..8).collect::<Vec<i32>>());
+    assert!(world.validate().is_empty(), "{:?}", world.validate());
+
+    assert_eq!(world.tiles.tiles[26][25].get_block_name(), "LOGIC_SENSOR");
+    assert_eq!(
+        world.tiles.tiles[26][25].block_frame,
+        Some(FrameImportantData::new(0, 18))
+    );
+    assert_eq!(
+        world.tiles.tiles[30][29].block_frame,
+        Some(FrameImportantData::new(216 + 36, 54))
+    );
+    assert_eq!(
+        world.tile_entities[6].position,
+        Coordinates { x: 28, y: 26 }
+    );
+}
+
+#[test]
+fn test_tile_entity_placement_checks() {
+    let mut world = World::new("museum", "small", "classic", "corruption");
+    world.fill(Rect::new(10, 30, 10, 1), "STONE").unwrap();
+
+    // no wall behind it
+    assert!(matches!(
+        world.place_item_frame(10, 20, sword()),
+        Err(PlacementError::NoAnchor { .. })
+    ));
+    // no floor under it
+    assert!(matches!(
+        world.place_mannequin(10, 20),
+        Err(PlacementError::NoAnchor { .. })
+    ));
+    world.place_training_dummy(10, 27).unwrap();
+    assert!(matches!(
+        world.place_training_dummy(11, 27),
+        Err(PlacementError::Occupied { .. })
+    ));
+    assert_eq!(world.tile_entities.len(), 1);
+}
@@ -0,0 +1,163 @@
+use terraria_world::world::chest::Chest;
+use terraria_world::world::coordinates::Coordinates;
+use terraria_world::world::error::TransformError;
+use terraria_world::world::pressure_plate::WeighedPressurePlate;
+use terraria_world::world::region::Rect;
+use terraria_world::world::tile::FrameImportantData;
+use terraria_world::world::World;
+
+#[test]
+fn test_flip_horizontal_fixes_slopes_and_furniture() {
+    let mut world = World::new("flip", "small", "classic", "corruption");
+    world.fill(Rect::new(0, 10, 10, 1), "STONE").unwrap();
+    world.tiles.tiles[0][9].block_id = world.tiles.tiles[0][10].block_id;
+    world.tiles.tiles[0][9].block_active = true;
+    world.tiles.tiles[0][9].block_shape = 2;
+    world.tiles.tiles[1][9].block_id = world.tiles.tiles[0][10].block_id;
+    world.tiles.tiles[1][9].block_shape = 1;
+    world.place_object(2, 8, "CHAIRS", 0).unwrap();
+    world.place_object(4, 8, "BEDS", 0).unwrap();
+    world.place_object(8, 8, "CONTAINERS", 1).unwrap();
+    world.chests.push(Chest {
+        position: Coordinates { x: 8, y: 8 },
+        name: "loot".to_string(),
+        contents: vec![None; 40],
+    });
+    let original = world.copy(Rect::new(0, 8, 10, 3));
+
+    let mut schematic = original.clone();
+    schematic.flip_horizontal();
+    let tiles = &schematic.tiles.tiles;
+    assert_eq!(tiles[9][1].block_shape, 3);
+    assert_eq!(tiles[8][1].block_shape, 1);
+    // the chair faces the other way
+    assert_eq!(
+        tiles[7][0].block_frame,
+        Some(FrameImportantData::new(18, 0))
+    );
+    // the bed faces the other way and its head stays at its end
+    let bed: Vec<u16> = (2..6)
+        .map(|x| tiles[x][0].block_frame.as_ref().unwrap().x)
+        .collect();
+    assert_eq!(bed, vec![72, 90, 108, 126]);
+    // the chest keeps its style and its frames stay in order
+    assert_eq!(
+        tiles[0][0].block_frame,
+        Some(FrameImportantData::new(36, 0))
+    );
+    assert_eq!(
+        tiles[1][0].block_frame,
+        Some(FrameImportantData::new(54, 0))
+    );
+    assert_eq!(schematic.chests[0].position, Coordinates { x: 0, y: 0 });
+
+    schematic.flip_horizontal();
+    assert_eq!(schematic.tiles.tiles, original.tiles.tiles);
+    assert_eq!(schematic.chests, original.chests);
+}
+
+#[test]
+fn test_rotate_turns_slopes_and_swaps_size() {
+    let mut world = World::new("rotate", "small", "classic", "corruption");
+    world.fill(Rect::new(0, 0, 3, 1), "STONE").unwrap();
+    world.tiles.tiles[0][0].block_shape = 2;
+    let original = world.copy(Rect::new(0, 0, 3, 2));
+
+    let mut schematic = original.clone();
+    schematic.rotate_clockwise();
+    assert_eq!(schematic.tiles.size, (2, 3));
+    assert_eq!(schematic.tiles.tiles[1][0].block_shape, 4);
+    assert!(!schematic.tiles.tiles[0][0].has_block());
+
+    schematic.rotate_counterclockwise();
+    assert_eq!(schematic.tiles.tiles, original.tiles.tiles);
+    for _ in 0..4 {
+        schematic.rotate_clockwise();
+    }
+    assert_eq!(schematic.tiles.tiles, original.tiles.tiles);
+}
+
+#[test]
+fn test_world_region_transforms() {
+    let mut world = World::new("region", "small", "classic", "corruption");
+    world.fill(Rect::new(10, 10, 4, 1), "STONE").unwrap();
+    world.fill(Rect::new(10, 11, 1, 1), "WOOD_BLOCK").unwrap();
+
+    let covered = world.flip_horizontal(Rect::new(10, 10, 4, 2));
+    assert_eq!(covered, Ok(Rect::new(10, 10, 4, 2)));
+    assert_eq!(world.tiles.tiles[13][11].get_block_name(), "WOOD_BLOCK");
+    assert!(!world.tiles.tiles[10][11].has_block());
+
+    let covered = world.rotate_clockwise(Rect::new(10, 10, 4, 2));
+    assert_eq!(covered, Ok(Rect::new(10, 10, 2, 4)));
+    assert_eq!(world.tiles.tiles[10][13].get_block_name(), "WOOD_BLOCK");
+    assert_eq!(world.tiles.tiles[11][10].get_block_name(), "STONE");
+    assert!(!world.tiles.tiles[13][10].has_block());
+
+    assert_eq!(
+        world.flip_vertical(Rect::new(-10, -10, 5, 5)),
+        Err(TransformError::OutsideWorld)
+    );
+}
+
+#[test]
+fn test_region_transforms_keep_neighbouring_objects() {
+    let mut world = World::new("region", "small", "classic", "corruption");
+    world.fill(Rect::new(10, 20, 10, 1), "STONE").unwrap();
+    world.add_chest(12, 18, 0, "loot").unwrap();
+    let before = world.copy(Rect::new(10, 15, 10, 6));
+
+    // the chest sticks out of the region on the right
+    assert_eq!(
+        world.flip_horizontal(Rect::new(10, 18, 3, 3)),
+        Err(TransformError::SplitsObject(Rect::new(12, 18, 2, 2)))
+    );
+    // the turned region would cover half of the chest
+    assert_eq!(
+        world.rotate_clockwise(Rect::new(11, 16, 4, 2)),
+        Err(TransformError::SplitsObject(Rect::new(12, 18, 2, 2)))
+    );
+    assert_eq!(
+        world.copy(Rect::new(10, 15, 10, 6)).tiles.tiles,
+        before.tiles.tiles
+    );
+    assert_eq!(world.chests.len(), 1);
+
+    // whole objects move along
+    assert_eq!(
+        world.flip_horizontal(Rect::new(10, 18, 6, 3)),
+        Ok(Rect::new(10, 18, 6, 3))
+    );
+    assert_eq!(world.chest_at(12, 18).unwrap().name, "loot");
+    assert!(world.validate().is_empty(), "{:?}", world.validate());
+}
+
+#[test]
+fn test_region_transforms_move_weighed_pressure_plates() {
+    let mut world = World::new("region", "small", "classic", "corruption");
+    world.fill(Rect::new(10, 20, 10, 1), "STONE").unwrap();
+    world.set_block(11, 19, "WEIGHTED_PRESSURE_PLATE");
+    world
+        .weighed_pressure_plates
+        .push(WeighedPressurePlate::new(Coordinates { x: 11, y: 19 }));
+
+    world.flip_horizontal(Rect::new(10, 19, 6, 2)).unwrap();
+    assert_eq!(
+        world.tiles.tiles[14][19].get_block_name(),
+        "WEIGHTED_PRESSURE_PLATE"
+    );
+    assert_eq!(
+        world.weighed_pressure_plates,
+        vec![WeighedPressurePlate::new(Coordinates { x: 14, y: 19 })]
+    );
+
+    world.rotate_clockwise(Rect::new(10, 19, 6, 2)).unwrap();
+    assert!(world.tiles.tiles[10][22].has_block());
+    assert_eq!(world.weighed_pressure_plates.len(), 1);
+    let plate = &world.weighed_pressure_plates[0].position;
+    assert_eq!(
+        world.tiles.tiles[plate.x as usize][plate.y as usize].get_block_name(),
+        "WEIGHTED_PRESSURE_PLATE"
+    );
+    assert!(world.validate().is_empty(), "{:?}", world.validate());
+}
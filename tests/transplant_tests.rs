@@ -0,0 +1,74 @@
+use terraria_world::world::coordinates::Coordinates;
+use terraria_world::world::pressure_plate::WeighedPressurePlate;
+use terraria_world::world::region::Rect;
+use terraria_world::world::room::Room;
+use terraria_world::world::sign::SignKind;
+use terraria_world::world::World;
+
+fn old_world_with_base() -> World {
+    let mut world = World::new("old", "small", "classic", "corruption");
+    world.fill(Rect::new(500, 300, 30, 1), "STONE").unwrap();
+    world
+        .fill_wall(Rect::new(500, 290, 30, 10), "WOOD")
+        .unwrap();
+    world.add_chest(502, 298, 0, "base loot").unwrap();
+    world.add_sign(506, 298, SignKind::Sign, "home").unwrap();
+    world.place_training_dummy(510, 297).unwrap();
+    world
+        .weighed_pressure_plates
+        .push(WeighedPressurePlate::new(Coordinates { x: 515, y: 299 }));
+    world
+        .rooms
+        .push(Room::new(22, Coordinates { x: 520, y: 298 }));
+    world
+}
+
+#[test]
+fn test_transplant_moves_tiles_and_entities() {
+    let old = old_world_with_base();
+    let mut fresh = World::new("fresh", "small", "classic", "corruption");
+    // ids the base's tile entities would collide with
+    fresh.fill(Rect::new(0, 20, 10, 1), "STONE").unwrap();
+    fresh.place_training_dummy(0, 17).unwrap();
+    let taken = fresh.tile_entities[0].id;
+
+    let target = fresh
+        .transplant(&old, Rect::new(500, 290, 30, 11), 1000, 390)
+        .unwrap();
+    assert_eq!(target, Some(Rect::new(1000, 390, 30, 11)));
+
+    assert!(fresh.tiles.tiles[1000][400].has_block());
+    assert_eq!(fresh.chest_at(1002, 398).unwrap().name, "base loot");
+    assert_eq!(fresh.sign_at(1006, 398).unwrap().text, "home");
+    let ids: Vec<i32> = fresh.tile_entities.iter().map(|e| e.id).collect();
+    assert_eq!(ids.len(), 2);
+    assert!(ids[1] != taken);
+    assert_eq!(
+        fresh.weighed_pressure_plates[0].position,
+        Coordinates { x: 1015, y: 399 }
+    );
+    assert_eq!(fresh.rooms[0].position, Coordinates { x: 1020, y: 398 });
+    // the guide moves into the transplanted room
+    assert!(!fresh.npcs[0].is_homeless);
+    assert_eq!(fresh.npcs[0].home, Coordinates { x: 1020, y: 398 });
+    assert!(fresh.validate().is_empty(), "{:?}", fresh.validate());
+}
+
+#[test]
+fn test_transplant_is_clipped_to_both_worlds() {
+    let old = old_world_with_base();
+    let mut fresh = World::new("fresh", "small", "classic", "corruption");
+    // only the left 10 columns fit
+    let target = fresh
+        .transplant(&old, Rect::new(500, 290, 30, 11), 4190, 100)
+        .unwrap();
+    assert_eq!(target, Some(Rect::new(4190, 100, 10, 11)));
+    assert_eq!(fresh.chests.len(), 1);
+    assert_eq!(fresh.signs.len(), 1);
+    assert!(fresh.tile_entities.is_empty());
+    assert!(fresh.weighed_pressure_plates.is_empty());
+    assert_eq!(
+        fresh.transplant(&old, Rect::new(-50, 0, 10, 10), 0, 0),
+        Ok(None)
+    );
+}
@@ -0,0 +1,55 @@
+use terraria_world::world::chest::Chest;
+use terraria_world::world::coordinates::Coordinates;
+use terraria_world::world::sign::Sign;
+use terraria_world::world::validation::ValidationIssue;
+use terraria_world::world::World;
+
+#[test]
+fn test_new_world_is_valid() {
+    let world = World::new("valid", "small", "classic", "corruption");
+    let issues = world.validate();
+    assert!(issues.is_empty(), "Unexpected issues: {issues:?}");
+}
+
+#[test]
+fn test_validation_reports_inconsistencies() {
+    let mut world = World::new("invalid", "small", "classic", "corruption");
+    world.chests_max_items = 40;
+    world.chests.push(Chest {
+        position: Coordinates { x: 100, y: 100 },
+        name: String::new(),
+        contents: vec![None; 40],
+    });
+    for _ in 0..2 {
+        world.signs.push(Sign {
+            text: "hello".to_string(),
+            position: Coordinates { x: 5000, y: 10 },
+        });
+    }
+    world.mob_kills.truncate(10);
+    world.bounds_vec[1] = 0;
+
+    let issues = world.validate();
+    for issue in &issues {
+        println!("{issue}");
+    }
+    assert!(issues.contains(&ValidationIssue::ChestNotOnChestTile {
+        index: 0,
+        position: Coordinates { x: 100, y: 100 },
+    }));
+    assert!(issues.contains(&ValidationIssue::SignOutOfBounds {
+        index: 1,
+        position: Coordinates { x: 5000, y: 10 },
+    }));
+    assert!(issues.contains(&ValidationIssue::DuplicateSignPosition {
+        position: Coordinates { x: 5000, y: 10 },
+        indices: vec![0, 1],
+    }));
+    assert!(issues.contains(&ValidationIssue::MobKillsLength {
+        length: 10,
+        expected: 688,
+    }));
+    assert!(issues
+        .iter()
+        .any(|issue| matches!(issue, ValidationIssue::BoundsMismatch { .. })));
+}
@@ -0,0 +1,85 @@
+use terraria_world::world::region::Rect;
+use terraria_world::world::world_status::DryadDescription;
+use terraria_world::world::World;
+
+#[test]
+fn test_world_status_counts_like_the_game() {
+    let mut world = World::new("status", "small", "classic", "corruption");
+    assert_eq!(world.world_status().description, DryadDescription::Pure);
+
+    // 100 surface tiles count 500, 400 underground tiles count 400
+    world.fill(Rect::new(0, 100, 100, 1), "STONE").unwrap();
+    world.fill(Rect::new(0, 800, 100, 4), "STONE").unwrap();
+    world.fill(Rect::new(0, 801, 90, 1), "EBONSTONE").unwrap();
+    world.fill(Rect::new(0, 802, 20, 1), "CRIMSTONE").unwrap();
+    // not part of the alignment counts at all
+    world.fill(Rect::new(0, 900, 100, 10), "DIRT").unwrap();
+    world
+        .fill(Rect::new(0, 910, 100, 10), "CORRUPT_HARDENED_SAND")
+        .unwrap();
+
+    let status = world.world_status();
+    assert_eq!(status.total_solid, 900);
+    assert_eq!(status.total_evil, 90);
+    assert_eq!(status.total_blood, 20);
+    assert_eq!((status.good, status.evil, status.blood), (0, 10, 2));
+    assert_eq!(status.description, DryadDescription::Work);
+    assert!(!status.near_threshold);
+
+    let (corruption, crimson, hallow) = world.get_corruption_stats();
+    assert_eq!((corruption, crimson, hallow), (10.0, 20.0 / 9.0, 0.0));
+}
+
+#[test]
+fn test_dryad_descriptions_and_thresholds() {
+    assert_eq!(
+        DryadDescription::from_percentages(10, 5, 6),
+        DryadDescription::Balanced
+    );
+    assert_eq!(
+        DryadDescription::from_percentages(20, 5, 6),
+        DryadDescription::FairyTale
+    );
+    assert_eq!(
+        DryadDescription::from_percentages(2, 20, 5),
+        DryadDescription::Grim
+    );
+    assert_eq!(
+        DryadDescription::from_percentages(0, 3, 1),
+        DryadDescription::Close
+    );
+    assert_eq!(
+        DryadDescription::from_percentages(0, 6, 0),
+        DryadDescription::Work
+    );
+
+    // 5.4% evil is Close, 5.6% would round to 6% and be Work
+    let mut world = World::new("status", "small", "classic", "corruption");
+    world.fill(Rect::new(0, 800, 500, 1), "STONE").unwrap();
+    world.fill(Rect::new(0, 800, 27, 1), "EBONSTONE").unwrap();
+    let status = world.world_status();
+    assert_eq!(status.evil, 5);
+    assert_eq!(status.description, DryadDescription::Close);
+    assert!(status.near_threshold);
+}
+
+#[test]
+fn test_world_status_counts_jungle_and_golf_grass() {
+    let mut world = World::new("status", "small", "classic", "corruption");
+    let row = Rect::new(0, 800, 10, 1);
+    for (block, good, evil, blood) in [
+        ("GOLF_GRASS_HALLOWED", 10, 0, 0),
+        ("CORRUPT_JUNGLE_GRASS", 0, 10, 0),
+        ("CRIMSON_JUNGLE_GRASS", 0, 0, 10),
+        ("MOWED_GOLF_GRASS", 0, 0, 0),
+    ] {
+        world.fill(row, block).unwrap();
+        let status = world.world_status();
+        assert_eq!(status.total_solid, 10, "{block}");
+        assert_eq!(
+            (status.total_good, status.total_evil, status.total_blood),
+            (good, evil, blood),
+            "{block}"
+        );
+    }
+}